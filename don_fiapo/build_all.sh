@@ -52,6 +52,7 @@ build_contract "staking" "Staking"
 build_contract "rewards" "Rewards"
 build_contract "affiliate" "Affiliate"
 build_contract "noble_affiliate" "Noble Affiliate"
+build_contract "boost_registry" "Boost Registry"
 
 # ===== Phase 3: Products =====
 echo "═══════════════════════════════════════════"
@@ -4,13 +4,14 @@
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-use fiapo_traits::{AccountId, Balance};
+use fiapo_traits::{AccountId, Balance, BoostSource, BoostTarget};
 
 #[ink::contract]
 mod fiapo_affiliate {
     use super::*;
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
+    use fiapo_logics::traits::boost::BoostRegistryCall;
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -19,6 +20,7 @@ mod fiapo_affiliate {
         CannotReferSelf,
         InvalidReferrer,
         Unauthorized,
+        BoostRegistryNotSet,
     }
 
     /// Constantes de configuração
@@ -114,6 +116,12 @@ mod fiapo_affiliate {
         /// Top 100 afiliados (ordenado por earnings DESC, referrals DESC)
         /// Armazenamos apenas IDs para economizar espaço no vetor packed.
         top_affiliates: Vec<AccountId>,
+        /// Boost Registry (recebe o boost de referidos de cada affiliate)
+        boost_registry: Option<AccountId>,
+        /// Governança pode atualizar a configuração
+        governance_contract: Option<AccountId>,
+        /// Timelock das propostas: atualiza a configuração como a governança
+        timelock_contract: Option<AccountId>,
     }
 
     impl FiapoAffiliate {
@@ -131,6 +139,9 @@ mod fiapo_affiliate {
                 level2_commission_bps: 100, // 1%
                 total_affiliates: 0,
                 top_affiliates: Vec::new(),
+                boost_registry: None,
+                governance_contract: None,
+                timelock_contract: None,
             }
         }
 
@@ -400,31 +411,140 @@ mod fiapo_affiliate {
                 stats.current_boost_bps = new_boost;
                 stats.active_referrals = active_count;
                 self.stats.insert(referrer, &stats);
+
+                self.publish_boost(referrer, new_boost);
             }
 
             Ok(())
         }
 
+        /// Migração: publica no Boost Registry o boost atual de referrers que já
+        /// existiam antes do registro (apenas owner); retorna quantos publicou
+        #[ink(message)]
+        pub fn publish_referrer_boosts(&mut self, referrers: Vec<AccountId>) -> Result<u32, AffiliateError> {
+            if self.env().caller() != self.owner {
+                return Err(AffiliateError::Unauthorized);
+            }
+            if self.boost_registry.is_none() {
+                return Err(AffiliateError::BoostRegistryNotSet);
+            }
+            let mut published = 0u32;
+            for referrer in referrers {
+                let boost = self.calculate_apy_boost(referrer);
+                if boost > 0 && self.publish_boost(referrer, boost) {
+                    published = published.saturating_add(1);
+                }
+            }
+            Ok(published)
+        }
+
+        /// Boost por referidos não expira; é republicado a cada mudança
+        fn publish_boost(&self, referrer: AccountId, boost: u32) -> bool {
+            match self.boost_registry {
+                Some(registry) => BoostRegistryCall::post_boost(
+                    registry, referrer, BoostSource::Affiliate, BoostTarget::StakingApy,
+                    boost, u64::MAX,
+                ),
+                None => false,
+            }
+        }
+
         /// Retorna atividade de um referido
         #[ink(message)]
         pub fn get_referral_activity(&self, user: AccountId) -> ReferralActivity {
             self.activities.get(user).unwrap_or_default()
         }
 
-        /// Atualiza configuração (apenas owner)
+        /// Define o Boost Registry (apenas owner)
         #[ink(message)]
-        pub fn update_config(&mut self, config: AffiliateConfig) -> Result<(), AffiliateError> {
+        pub fn set_boost_registry(&mut self, registry: Option<AccountId>) -> Result<(), AffiliateError> {
             if self.env().caller() != self.owner {
                 return Err(AffiliateError::Unauthorized);
             }
+            self.boost_registry = registry;
+            Ok(())
+        }
+
+        /// Atualiza configuração (owner ou governança)
+        #[ink(message)]
+        pub fn update_config(&mut self, config: AffiliateConfig) -> Result<(), AffiliateError> {
+            let caller = self.env().caller();
+            if caller != self.owner
+                && Some(caller) != self.governance_contract
+                && Some(caller) != self.timelock_contract
+            {
+                return Err(AffiliateError::Unauthorized);
+            }
             self.config = config;
             Ok(())
         }
+
+        #[ink(message)]
+        pub fn set_governance_contract(&mut self, governance: Option<AccountId>) -> Result<(), AffiliateError> {
+            if self.env().caller() != self.owner {
+                return Err(AffiliateError::Unauthorized);
+            }
+            self.governance_contract = governance;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_timelock_contract(&mut self, timelock: Option<AccountId>) -> Result<(), AffiliateError> {
+            if self.env().caller() != self.owner {
+                return Err(AffiliateError::Unauthorized);
+            }
+            self.timelock_contract = timelock;
+            Ok(())
+        }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use fiapo_logics::traits::affiliate::AffiliateConfigParams;
+        use scale::{Decode, Encode};
+
+        #[ink::test]
+        fn governance_updates_config_via_mirror() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoAffiliate::new(accounts.charlie);
+            contract.set_governance_contract(Some(accounts.eve)).unwrap();
+
+            let params = AffiliateConfigParams {
+                enabled: false,
+                boost_per_affiliate_bps: 100,
+                max_boost_bps: 800,
+                min_staking_for_active: 500 * SCALE,
+            };
+            let config = AffiliateConfig::decode(&mut &params.encode()[..]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.update_config(config.clone()), Err(AffiliateError::Unauthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.update_config(config.clone()), Ok(()));
+            assert_eq!(contract.config, config);
+
+            // Propostas executadas pelo Timelock também passam
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_timelock_contract(Some(accounts.frank)).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.update_config(config), Ok(()));
+        }
+
+        #[ink::test]
+        fn referrer_boost_migration_requires_owner_and_registry() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoAffiliate::new(accounts.charlie);
+
+            assert_eq!(contract.publish_referrer_boosts(vec![accounts.bob]), Err(AffiliateError::BoostRegistryNotSet));
+            contract.set_boost_registry(Some(accounts.django)).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.publish_referrer_boosts(vec![accounts.bob]), Err(AffiliateError::Unauthorized));
+
+            // Referrer sem referidos ativos não tem boost a publicar
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.publish_referrer_boosts(vec![accounts.bob]), Ok(0));
+        }
 
         #[ink::test]
         fn constructor_works() {
@@ -0,0 +1,407 @@
+//! # Fiapo Boost Registry
+//!
+//! Registro unificado de boosts do ecossistema Don Fiapo.
+//! Fontes autorizadas publicam boosts temporários (fonte, bps, expiração) por usuário:
+//! - Affiliate: boost por referidos ativos
+//! - Spin Game: prêmio de boost da Royal Wheel
+//! - NFT Mining: boost de mineração do ICO
+//! - Burn: boost por volume de queima
+//!
+//! Staking e ICO leem o valor agregado, limitado por caps por fonte e global.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use fiapo_traits::{BoostSource, BoostTarget};
+
+#[ink::contract]
+mod fiapo_boost_registry {
+    use super::*;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+
+    /// Caps padrão (bps)
+    pub const DEFAULT_AFFILIATE_CAP_BPS: u32 = 500;
+    pub const DEFAULT_SPIN_CAP_BPS: u32 = 200;
+    pub const DEFAULT_NFT_MINING_CAP_BPS: u32 = 100;
+    pub const DEFAULT_BURN_CAP_BPS: u32 = 2000;
+    pub const DEFAULT_STAKING_GLOBAL_CAP_BPS: u32 = 2500;
+    pub const DEFAULT_MINING_GLOBAL_CAP_BPS: u32 = 100;
+
+    /// Chave de um boost: (usuário, fonte, alvo)
+    pub type BoostKey = (AccountId, BoostSource, BoostTarget);
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum BoostRegistryError {
+        Unauthorized,
+        InvalidExpiry,
+        BoostNotFound,
+    }
+
+    /// Boost publicado por uma fonte
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct BoostEntry {
+        pub source: BoostSource,
+        pub target: BoostTarget,
+        /// Valor bruto publicado (o cap da fonte é aplicado na leitura)
+        pub bps: u32,
+        pub expires_at: u64,
+        pub posted_by: AccountId,
+        pub posted_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct BoostPosted {
+        #[ink(topic)]
+        user: AccountId,
+        source: BoostSource,
+        target: BoostTarget,
+        bps: u32,
+        expires_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct BoostRevoked {
+        #[ink(topic)]
+        user: AccountId,
+        source: BoostSource,
+        target: BoostTarget,
+    }
+
+    #[ink(event)]
+    pub struct SourceAuthorizationChanged {
+        #[ink(topic)]
+        account: AccountId,
+        source: BoostSource,
+        authorized: bool,
+    }
+
+    #[ink(storage)]
+    pub struct FiapoBoostRegistry {
+        owner: AccountId,
+        /// Contas autorizadas a publicar por fonte
+        authorized: Mapping<(BoostSource, AccountId), bool>,
+        boosts: Mapping<BoostKey, BoostEntry>,
+        source_caps: Mapping<BoostSource, u32>,
+        global_caps: Mapping<BoostTarget, u32>,
+    }
+
+    impl FiapoBoostRegistry {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let mut contract = Self {
+                owner: Self::env().caller(),
+                authorized: Mapping::default(),
+                boosts: Mapping::default(),
+                source_caps: Mapping::default(),
+                global_caps: Mapping::default(),
+            };
+
+            contract.source_caps.insert(BoostSource::Affiliate, &DEFAULT_AFFILIATE_CAP_BPS);
+            contract.source_caps.insert(BoostSource::SpinGame, &DEFAULT_SPIN_CAP_BPS);
+            contract.source_caps.insert(BoostSource::NftMining, &DEFAULT_NFT_MINING_CAP_BPS);
+            contract.source_caps.insert(BoostSource::Burn, &DEFAULT_BURN_CAP_BPS);
+            contract.global_caps.insert(BoostTarget::StakingApy, &DEFAULT_STAKING_GLOBAL_CAP_BPS);
+            contract.global_caps.insert(BoostTarget::Mining, &DEFAULT_MINING_GLOBAL_CAP_BPS);
+            contract
+        }
+
+        // ==================== Views ====================
+
+        #[ink(message)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Boost agregado (bps) de um usuário para um destino, já com caps aplicados
+        #[ink(message)]
+        pub fn get_boost(&self, user: AccountId, target: BoostTarget) -> u32 {
+            let total = self.get_user_boosts(user, target)
+                .iter()
+                .fold(0u32, |acc, entry| acc.saturating_add(self.capped_bps(entry)));
+            total.min(self.get_global_cap(target))
+        }
+
+        /// Boost médio (bps) ponderado pelo tempo em `[from, to)`, com caps. Cada
+        /// boost conta só entre `posted_at` e `expires_at`, então um boost recém
+        /// publicado não retroage sobre o intervalo.
+        #[ink(message)]
+        pub fn get_boost_over(&self, user: AccountId, target: BoostTarget, from: u64, to: u64) -> u32 {
+            if to <= from {
+                return 0;
+            }
+            let entries: Vec<BoostEntry> = (0..BoostSource::COUNT)
+                .filter_map(BoostSource::from_u8)
+                .filter_map(|source| self.boosts.get((user, source, target)))
+                .collect();
+
+            let mut points: Vec<u64> = ink::prelude::vec![from, to];
+            for entry in entries.iter() {
+                points.extend([entry.posted_at, entry.expires_at].iter().filter(|t| **t > from && **t < to));
+            }
+            points.sort_unstable();
+            points.dedup();
+
+            let global_cap = self.get_global_cap(target);
+            let weighted = points.windows(2).fold(0u128, |acc, segment| {
+                let bps = entries.iter()
+                    .filter(|entry| entry.posted_at <= segment[0] && entry.expires_at > segment[0])
+                    .fold(0u32, |sum, entry| sum.saturating_add(self.capped_bps(entry)))
+                    .min(global_cap);
+                acc.saturating_add((bps as u128).saturating_mul(segment[1].saturating_sub(segment[0]) as u128))
+            });
+            weighted.checked_div(to.saturating_sub(from) as u128).unwrap_or(0) as u32
+        }
+
+        /// Boosts ativos (não expirados) de um usuário para um destino
+        #[ink(message)]
+        pub fn get_user_boosts(&self, user: AccountId, target: BoostTarget) -> Vec<BoostEntry> {
+            let now = self.env().block_timestamp();
+            (0..BoostSource::COUNT)
+                .filter_map(BoostSource::from_u8)
+                .filter_map(|source| self.boosts.get((user, source, target)))
+                .filter(|entry| entry.expires_at > now)
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_source_cap(&self, source: BoostSource) -> u32 {
+            self.source_caps.get(source).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_global_cap(&self, target: BoostTarget) -> u32 {
+            self.global_caps.get(target).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn is_authorized(&self, source: BoostSource, account: AccountId) -> bool {
+            self.authorized.get((source, account)).unwrap_or(false)
+        }
+
+        // ==================== Sources ====================
+
+        /// Publica (ou substitui) o boost de uma fonte para o usuário.
+        /// `bps = 0` remove o boost da fonte.
+        #[ink(message)]
+        pub fn post_boost(
+            &mut self,
+            user: AccountId,
+            source: BoostSource,
+            target: BoostTarget,
+            bps: u32,
+            expires_at: u64,
+        ) -> Result<(), BoostRegistryError> {
+            let caller = self.env().caller();
+            if !self.is_authorized(source, caller) {
+                return Err(BoostRegistryError::Unauthorized);
+            }
+
+            if bps == 0 {
+                self.boosts.remove((user, source, target));
+                Self::env().emit_event(BoostRevoked { user, source, target });
+                return Ok(());
+            }
+
+            let now = self.env().block_timestamp();
+            if expires_at <= now {
+                return Err(BoostRegistryError::InvalidExpiry);
+            }
+
+            self.boosts.insert((user, source, target), &BoostEntry {
+                source,
+                target,
+                bps,
+                expires_at,
+                posted_by: caller,
+                posted_at: now,
+            });
+
+            Self::env().emit_event(BoostPosted {
+                user,
+                source,
+                target,
+                bps,
+                expires_at,
+            });
+
+            Ok(())
+        }
+
+        /// Remove um boost (fonte autorizada ou owner)
+        #[ink(message)]
+        pub fn revoke_boost(&mut self, user: AccountId, source: BoostSource, target: BoostTarget) -> Result<(), BoostRegistryError> {
+            let caller = self.env().caller();
+            if caller != self.owner && !self.is_authorized(source, caller) {
+                return Err(BoostRegistryError::Unauthorized);
+            }
+            if self.boosts.get((user, source, target)).is_none() {
+                return Err(BoostRegistryError::BoostNotFound);
+            }
+
+            self.boosts.remove((user, source, target));
+            Self::env().emit_event(BoostRevoked { user, source, target });
+            Ok(())
+        }
+
+        // ==================== Admin ====================
+
+        #[ink(message)]
+        pub fn set_source_authorization(&mut self, source: BoostSource, account: AccountId, authorized: bool) -> Result<(), BoostRegistryError> {
+            self.ensure_owner()?;
+            if authorized {
+                self.authorized.insert((source, account), &true);
+            } else {
+                self.authorized.remove((source, account));
+            }
+            Self::env().emit_event(SourceAuthorizationChanged { account, source, authorized });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_source_cap(&mut self, source: BoostSource, cap_bps: u32) -> Result<(), BoostRegistryError> {
+            self.ensure_owner()?;
+            self.source_caps.insert(source, &cap_bps);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_global_cap(&mut self, target: BoostTarget, cap_bps: u32) -> Result<(), BoostRegistryError> {
+            self.ensure_owner()?;
+            self.global_caps.insert(target, &cap_bps);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), BoostRegistryError> {
+            self.ensure_owner()?;
+            self.owner = new_owner;
+            Ok(())
+        }
+
+        // ==================== Internal ====================
+
+        fn capped_bps(&self, entry: &BoostEntry) -> u32 {
+            entry.bps.min(self.get_source_cap(entry.source))
+        }
+
+        fn ensure_owner(&self) -> Result<(), BoostRegistryError> {
+            if self.env().caller() != self.owner {
+                return Err(BoostRegistryError::Unauthorized);
+            }
+            Ok(())
+        }
+    }
+
+    impl Default for FiapoBoostRegistry {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        type Env = ink::env::DefaultEnvironment;
+
+        fn setup() -> (FiapoBoostRegistry, ink::env::test::DefaultAccounts<Env>) {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut registry = FiapoBoostRegistry::new();
+            registry.set_source_authorization(BoostSource::Affiliate, accounts.bob, true).unwrap();
+            registry.set_source_authorization(BoostSource::SpinGame, accounts.charlie, true).unwrap();
+            (registry, accounts)
+        }
+
+        #[ink::test]
+        fn only_authorized_sources_can_post() {
+            let (mut registry, accounts) = setup();
+            ink::env::test::set_block_timestamp::<Env>(1_000);
+
+            // Bob só está autorizado como Affiliate
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(
+                registry.post_boost(accounts.eve, BoostSource::SpinGame, BoostTarget::StakingApy, 100, 5_000),
+                Err(BoostRegistryError::Unauthorized)
+            );
+            assert_eq!(
+                registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 100, 1_000),
+                Err(BoostRegistryError::InvalidExpiry)
+            );
+            assert!(registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 100, 5_000).is_ok());
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::StakingApy), 100);
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::Mining), 0);
+        }
+
+        #[ink::test]
+        fn aggregates_with_caps_and_expiry() {
+            let (mut registry, accounts) = setup();
+            ink::env::test::set_block_timestamp::<Env>(0);
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 800, u64::MAX).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.charlie);
+            registry.post_boost(accounts.eve, BoostSource::SpinGame, BoostTarget::StakingApy, 150, 10_000).unwrap();
+
+            // Affiliate limitado a 500 pelo cap da fonte
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::StakingApy), 650);
+
+            // Cap global
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            registry.set_global_cap(BoostTarget::StakingApy, 600).unwrap();
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::StakingApy), 600);
+
+            // Boost do spin expira
+            ink::env::test::set_block_timestamp::<Env>(10_000);
+            assert_eq!(registry.get_user_boosts(accounts.eve, BoostTarget::StakingApy).len(), 1);
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::StakingApy), 500);
+        }
+
+        #[ink::test]
+        fn boost_over_interval_is_time_weighted() {
+            let (mut registry, accounts) = setup();
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            registry.set_source_authorization(BoostSource::NftMining, accounts.bob, true).unwrap();
+
+            // Boost publicado no meio do intervalo e expirando antes do fim
+            ink::env::test::set_block_timestamp::<Env>(1_000);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            registry.post_boost(accounts.eve, BoostSource::NftMining, BoostTarget::Mining, 100, 2_000).unwrap();
+
+            ink::env::test::set_block_timestamp::<Env>(4_000);
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::Mining), 0);
+            assert_eq!(registry.get_boost_over(accounts.eve, BoostTarget::Mining, 0, 4_000), 25);
+            assert_eq!(registry.get_boost_over(accounts.eve, BoostTarget::Mining, 1_000, 2_000), 100);
+            assert_eq!(registry.get_boost_over(accounts.eve, BoostTarget::Mining, 2_000, 4_000), 0);
+            assert_eq!(registry.get_boost_over(accounts.eve, BoostTarget::Mining, 4_000, 4_000), 0);
+        }
+
+        #[ink::test]
+        fn revoke_and_zero_post_remove_boost() {
+            let (mut registry, accounts) = setup();
+            ink::env::test::set_block_timestamp::<Env>(0);
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 100, 50).unwrap();
+            registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 0, 0).unwrap();
+            assert_eq!(registry.get_boost(accounts.eve, BoostTarget::StakingApy), 0);
+
+            registry.post_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy, 100, 50).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(
+                registry.revoke_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy),
+                Err(BoostRegistryError::Unauthorized)
+            );
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert!(registry.revoke_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy).is_ok());
+            assert_eq!(
+                registry.revoke_boost(accounts.eve, BoostSource::Affiliate, BoostTarget::StakingApy),
+                Err(BoostRegistryError::BoostNotFound)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "ink-as-dependency")]
+pub use self::fiapo_boost_registry::*;
@@ -7,6 +7,7 @@
 //! - Transaction fees with distribution
 //! - Authorized minting (for ICO, Staking rewards)
 //! - Pause functionality for emergencies
+//! - Per-block balance checkpoints (governance snapshots)
 //! 
 //! ## Security Features
 //! - Reentrancy protection
@@ -17,7 +18,7 @@
 
 use fiapo_traits::{
     AccountId, Balance, PSP22Error, PSP22Result,
-    IPSP22, IPSP22Mintable, IPSP22Burnable,
+    IPSP22, IPSP22Mintable, IPSP22Burnable, IPSP22Votes,
 };
 
 #[ink::contract]
@@ -37,6 +38,9 @@ mod fiapo_core {
     /// Taxa de transação padrão (0.6%)
     pub const TRANSACTION_FEE_BPS: u32 = 60; // 0.6% = 60 basis points
 
+    /// Saldo registrado ao fim de um bloco: (bloco, saldo)
+    pub type Checkpoint = (u32, Balance);
+
     /// Storage do contrato
     #[ink(storage)]
     pub struct FiapoCore {
@@ -70,6 +74,9 @@ mod fiapo_core {
         paused: bool,
         /// Guard de reentrância
         reentrancy_guard: bool,
+        /// Histórico de saldos: (conta, índice) -> (bloco, saldo)
+        balance_checkpoints: Mapping<(AccountId, u32), Checkpoint>,
+        checkpoint_counts: Mapping<AccountId, u32>,
     }
 
     /// Evento de transferência
@@ -135,20 +142,12 @@ mod fiapo_core {
             }
 
             let caller = Self::env().caller();
-            let mut balances = Mapping::default();
-            balances.insert(caller, &initial_supply);
-
-            Self::env().emit_event(Transfer {
-                from: None,
-                to: Some(caller),
-                value: initial_supply,
-            });
 
-            Ok(Self {
+            let mut contract = Self {
                 name,
                 symbol,
                 total_supply: initial_supply,
-                balances,
+                balances: Mapping::default(),
                 allowances: Mapping::default(),
                 owner: caller,
                 authorized_minters: Mapping::default(),
@@ -160,7 +159,18 @@ mod fiapo_core {
                 total_burned: 0,
                 paused: false,
                 reentrancy_guard: false,
-            })
+                balance_checkpoints: Mapping::default(),
+                checkpoint_counts: Mapping::default(),
+            };
+            contract.set_balance(caller, initial_supply);
+
+            Self::env().emit_event(Transfer {
+                from: None,
+                to: Some(caller),
+                value: initial_supply,
+            });
+
+            Ok(contract)
         }
 
         // ==================== PSP22 Metadata ====================
@@ -300,6 +310,43 @@ mod fiapo_core {
             Ok(())
         }
 
+        /// Atualiza o saldo e grava um checkpoint no bloco atual
+        fn set_balance(&mut self, account: AccountId, balance: Balance) {
+            self.balances.insert(account, &balance);
+
+            let block = self.env().block_number();
+            let count = self.checkpoint_counts.get(account).unwrap_or(0);
+            if count > 0 {
+                let last = count.saturating_sub(1);
+                if let Some((last_block, _)) = self.balance_checkpoints.get((account, last)) {
+                    if last_block == block {
+                        self.balance_checkpoints.insert((account, last), &(block, balance));
+                        return;
+                    }
+                }
+            }
+            self.balance_checkpoints.insert((account, count), &(block, balance));
+            self.checkpoint_counts.insert(account, &count.saturating_add(1));
+        }
+
+        /// Busca binária pelo último checkpoint com bloco <= `block`
+        fn checkpoint_at(&self, account: AccountId, block: u32) -> Balance {
+            let (mut low, mut high) = (0u32, self.checkpoint_counts.get(account).unwrap_or(0));
+            while low < high {
+                let mid = low.saturating_add(high.saturating_sub(low) / 2);
+                match self.balance_checkpoints.get((account, mid)) {
+                    Some((checkpoint_block, _)) if checkpoint_block <= block => low = mid.saturating_add(1),
+                    _ => high = mid,
+                }
+            }
+            if low == 0 {
+                return 0;
+            }
+            self.balance_checkpoints.get((account, low.saturating_sub(1)))
+                .map(|(_, balance)| balance)
+                .unwrap_or(0)
+        }
+
         /// Transferência interna sem taxas (para uso de contratos autorizados)
         fn transfer_internal(
             &mut self,
@@ -312,9 +359,9 @@ mod fiapo_core {
                 return Err(PSP22Error::InsufficientBalance);
             }
 
-            self.balances.insert(from, &from_balance.saturating_sub(value));
+            self.set_balance(from, from_balance.saturating_sub(value));
             let to_balance = self.balances.get(to).unwrap_or(0);
-            self.balances.insert(to, &to_balance.saturating_add(value));
+            self.set_balance(to, to_balance.saturating_add(value));
 
             self.env().emit_event(Transfer {
                 from: Some(from),
@@ -342,9 +389,9 @@ mod fiapo_core {
             let net_value = value.saturating_sub(fee);
 
             // Transfere valor líquido
-            self.balances.insert(from, &from_balance.saturating_sub(value));
+            self.set_balance(from, from_balance.saturating_sub(value));
             let to_balance = self.balances.get(to).unwrap_or(0);
-            self.balances.insert(to, &to_balance.saturating_add(net_value));
+            self.set_balance(to, to_balance.saturating_add(net_value));
 
             // Distribui taxa de transação (30% burn, 50% staking, 20% rewards)
             // Conforme regras do monólito fees/distribution.rs
@@ -362,13 +409,13 @@ mod fiapo_core {
                 // Distribui para staking
                 if staking_amount > 0 {
                     let staking_balance = self.balances.get(self.staking_wallet).unwrap_or(0);
-                    self.balances.insert(self.staking_wallet, &staking_balance.saturating_add(staking_amount));
+                    self.set_balance(self.staking_wallet, staking_balance.saturating_add(staking_amount));
                 }
 
                 // Distribui para rewards
                 if rewards_amount > 0 {
                     let rewards_balance = self.balances.get(self.rewards_wallet).unwrap_or(0);
-                    self.balances.insert(self.rewards_wallet, &rewards_balance.saturating_add(rewards_amount));
+                    self.set_balance(self.rewards_wallet, rewards_balance.saturating_add(rewards_amount));
                 }
             }
 
@@ -530,6 +577,28 @@ mod fiapo_core {
             let result = contract.transfer(accounts.bob, 100 * SCALE);
             assert!(result.is_ok());
         }
+
+        #[ink::test]
+        fn balance_checkpoints_track_history() {
+            let mut contract = create_contract();
+            let accounts = default_accounts();
+            let initial = contract.balance_of(accounts.alice);
+
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            contract.burn(1000 * SCALE).unwrap();
+            contract.burn(1000 * SCALE).unwrap();
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            contract.burn(1000 * SCALE).unwrap();
+
+            // Várias alterações no mesmo bloco ocupam um único checkpoint
+            assert_eq!(contract.checkpoint_counts.get(accounts.alice), Some(3));
+            assert_eq!(contract.balance_of_at(accounts.alice, 0), initial);
+            assert_eq!(contract.balance_of_at(accounts.alice, 1), initial);
+            assert_eq!(contract.balance_of_at(accounts.alice, 2), initial - 2000 * SCALE);
+            assert_eq!(contract.balance_of_at(accounts.alice, 10), contract.balance_of(accounts.alice));
+            assert_eq!(contract.balance_of_at(accounts.bob, 10), 0);
+        }
     }
 
     // ==================== PSP22 Implementation ====================
@@ -612,7 +681,7 @@ mod fiapo_core {
             // Minta tokens
             self.total_supply = new_supply;
             let current_balance = self.balances.get(to).unwrap_or(0);
-            self.balances.insert(to, &current_balance.saturating_add(amount));
+            self.set_balance(to, current_balance.saturating_add(amount));
 
             self.env().emit_event(Mint {
                 to,
@@ -644,7 +713,7 @@ mod fiapo_core {
             }
 
             // Queima
-            self.balances.insert(caller, &balance.saturating_sub(amount));
+            self.set_balance(caller, balance.saturating_sub(amount));
             self.total_supply = self.total_supply.saturating_sub(amount);
             self.total_burned = self.total_burned.saturating_add(amount);
 
@@ -668,7 +737,7 @@ mod fiapo_core {
             }
 
             // Queima
-            self.balances.insert(from, &balance.saturating_sub(amount));
+            self.set_balance(from, balance.saturating_sub(amount));
             self.total_supply = self.total_supply.saturating_sub(amount);
             self.total_burned = self.total_burned.saturating_add(amount);
 
@@ -682,6 +751,15 @@ mod fiapo_core {
         }
     }
 
+    // ==================== Votes Implementation ====================
+
+    impl IPSP22Votes for FiapoCore {
+        #[ink(message)]
+        fn balance_of_at(&self, owner: AccountId, block: u32) -> Balance {
+            self.checkpoint_at(owner, block)
+        }
+    }
+
     // ==================== Tests ====================
 }
 
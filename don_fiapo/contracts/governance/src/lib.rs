@@ -6,7 +6,8 @@
 //! - Criação de Proposta: 100 USDT (Solana via Oráculo) + 1000 FIAPO + Exige Staking Ativo.
 //! - Votação: 10 USDT (Solana via Oráculo) + 100 FIAPO + Exige Staking Ativo.
 //! - Limite Anti-Spam: Máximo 10 votos por hora por usuário.
-//! - Distribuição FIAPO: 40% Equipe, 25% Staking, 20% Rewards, 5% Noble, 10% Burn.
+//! - Distribuição (FIAPO ou LUSDT): 40% Equipe, 25% Staking, 20% Rewards, 5% Noble, 10% Burn.
+//! - Propostas aprovadas são executadas via Timelock, sujeitas a veto dos guardiões.
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
@@ -15,14 +16,55 @@ mod fiapo_governance {
     use ink::prelude::{string::String, vec::Vec};
     use ink::storage::Mapping;
     // Cross-contract references (pure ink!, no OpenBrush)
-    use fiapo_logics::traits::staking::{Staking, StakingRef};
-    use fiapo_logics::traits::rewards::RewardsCall;
-    use fiapo_logics::traits::oracle::{Oracle, OracleRef};
-    use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
+    use fiapo_logics::traits::staking::{PoolConfigParams, Staking, StakingRef};
+    use fiapo_logics::traits::rewards::{RewardsCall, ScoringWeightsParams};
+    use fiapo_logics::traits::affiliate::AffiliateConfigParams;
+    use fiapo_logics::traits::oracle::{Oracle, OracleRef, PaymentPurpose};
+    use fiapo_logics::traits::psp22::{PSP22, PSP22Ref, PSP22Votes, PSP22VotesRef};
+    use fiapo_logics::traits::timelock::{dispatch_raw, OperationKind, OperationState, TimelockCall};
+    use fiapo_logics::traits::treasury::VestingScheduleParams;
+    use fiapo_traits::{IGovernance, PSP22Error, PSP22Result};
+    use scale::Encode;
 
-    /// Constantes 
-    pub const HOUR: u64 = 3600;
+    /// Constantes (prazos em ms, como `block_timestamp` e o Timelock)
+    pub const MINUTE: u64 = 60 * 1000;
+    pub const HOUR: u64 = 60 * MINUTE;
+    pub const DAY: u64 = 24 * HOUR;
     pub const SCALE: u128 = 100_000_000;
+    pub const LUSDT_SCALE: u128 = 1_000_000;
+    pub const MAX_PROPOSAL_CALLS: usize = 10;
+    /// Limite de delegadores por delegado: cada um custa até 3 chamadas entre
+    /// contratos no voto do delegado
+    pub const MAX_DELEGATORS: u32 = 20;
+    pub const MAX_GUARDIANS: usize = 20;
+    /// Limites aceitos em `ProposalAction`
+    pub const MAX_MARKETPLACE_FEE_BPS: u16 = 1000;
+    pub const MAX_AFFILIATE_BOOST_BPS: u32 = 2000;
+    pub const MAX_POOL_APY_BPS: u16 = 30000;
+    pub const MAX_VOTES_PER_HOUR: u32 = 100;
+    pub const MAX_GRANT_MILESTONES: usize = 12;
+    /// Separação de domínio das mensagens assinadas off-chain
+    pub const SIGNED_VOTE_DOMAIN: &[u8] = b"DonFiapo:governance:vote:v1";
+    pub const SIGNING_KEY_DOMAIN: &[u8] = b"DonFiapo:governance:signing-key:v1";
+    pub const MAX_SIGNED_VOTES_PER_BATCH: usize = 50;
+    pub const MAX_MERKLE_PROOF_DEPTH: usize = 32;
+    /// Merkle roots por proposta (custo da apuração)
+    pub const MAX_SIGNED_VOTE_ROOTS: u32 = 10;
+    /// Janela após `voting_end` para liquidar folhas de um merkle root
+    pub const SIGNED_VOTE_SETTLEMENT_PERIOD: u64 = DAY;
+    pub const MAX_REMUNERATION_SHARE_BPS: u32 = 5000;
+    /// Pontos de score de governança por participação
+    pub const VOTE_SCORE_POINTS: u32 = 1;
+    pub const APPROVED_PROPOSAL_SCORE_POINTS: u32 = 5;
+
+    /// Delegação registrada ao fim de um bloco: (bloco, delegado)
+    pub type DelegationCheckpoint = (u32, Option<AccountId>);
+
+    /// Chave dos índices de listagem: (status, tipo); `None` = qualquer
+    pub type ProposalFilter = (Option<ProposalStatus>, Option<ProposalType>);
+
+    /// Folha de merkle root liquidada: (proposta, relayer, folha)
+    pub type SettledLeaf = (u64, AccountId, [u8; 32]);
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -45,6 +87,35 @@ mod fiapo_governance {
         RateLimitExceeded,
         OraclePaymentNotConfirmed,
         TxHashAlreadyUsed,
+        ProposalNotApproved,
+        TimelockSchedulingFailed,
+        VoteNotFound,
+        InvalidDelegate,
+        DelegationCycle,
+        TooManyDelegators,
+        NotDelegated,
+        VotingNotStarted,
+        VotingStarted,
+        NotGuardian,
+        VetoAlreadyApproved,
+        InvalidAction,
+        ActionTargetNotSet,
+        RewardAlreadyClaimed,
+        NothingToClaim,
+        PaymentAssetNotConfigured,
+        OraclePriceUnavailable,
+        FeeAboveMaximum,
+        NotRelayer,
+        InvalidSignature,
+        RootAlreadyCommitted,
+        SettlementPending,
+        DelegationsNotAccepted,
+    }
+
+    impl From<GovernanceError> for PSP22Error {
+        fn from(error: GovernanceError) -> Self {
+            PSP22Error::Custom(ink::prelude::format!("{:?}", error))
+        }
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -64,6 +135,10 @@ mod fiapo_governance {
         Approved,
         Rejected,
         Executed,
+        Canceled,
+        Vetoed,
+        /// Aprovada, mas operações no Timelock expiraram ou foram canceladas sem executar
+        Expired,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -74,6 +149,225 @@ mod fiapo_governance {
         Abstain,
     }
 
+    /// Forma de pagamento da parte em USDT das taxas
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum FeePayment {
+        /// USDT na Solana confirmado pelo oráculo (hash da transação)
+        OracleUsdt(String),
+        /// LUSDT on-chain via `transfer_from` (exige allowance)
+        Lusdt,
+        /// Equivalente em FIAPO ao preço do oráculo, limitado a `max_amount`
+        Fiapo { max_amount: Balance },
+    }
+
+    /// Ponderação do poder de voto a partir do saldo no snapshot
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum VotingFormula {
+        /// 1 FIAPO = 1 voto
+        Linear,
+        /// Raiz quadrada do saldo (em unidades FIAPO), reduz a influência de whales
+        SquareRoot,
+        /// Linear até o teto por conta
+        Capped(Balance),
+    }
+
+    impl VotingFormula {
+        pub fn apply(&self, power: Balance) -> Balance {
+            match self {
+                VotingFormula::Linear => power,
+                VotingFormula::SquareRoot => integer_sqrt(power.saturating_mul(SCALE)),
+                VotingFormula::Capped(cap) => power.min(*cap),
+            }
+        }
+    }
+
+    /// Raiz quadrada inteira (arredondada para baixo) pelo método de Newton
+    fn integer_sqrt(value: u128) -> u128 {
+        if value < 2 {
+            return value;
+        }
+        let mut x = value;
+        let mut y = x / 2 + 1;
+        while y < x {
+            x = y;
+            y = (x + value / x) / 2;
+        }
+        x
+    }
+
+    /// Chamada cross-contract anexada a uma proposta (selector + args SCALE)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ProposalCall {
+        pub target: AccountId,
+        pub selector: [u8; 4],
+        pub input: Vec<u8>,
+        pub transferred_value: Balance,
+    }
+
+    impl ProposalCall {
+        pub fn call_data(&self) -> Vec<u8> {
+            let mut data = Vec::with_capacity(self.input.len().saturating_add(4));
+            data.extend_from_slice(&self.selector);
+            data.extend_from_slice(&self.input);
+            data
+        }
+    }
+
+    /// Alteração de parâmetro aplicada automaticamente se a proposta for aprovada
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum ProposalAction {
+        /// `FiapoMarketplace::set_fee_bps`
+        SetMarketplaceFee(u16),
+        /// `FiapoGovernance::update_config` (aplicada localmente na execução)
+        UpdateGovernanceConfig(GovernanceConfig),
+        /// `FiapoAffiliate::update_config`
+        UpdateAffiliateConfig(AffiliateConfigParams),
+        /// `FiapoRewards::update_scoring_weights`
+        UpdateScoringWeights(ScoringWeightsParams),
+        /// `FiapoStaking::set_pool_config`
+        SetStakingPoolConfig { pool: u8, config: PoolConfigParams },
+        /// `FiapoTreasury::create_grant` (asset: 0 = Fiapo, 1 = Lunes, 2 = Lusdt), liberada
+        /// em marcos ou fluxo linear. Só em propostas Marketing/Development.
+        TreasurySpend { recipient: AccountId, asset: u8, total: Balance, schedule: VestingScheduleParams },
+        /// `FiapoTreasury::claw_back`: recupera o não liberado de uma concessão
+        TreasuryClawBack(u64),
+    }
+
+    impl ProposalAction {
+        /// Limites verificados na criação da proposta
+        pub fn validate(&self) -> Result<(), GovernanceError> {
+            let valid = match self {
+                ProposalAction::SetMarketplaceFee(fee_bps) => *fee_bps <= MAX_MARKETPLACE_FEE_BPS,
+                ProposalAction::UpdateGovernanceConfig(config) => {
+                    config.max_votes_per_hour > 0
+                        && config.max_votes_per_hour <= MAX_VOTES_PER_HOUR
+                        && config.remuneration_share_bps <= MAX_REMUNERATION_SHARE_BPS
+                        && config.voting_formula != VotingFormula::Capped(0)
+                }
+                ProposalAction::UpdateAffiliateConfig(config) => {
+                    config.max_boost_bps <= MAX_AFFILIATE_BOOST_BPS
+                        && config.boost_per_affiliate_bps <= config.max_boost_bps
+                }
+                ProposalAction::UpdateScoringWeights(weights) => {
+                    [
+                        weights.balance_weight,
+                        weights.staking_weight,
+                        weights.burn_weight,
+                        weights.transaction_weight,
+                        weights.affiliate_weight,
+                        weights.governance_weight,
+                    ].iter().map(|&weight| weight as u16).sum::<u16>() == 100
+                }
+                ProposalAction::SetStakingPoolConfig { pool, config } => {
+                    *pool <= 2
+                        && config.apy_bps <= MAX_POOL_APY_BPS
+                        && config.early_withdrawal_penalty_bps <= 10000
+                        && config.cancellation_penalty_bps <= 10000
+                        && config.instant_exit_fee_bps <= 10000
+                        && config.payment_frequency_days > 0
+                        && config.reward_units_per_fiapo > 0
+                        && config.reward_asset <= 2
+                        // Taxa de entrada: FIAPO (0) ou LUSDT (2)
+                        && (config.fee_asset == 0 || config.fee_asset == 2)
+                }
+                ProposalAction::TreasurySpend { asset, total, schedule, .. } => {
+                    *asset <= 2 && *total > 0 && match schedule {
+                        VestingScheduleParams::Milestones(milestones) => {
+                            !milestones.is_empty()
+                                && milestones.len() <= MAX_GRANT_MILESTONES
+                                && milestones.iter().all(|milestone| milestone.amount > 0)
+                                && milestones.iter().fold(0, |acc: Balance, milestone| acc.saturating_add(milestone.amount)) == *total
+                        }
+                        VestingScheduleParams::Linear { cliff, duration } => *duration > 0 && cliff <= duration,
+                    }
+                }
+                ProposalAction::TreasuryClawBack(_) => true,
+            };
+            if valid { Ok(()) } else { Err(GovernanceError::InvalidAction) }
+        }
+    }
+
+    /// Voto registrado de uma conta em uma proposta
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct VoteRecord {
+        pub vote: Vote,
+        /// Peso aplicado ao placar (fórmula sobre o saldo no snapshot)
+        pub weight: Balance,
+        /// Parte de `weight` vinda de delegadores
+        pub delegated_weight: Balance,
+        pub voted_at: u64,
+    }
+
+    /// Poder de um voto: `staked` conta para o quórum, `weight` para o placar
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct VotePower {
+        pub staked: Balance,
+        pub weight: Balance,
+        pub delegated_weight: Balance,
+    }
+
+    /// Voto assinado off-chain: ECDSA (65 bytes) sobre `signed_vote_hash`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SignedVote {
+        pub voter: AccountId,
+        pub vote: Vote,
+        pub signature: [u8; 65],
+    }
+
+    /// Compromisso de um relayer com um conjunto de votos assinados
+    /// (folha = blake2_256 do `SignedVote` codificado; pares ordenados).
+    /// `settled` conta folhas provadas, aceitas ou não; os totais são o que os
+    /// votos aceitos somam à proposta, descontados se o root ficar incompleto.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct SignedVoteRoot {
+        pub relayer: AccountId,
+        pub root: [u8; 32],
+        pub leaf_count: u32,
+        pub settled: u32,
+        pub votes_for: Balance,
+        pub votes_against: Balance,
+        pub votes_abstain: Balance,
+        pub turnout: Balance,
+    }
+
+    impl SignedVoteRoot {
+        fn apply(&mut self, vote: &Vote, weight: Balance, staked: Balance, add: bool) {
+            let tally = match vote {
+                Vote::For => &mut self.votes_for,
+                Vote::Against => &mut self.votes_against,
+                Vote::Abstain => &mut self.votes_abstain,
+            };
+            if add {
+                *tally = tally.saturating_add(weight);
+                self.turnout = self.turnout.saturating_add(staked);
+            } else {
+                *tally = tally.saturating_sub(weight);
+                self.turnout = self.turnout.saturating_sub(staked);
+            }
+        }
+
+        fn weight(&self) -> Balance {
+            self.votes_for.saturating_add(self.votes_against).saturating_add(self.votes_abstain)
+        }
+    }
+
+    /// Poder de um delegador computado no voto do seu delegado
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DelegatedVote {
+        pub delegate: AccountId,
+        pub staked: Balance,
+        pub weight: Balance,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct Proposal {
@@ -81,39 +375,107 @@ mod fiapo_governance {
         pub proposer: AccountId,
         pub proposal_type: ProposalType,
         pub description: String,
+        pub voting_start: u64,
         pub voting_end: u64,
+        /// Fim do timelock: `voting_end + timelock_period` do tipo; se as chamadas forem
+        /// agendadas no Timelock, passa a contar da finalização
         pub execution_time: u64,
         pub status: ProposalStatus,
-        pub votes_for: u32,
-        pub votes_against: u32,
-        pub votes_abstain: u32,
+        pub votes_for: Balance,
+        pub votes_against: Balance,
+        pub votes_abstain: Balance,
         pub executed: bool,
+        pub calls: Vec<ProposalCall>,
+        /// Ações tipadas; as externas também constam em `calls`
+        pub actions: Vec<ProposalAction>,
+        /// Operações agendadas no Timelock na finalização
+        pub timelock_operations: Vec<u64>,
+        /// Bloco do snapshot de poder de voto (anterior ao de criação)
+        pub snapshot_block: u32,
+        /// Instante do snapshot do poder veFIAPO (anterior ao de criação)
+        pub snapshot_time: u64,
+        /// Total em staking no snapshot (base do quórum)
+        pub quorum_base: Balance,
+        /// Stake no snapshot dos votantes, incluindo abstenções
+        pub turnout: Balance,
+        /// Limites do tipo da proposta fixados na criação
+        pub quorum_bps: u32,
+        pub approval_threshold_bps: u32,
+        /// Taxa FIAPO de criação em custódia até a apuração
+        pub fee_escrow: Balance,
+        /// Parte das taxas retida para os votantes (sacada pro-rata ao peso)
+        pub voter_pool: Balance,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub struct GovernanceConfig {
-        pub quorum_bps: u32,
-        pub voting_period: u64,
-        pub timelock_period: u64,
         pub proposal_fee_fiapo: Balance,
         pub proposal_fee_usdt_cents: u64, // 100 USDT = 10000
         pub vote_fee_fiapo: Balance,
         pub vote_fee_usdt_cents: u64, // 10 USDT = 1000
         pub max_votes_per_hour: u32,
+        pub voting_formula: VotingFormula,
+        /// Soma o saldo FIAPO (checkpoint do core) ao stake no cálculo do peso
+        pub include_token_balance: bool,
+        /// Fatia das taxas FIAPO de voto retida como pool da proposta, sacada pelos
+        /// votantes pro-rata ao peso após a apuração
+        pub remuneration_share_bps: u32,
     }
 
     impl Default for GovernanceConfig {
         fn default() -> Self {
             Self {
-                quorum_bps: 5100, 
-                voting_period: 3 * 86400,
-                timelock_period: 86400,
                 proposal_fee_fiapo: 1000 * SCALE,
                 proposal_fee_usdt_cents: 10000, 
                 vote_fee_fiapo: 100 * SCALE,
                 vote_fee_usdt_cents: 1000, 
                 max_votes_per_hour: 10,
+                voting_formula: VotingFormula::Linear,
+                include_token_balance: false,
+                remuneration_share_bps: 2000,
+            }
+        }
+    }
+
+    /// Prazos e limites de um tipo de proposta
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct ProposalTypeConfig {
+        /// Intervalo entre criação e início da votação (janela de cancelamento). Prazos em ms.
+        pub voting_delay: u64,
+        pub voting_period: u64,
+        pub timelock_period: u64,
+        /// Participação mínima (`turnout`) sobre `Proposal::quorum_base`
+        pub quorum_bps: u32,
+        /// Fatia mínima de votos a favor entre a favor + contra
+        pub approval_threshold_bps: u32,
+    }
+
+    impl ProposalTypeConfig {
+        pub fn default_for(proposal_type: &ProposalType) -> Self {
+            match proposal_type {
+                ProposalType::Emergency => Self {
+                    voting_delay: 10 * MINUTE,
+                    voting_period: DAY,
+                    timelock_period: 6 * HOUR,
+                    quorum_bps: 6600,
+                    approval_threshold_bps: 6600,
+                },
+                ProposalType::Upgrade => Self {
+                    voting_delay: DAY,
+                    voting_period: 5 * DAY,
+                    timelock_period: 2 * DAY,
+                    quorum_bps: 5100,
+                    approval_threshold_bps: 6000,
+                },
+                _ => Self {
+                    voting_delay: DAY,
+                    voting_period: 3 * DAY,
+                    timelock_period: DAY,
+                    quorum_bps: 5100,
+                    approval_threshold_bps: 5000,
+                },
             }
         }
     }
@@ -133,6 +495,155 @@ mod fiapo_governance {
         #[ink(topic)]
         voter: AccountId,
         vote: Vote,
+        weight: Balance,
+    }
+
+    #[ink(event)]
+    pub struct VoteChanged {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        voter: AccountId,
+        previous: Vote,
+        vote: Vote,
+    }
+
+    #[ink(event)]
+    pub struct DelegateChanged {
+        #[ink(topic)]
+        delegator: AccountId,
+        from: Option<AccountId>,
+        to: Option<AccountId>,
+    }
+
+    #[ink(event)]
+    pub struct DelegatedVoteOverridden {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        delegator: AccountId,
+        delegate: AccountId,
+        weight: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProposalCanceled {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        proposer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct VetoApproved {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        guardian: AccountId,
+        approvals: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProposalVetoed {
+        #[ink(topic)]
+        proposal_id: u64,
+        /// Operações do Timelock canceladas com sucesso
+        cancelled_operations: u32,
+    }
+
+    #[ink(event)]
+    pub struct ProposalFinalized {
+        #[ink(topic)]
+        proposal_id: u64,
+        status: ProposalStatus,
+        votes_for: Balance,
+        votes_against: Balance,
+        votes_abstain: Balance,
+        quorum_reached: bool,
+    }
+
+    #[ink(event)]
+    pub struct VoteRewardClaimed {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        voter: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct ProposalFeeRefunded {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        proposer: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct SignedVoteRootCommitted {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        relayer: AccountId,
+        root: [u8; 32],
+        leaf_count: u32,
+    }
+
+    /// Root não liquidado por completo na janela: seus votos saem da apuração
+    #[ink(event)]
+    pub struct SignedVoteRootDiscarded {
+        #[ink(topic)]
+        proposal_id: u64,
+        #[ink(topic)]
+        relayer: AccountId,
+        settled: u32,
+        leaf_count: u32,
+    }
+
+    /// Resultado de um lote de votos assinados (inválidos ou repetidos são ignorados)
+    #[ink(event)]
+    pub struct SignedVotesSettled {
+        #[ink(topic)]
+        proposal_id: u64,
+        submitter: AccountId,
+        accepted: u32,
+        rejected: u32,
+    }
+
+    /// Parte em USDT paga on-chain; `token` é o ativo recebido
+    #[ink(event)]
+    pub struct UsdtFeePaid {
+        #[ink(topic)]
+        payer: AccountId,
+        token: AccountId,
+        amount: Balance,
+        usdt_cents: u64,
+    }
+
+    #[ink(event)]
+    pub struct ProposalScheduled {
+        #[ink(topic)]
+        proposal_id: u64,
+        operation_ids: Vec<u64>,
+    }
+
+    #[ink(event)]
+    pub struct ProposalCallExecuted {
+        #[ink(topic)]
+        proposal_id: u64,
+        index: u32,
+        target: AccountId,
+        success: bool,
+    }
+
+    #[ink(event)]
+    pub struct ProposalExecuted {
+        #[ink(topic)]
+        proposal_id: u64,
+        executor: AccountId,
+        succeeded: u32,
+        failed: u32,
     }
 
     #[ink(storage)]
@@ -151,6 +662,69 @@ mod fiapo_governance {
         next_proposal_id: u64,
         is_active: bool,
         owner: AccountId,
+        /// Timelock onde as chamadas aprovadas são agendadas (governança deve ser admin)
+        timelock_contract: Option<AccountId>,
+        votes: Mapping<(u64, AccountId), VoteRecord>,
+        /// Índice de votantes por proposta: (proposta, posição) -> votante
+        voters: Mapping<(u64, u32), AccountId>,
+        voter_count: Mapping<u64, u32>,
+        /// Delegado atual de cada conta
+        delegates: Mapping<AccountId, AccountId>,
+        /// Delegadores atuais: (delegado, posição) -> delegador
+        delegators: Mapping<(AccountId, u32), AccountId>,
+        delegator_count: Mapping<AccountId, u32>,
+        delegator_index: Mapping<AccountId, u32>,
+        /// Contas que aceitam receber delegações (opt-in)
+        accepts_delegations: Mapping<AccountId, bool>,
+        /// Histórico de delegação para checagem no snapshot
+        delegation_checkpoints: Mapping<(AccountId, u32), DelegationCheckpoint>,
+        delegation_checkpoint_counts: Mapping<AccountId, u32>,
+        /// Delegadores já computados no voto do delegado, por proposta
+        delegated_votes: Mapping<(u64, AccountId), DelegatedVote>,
+        /// Configuração por tipo (ausente = `ProposalTypeConfig::default_for`)
+        type_configs: Mapping<ProposalType, ProposalTypeConfig>,
+        /// Conselho de guardiões: M aprovações de veto entre N guardiões
+        guardians: Vec<AccountId>,
+        guardian_threshold: u32,
+        veto_approvals: Mapping<(u64, AccountId), bool>,
+        /// Alvos de `ProposalAction` além de staking/rewards
+        marketplace_contract: Option<AccountId>,
+        affiliate_contract: Option<AccountId>,
+        treasury_contract: Option<AccountId>,
+        /// Relayers autorizados a enviar votos assinados
+        relayers: Mapping<AccountId, bool>,
+        /// blake2_256(chave ECDSA) registrada -> conta (sem registro: conta = o próprio hash)
+        vote_signing_keys: Mapping<[u8; 32], AccountId>,
+        account_signing_key: Mapping<AccountId, [u8; 32]>,
+        /// Chamadas/ações de propostas já executadas com sucesso (índice na ordem
+        /// de execução); as que falharam podem ser re-tentadas
+        executed_calls: Mapping<(u64, u32), bool>,
+        /// Um merkle root por (proposta, relayer)
+        signed_vote_roots: Mapping<(u64, AccountId), SignedVoteRoot>,
+        signed_vote_root_count: Mapping<u64, u32>,
+        /// (proposta, posição) -> relayer do root
+        signed_vote_root_relayers: Mapping<(u64, u32), AccountId>,
+        settled_leaves: Mapping<SettledLeaf, bool>,
+        /// Relayer do root que registrou o voto de (proposta, votante)
+        root_votes: Mapping<(u64, AccountId), AccountId>,
+        /// Votos retransmitidos (sem taxa) não participam do pool de votantes
+        relayed_votes: Mapping<(u64, AccountId), bool>,
+        relayed_weight: Mapping<u64, Balance>,
+        /// Índices de listagem mantidos na escrita: (filtro, posição) -> proposta
+        proposal_index: Mapping<(ProposalFilter, u32), u64>,
+        proposal_index_len: Mapping<ProposalFilter, u32>,
+        proposal_index_position: Mapping<(ProposalFilter, u64), u32>,
+        /// Propostas por proponente, em ordem de criação
+        proposer_proposals: Mapping<(AccountId, u32), u64>,
+        proposer_proposal_count: Mapping<AccountId, u32>,
+        /// Saques de remuneração já feitos: (proposta, votante)
+        reward_claims: Mapping<(u64, AccountId), bool>,
+        /// Soma dos pools de votantes ainda não sacados
+        remuneration_pool: Balance,
+        /// Score de participação publicado no Rewards
+        governance_scores: Mapping<AccountId, u32>,
+        /// Token LUSDT aceito como alternativa ao USDT via oráculo
+        lusdt_contract: Option<AccountId>,
     }
 
     impl FiapoGovernance {
@@ -172,6 +746,45 @@ mod fiapo_governance {
                 next_proposal_id: 1,
                 is_active: true,
                 owner: caller,
+                timelock_contract: None,
+                votes: Mapping::default(),
+                voters: Mapping::default(),
+                voter_count: Mapping::default(),
+                delegates: Mapping::default(),
+                delegators: Mapping::default(),
+                delegator_count: Mapping::default(),
+                accepts_delegations: Mapping::default(),
+                delegator_index: Mapping::default(),
+                delegation_checkpoints: Mapping::default(),
+                delegation_checkpoint_counts: Mapping::default(),
+                delegated_votes: Mapping::default(),
+                type_configs: Mapping::default(),
+                guardians: Vec::new(),
+                guardian_threshold: 0,
+                veto_approvals: Mapping::default(),
+                marketplace_contract: None,
+                affiliate_contract: None,
+                treasury_contract: None,
+                relayers: Mapping::default(),
+                vote_signing_keys: Mapping::default(),
+                account_signing_key: Mapping::default(),
+                executed_calls: Mapping::default(),
+                signed_vote_roots: Mapping::default(),
+                signed_vote_root_count: Mapping::default(),
+                signed_vote_root_relayers: Mapping::default(),
+                settled_leaves: Mapping::default(),
+                root_votes: Mapping::default(),
+                relayed_votes: Mapping::default(),
+                relayed_weight: Mapping::default(),
+                proposal_index: Mapping::default(),
+                proposal_index_len: Mapping::default(),
+                proposal_index_position: Mapping::default(),
+                proposer_proposals: Mapping::default(),
+                proposer_proposal_count: Mapping::default(),
+                reward_claims: Mapping::default(),
+                remuneration_pool: 0,
+                governance_scores: Mapping::default(),
+                lusdt_contract: None,
             }
         }
 
@@ -180,54 +793,137 @@ mod fiapo_governance {
             &mut self,
             proposal_type: ProposalType,
             description: String,
-            usdt_tx_hash: String,
+            payment: FeePayment,
+        ) -> Result<u64, GovernanceError> {
+            self.create_proposal_with_calls(proposal_type, description, Vec::new(), payment)
+        }
+
+        /// Cria uma proposta com chamadas a executar se aprovada. A taxa FIAPO fica em
+        /// custódia: devolvida ao proponente se aprovada, senão distribuída.
+        #[ink(message)]
+        pub fn create_proposal_with_calls(
+            &mut self,
+            proposal_type: ProposalType,
+            description: String,
+            calls: Vec<ProposalCall>,
+            payment: FeePayment,
+        ) -> Result<u64, GovernanceError> {
+            self.create_proposal_internal(proposal_type, description, calls, Vec::new(), payment)
+        }
+
+        /// Cria uma proposta de alteração de parâmetros aplicada automaticamente se aprovada
+        #[ink(message)]
+        pub fn create_action_proposal(
+            &mut self,
+            proposal_type: ProposalType,
+            description: String,
+            actions: Vec<ProposalAction>,
+            payment: FeePayment,
+        ) -> Result<u64, GovernanceError> {
+            if actions.is_empty() {
+                return Err(GovernanceError::InvalidAction);
+            }
+            self.create_proposal_internal(proposal_type, description, Vec::new(), actions, payment)
+        }
+
+        fn create_proposal_internal(
+            &mut self,
+            proposal_type: ProposalType,
+            description: String,
+            mut calls: Vec<ProposalCall>,
+            actions: Vec<ProposalAction>,
+            payment: FeePayment,
         ) -> Result<u64, GovernanceError> {
             let caller = self.env().caller();
             if !self.is_active { return Err(GovernanceError::GovernanceDisabled); }
+            if calls.len().saturating_add(actions.len()) > MAX_PROPOSAL_CALLS {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            for action in actions.iter() {
+                action.validate()?;
+                // Gastos da tesouraria só em propostas de Marketing/Desenvolvimento
+                if matches!(action, ProposalAction::TreasurySpend { .. })
+                    && !matches!(proposal_type, ProposalType::Marketing | ProposalType::Development)
+                {
+                    return Err(GovernanceError::InvalidAction);
+                }
+                if let Some(call) = self.action_call(action)? {
+                    calls.push(call);
+                }
+            }
 
-            // 1. Verificação de Staking
-            self.ensure_has_staking(caller)?;
+            // 1. Verificação de Staking no snapshot da nova proposta
+            let snapshot_block = self.env().block_number().saturating_sub(1);
+            self.ensure_has_staking(caller, snapshot_block)?;
 
-            // 2. Verificação USDT via Oráculo
-            self.verify_oracle_usdt(usdt_tx_hash, caller, self.config.proposal_fee_usdt_cents)?;
+            // 2. Parte em USDT (oráculo, LUSDT ou FIAPO)
+            self.collect_usdt_fee(caller, payment, self.config.proposal_fee_usdt_cents, String::from("Proposal"))?;
 
-            // 3. Coleta de FIAPO Local
-            self.collect_fiapo_fees(caller, self.config.proposal_fee_fiapo, String::from("Proposal"))?;
+            // 3. Taxa FIAPO em custódia até a apuração
+            let fee_escrow = self.config.proposal_fee_fiapo;
+            if fee_escrow > 0 {
+                self.call_token_transfer_from(self.core_contract, caller, self.env().account_id(), fee_escrow)?;
+            }
 
             let current_time = self.env().block_timestamp();
             let proposal_id = self.next_proposal_id;
+            let type_config = self.get_type_config(proposal_type.clone());
+            let voting_start = current_time.saturating_add(type_config.voting_delay);
+            let voting_end = voting_start.saturating_add(type_config.voting_period);
 
             let proposal = Proposal {
                 id: proposal_id,
                 proposer: caller,
                 proposal_type,
                 description,
-                voting_end: current_time.saturating_add(self.config.voting_period),
-                execution_time: current_time.saturating_add(self.config.voting_period).saturating_add(self.config.timelock_period),
+                voting_start,
+                voting_end,
+                execution_time: voting_end.saturating_add(type_config.timelock_period),
                 status: ProposalStatus::Active,
                 votes_for: 0,
                 votes_against: 0,
                 votes_abstain: 0,
                 executed: false,
+                calls,
+                actions,
+                timelock_operations: Vec::new(),
+                snapshot_block,
+                snapshot_time: current_time.saturating_sub(1),
+                quorum_base: self.fetch_total_staked(snapshot_block),
+                turnout: 0,
+                quorum_bps: type_config.quorum_bps,
+                approval_threshold_bps: type_config.approval_threshold_bps,
+                fee_escrow,
+                voter_pool: 0,
             };
 
             self.proposals.insert(proposal_id, &proposal);
+            self.index_new_proposal(&proposal);
             self.next_proposal_id = self.next_proposal_id.saturating_add(1);
 
             Self::env().emit_event(ProposalCreated { proposal_id, proposer: caller });
             Ok(proposal_id)
         }
 
+        /// Vota com o poder do snapshot, incluindo o dos delegadores que ainda não
+        /// votaram, pagando uma única taxa
         #[ink(message)]
-        pub fn vote(&mut self, proposal_id: u64, vote: Vote, usdt_tx_hash: String) -> Result<(), GovernanceError> {
+        pub fn vote(&mut self, proposal_id: u64, vote: Vote, payment: FeePayment) -> Result<(), GovernanceError> {
             let caller = self.env().caller();
             let current_time = self.env().block_timestamp();
             let hour_index = current_time / HOUR;
 
             if !self.is_active { return Err(GovernanceError::GovernanceDisabled); }
 
-            // 1. Verificação de Staking
-            self.ensure_has_staking(caller)?;
+            // Um voto por conta; mudanças de voto usam `change_vote` (sem nova taxa)
+            self.ensure_can_vote(proposal_id, caller, current_time)?;
+
+            // 1. Poder no snapshot: stake próprio + delegadores que ainda não votaram
+            let proposal = self.active_proposal(proposal_id, current_time)?;
+            let (power, delegated) = self.vote_power(&proposal, caller);
+            if power.staked == 0 {
+                return Err(GovernanceError::StakingRequired);
+            }
 
             // 2. Rate Limit (10 votos/hora)
             let count = self.hourly_vote_count.get((caller, hour_index)).unwrap_or(0);
@@ -235,84 +931,1250 @@ mod fiapo_governance {
                 return Err(GovernanceError::RateLimitExceeded);
             }
 
-            // 3. Verificação USDT via Oráculo
-            self.verify_oracle_usdt(usdt_tx_hash, caller, self.config.vote_fee_usdt_cents)?;
-
-            // 4. Coleta de FIAPO
-            self.collect_fiapo_fees(caller, self.config.vote_fee_fiapo, String::from("Vote"))?;
+            // 3. Parte em USDT (uma taxa por voto, com ou sem delegações)
+            self.collect_usdt_fee(caller, payment, self.config.vote_fee_usdt_cents, String::from("Vote"))?;
 
-            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
-            if proposal.status != ProposalStatus::Active || current_time > proposal.voting_end {
-                return Err(GovernanceError::ProposalNotActive);
-            }
+            // 4. Coleta de FIAPO (a parte de remuneração vai para o pool da proposta)
+            let retained = self.collect_fiapo_fees(caller, self.config.vote_fee_fiapo, String::from("Vote"))?;
 
             self.hourly_vote_count.insert((caller, hour_index), &(count.saturating_add(1)));
-            
-            match vote {
-                Vote::For => proposal.votes_for = proposal.votes_for.saturating_add(1),
-                Vote::Against => proposal.votes_against = proposal.votes_against.saturating_add(1),
-                Vote::Abstain => proposal.votes_abstain = proposal.votes_abstain.saturating_add(1),
+            self.record_vote(proposal_id, caller, vote, power, current_time)?;
+            for (delegator, delegated_vote) in delegated {
+                self.delegated_votes.insert((proposal_id, delegator), &delegated_vote);
             }
-
-            self.proposals.insert(proposal_id, &proposal);
-            Self::env().emit_event(VoteCast { proposal_id, voter: caller, vote });
+            self.fund_voter_pool(proposal_id, retained);
+            self.add_governance_score(caller, VOTE_SCORE_POINTS);
             Ok(())
         }
 
+        /// Delega o poder de voto a `to`, que precisa aceitar delegações. Exige stake
+        /// no bloco atual. Delegação tem um nível: quem delega não pode receber
+        /// delegações e vice-versa (impede ciclos).
         #[ink(message)]
-        pub fn staking_contract(&self) -> Option<AccountId> {
-            self.staking_contract
+        pub fn delegate(&mut self, to: AccountId) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            if !self.is_active { return Err(GovernanceError::GovernanceDisabled); }
+            self.ensure_has_staking(caller, self.env().block_number())?;
+            self.delegate_account(caller, to)
         }
 
         #[ink(message)]
-        pub fn rewards_contract(&self) -> Option<AccountId> {
-            self.rewards_contract
+        pub fn undelegate(&mut self) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            let previous = self.delegates.get(caller).ok_or(GovernanceError::NotDelegated)?;
+            self.clear_delegation(caller, previous);
+            Ok(())
         }
 
+        /// Passa a aceitar (ou recusar) novas delegações. Quem aceita não pode
+        /// delegar; recusar mantém os delegadores atuais (ver `evict_delegators`).
         #[ink(message)]
-        pub fn oracle_contract(&self) -> Option<AccountId> {
-            self.oracle_multisig
+        pub fn set_accepts_delegations(&mut self, accept: bool) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            if accept {
+                if self.delegates.contains(caller) {
+                    return Err(GovernanceError::DelegationCycle);
+                }
+                self.accepts_delegations.insert(caller, &true);
+            } else {
+                self.accepts_delegations.remove(caller);
+            }
+            Ok(())
         }
 
         #[ink(message)]
-        pub fn test_ping(&self) -> u32 {
-            if let Some(staking_addr) = self.staking_contract {
-                // Usa StakingRef — selector correto do trait Staking::ping
-                let staking: StakingRef = staking_addr.into();
-                return staking.ping();
+        pub fn accepts_delegations(&self, account: AccountId) -> bool {
+            self.accepts_delegations.get(account).unwrap_or(false)
+        }
+
+        /// Delegado remove delegadores, liberando vagas de `MAX_DELEGATORS`;
+        /// retorna quantos foram removidos
+        #[ink(message)]
+        pub fn evict_delegators(&mut self, delegators: Vec<AccountId>) -> Result<u32, GovernanceError> {
+            let caller = self.env().caller();
+            let mut evicted = 0u32;
+            for delegator in delegators {
+                if self.delegates.get(delegator) != Some(caller) {
+                    return Err(GovernanceError::NotDelegated);
+                }
+                self.clear_delegation(delegator, caller);
+                evicted = evicted.saturating_add(1);
             }
-            0
+            Ok(evicted)
         }
 
         #[ink(message)]
-        pub fn test_ping_manual(&self) -> u32 {
-            // Mesma lógica que test_ping — ambos usam trait selector agora
-            self.test_ping()
+        pub fn get_delegate(&self, account: AccountId) -> Option<AccountId> {
+            self.delegates.get(account)
         }
 
-        /// Test getting user positions using StakingRef wrapper
+        /// Delegado de `account` ao fim do bloco `block`
         #[ink(message)]
-        pub fn test_staking_call(&self, account: AccountId) -> Vec<u64> {
-            if let Some(staking_addr) = self.staking_contract {
-                // Staking implements the Staking trait — selector matches
-                let staking: StakingRef = staking_addr.into();
-                return staking.get_user_positions(account);
+        pub fn get_delegate_at(&self, account: AccountId, block: u32) -> Option<AccountId> {
+            let (mut low, mut high) = (0u32, self.delegation_checkpoint_counts.get(account).unwrap_or(0));
+            while low < high {
+                let mid = low.saturating_add(high.saturating_sub(low) / 2);
+                match self.delegation_checkpoints.get((account, mid)) {
+                    Some((checkpoint_block, _)) if checkpoint_block <= block => low = mid.saturating_add(1),
+                    _ => high = mid,
+                }
             }
-            Vec::new()
+            if low == 0 {
+                return None;
+            }
+            self.delegation_checkpoints.get((account, low.saturating_sub(1))).and_then(|(_, delegate)| delegate)
         }
 
-        // ==================== Private Helpers ====================
-
-        fn verify_oracle_usdt(&mut self, tx_hash: String, user: AccountId, expected_cents: u64) -> Result<(), GovernanceError> {
-            if self.used_tx_hashes.get(&tx_hash).unwrap_or(false) {
-                return Err(GovernanceError::TxHashAlreadyUsed);
-            }
+        #[ink(message)]
+        pub fn get_delegator_count(&self, delegate: AccountId) -> u32 {
+            self.delegator_count.get(delegate).unwrap_or(0)
+        }
+
+        /// Delegadores atuais de `delegate` (paginado)
+        #[ink(message)]
+        pub fn get_delegators(&self, delegate: AccountId, offset: u32, limit: u32) -> Vec<AccountId> {
+            let end = offset.saturating_add(limit.min(100)).min(self.get_delegator_count(delegate));
+            (offset..end)
+                .filter_map(|index| self.delegators.get((delegate, index)))
+                .collect()
+        }
+
+        /// Peso que os delegadores somariam ao voto de `delegate` na proposta
+        #[ink(message)]
+        pub fn get_delegated_power(&self, proposal_id: u64, delegate: AccountId) -> Balance {
+            let Some(proposal) = self.proposals.get(proposal_id) else { return 0 };
+            self.collect_delegated(&proposal, delegate)
+                .iter()
+                .fold(0, |acc, (_, delegated_vote)| acc.saturating_add(delegated_vote.weight))
+        }
+
+        #[ink(message)]
+        pub fn get_delegated_vote(&self, proposal_id: u64, delegator: AccountId) -> Option<DelegatedVote> {
+            self.delegated_votes.get((proposal_id, delegator))
+        }
+
+        /// Hash assinado pelo votante: domínio + este contrato + proposta + conta + voto
+        #[ink(message)]
+        pub fn signed_vote_hash(&self, proposal_id: u64, voter: AccountId, vote: Vote) -> [u8; 32] {
+            Self::blake2_256(&(SIGNED_VOTE_DOMAIN, self.env().account_id(), proposal_id, voter, vote).encode())
+        }
+
+        /// Hash que a chave de assinatura assina para ser vinculada a `account`
+        #[ink(message)]
+        pub fn signing_key_hash(&self, account: AccountId) -> [u8; 32] {
+            Self::blake2_256(&(SIGNING_KEY_DOMAIN, self.env().account_id(), account).encode())
+        }
+
+        /// Vincula ao caller a chave ECDSA que assinou `signing_key_hash(caller)`
+        /// (necessário para contas sr25519; contas ECDSA assinam diretamente)
+        #[ink(message)]
+        pub fn register_vote_signing_key(&mut self, signature: [u8; 65]) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            let key = Self::recover_key(&signature, &self.signing_key_hash(caller))
+                .map(|key| Self::blake2_256(&key))
+                .ok_or(GovernanceError::InvalidSignature)?;
+            if self.vote_signing_keys.get(key).is_some_and(|owner| owner != caller) {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            if let Some(previous) = self.account_signing_key.get(caller) {
+                self.vote_signing_keys.remove(previous);
+            }
+            self.vote_signing_keys.insert(key, &caller);
+            self.account_signing_key.insert(caller, &key);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn clear_vote_signing_key(&mut self) {
+            let caller = self.env().caller();
+            if let Some(previous) = self.account_signing_key.get(caller) {
+                self.vote_signing_keys.remove(previous);
+                self.account_signing_key.remove(caller);
+            }
+        }
+
+        /// Relayer envia votos assinados durante a votação; retorna quantos foram aceitos.
+        /// Sem taxa; o poder é o do snapshot e cada conta vota uma vez por proposta.
+        #[ink(message)]
+        pub fn submit_signed_votes(&mut self, proposal_id: u64, votes: Vec<SignedVote>) -> Result<u32, GovernanceError> {
+            let caller = self.env().caller();
+            self.ensure_relayer(caller)?;
+            if votes.len() > MAX_SIGNED_VOTES_PER_BATCH {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            let now = self.env().block_timestamp();
+            self.active_proposal(proposal_id, now)?;
+
+            let mut accepted: u32 = 0;
+            for signed_vote in votes.iter() {
+                if self.settle_signed_vote(proposal_id, signed_vote, now).is_some() {
+                    accepted = accepted.saturating_add(1);
+                }
+            }
+
+            Self::env().emit_event(SignedVotesSettled {
+                proposal_id,
+                submitter: caller,
+                accepted,
+                rejected: (votes.len() as u32).saturating_sub(accepted),
+            });
+            Ok(accepted)
+        }
+
+        /// Relayer compromete um merkle root de votos assinados durante a votação.
+        /// A apuração aguarda todas as folhas ou o fim da janela de liquidação;
+        /// roots incompletos têm seus votos descartados.
+        #[ink(message)]
+        pub fn commit_signed_vote_root(&mut self, proposal_id: u64, root: [u8; 32], leaf_count: u32) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            self.ensure_relayer(caller)?;
+            self.active_proposal(proposal_id, self.env().block_timestamp())?;
+            if self.signed_vote_roots.contains((proposal_id, caller)) {
+                return Err(GovernanceError::RootAlreadyCommitted);
+            }
+            let roots = self.signed_vote_root_count.get(proposal_id).unwrap_or(0);
+            if leaf_count == 0 || roots >= MAX_SIGNED_VOTE_ROOTS {
+                return Err(GovernanceError::InvalidParameters);
+            }
+
+            self.signed_vote_roots.insert((proposal_id, caller), &SignedVoteRoot {
+                relayer: caller,
+                root,
+                leaf_count,
+                settled: 0,
+                votes_for: 0,
+                votes_against: 0,
+                votes_abstain: 0,
+                turnout: 0,
+            });
+            self.signed_vote_root_relayers.insert((proposal_id, roots), &caller);
+            self.signed_vote_root_count.insert(proposal_id, &roots.saturating_add(1));
+            Self::env().emit_event(SignedVoteRootCommitted { proposal_id, relayer: caller, root, leaf_count });
+            Ok(())
+        }
+
+        /// Liquida folhas do merkle root de `relayer` com suas provas (qualquer um
+        /// pode enviar), entre o fim da votação e o fim da janela de liquidação.
+        /// Votos contam como emitidos no fim da votação.
+        #[ink(message)]
+        pub fn settle_signed_vote_root(
+            &mut self,
+            proposal_id: u64,
+            relayer: AccountId,
+            votes: Vec<(SignedVote, Vec<[u8; 32]>)>,
+        ) -> Result<u32, GovernanceError> {
+            let caller = self.env().caller();
+            if votes.len() > MAX_SIGNED_VOTES_PER_BATCH {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            let mut commitment = self.signed_vote_roots.get((proposal_id, relayer)).ok_or(GovernanceError::ProposalNotFound)?;
+            let proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            let now = self.env().block_timestamp();
+            if proposal.status != ProposalStatus::Active
+                || now > proposal.voting_end.saturating_add(SIGNED_VOTE_SETTLEMENT_PERIOD)
+            {
+                return Err(GovernanceError::ProposalNotActive);
+            }
+            if now <= proposal.voting_end {
+                return Err(GovernanceError::VotingNotFinished);
+            }
+
+            let mut accepted: u32 = 0;
+            for (signed_vote, proof) in votes.iter() {
+                let leaf = Self::blake2_256(&signed_vote.encode());
+                if proof.len() > MAX_MERKLE_PROOF_DEPTH
+                    || commitment.settled >= commitment.leaf_count
+                    || self.settled_leaves.contains((proposal_id, relayer, leaf))
+                    || Self::merkle_root(leaf, proof) != commitment.root
+                {
+                    continue;
+                }
+                self.settled_leaves.insert((proposal_id, relayer, leaf), &true);
+                commitment.settled = commitment.settled.saturating_add(1);
+                // Grava o root antes: o voto pode descontar delegadores de outro voto deste root
+                self.signed_vote_roots.insert((proposal_id, relayer), &commitment);
+                if let Some(power) = self.settle_signed_vote(proposal_id, signed_vote, proposal.voting_end) {
+                    accepted = accepted.saturating_add(1);
+                    commitment = self.signed_vote_roots.get((proposal_id, relayer)).unwrap_or(commitment);
+                    commitment.apply(&signed_vote.vote, power.weight, power.staked, true);
+                    self.root_votes.insert((proposal_id, signed_vote.voter), &relayer);
+                } else {
+                    commitment = self.signed_vote_roots.get((proposal_id, relayer)).unwrap_or(commitment);
+                }
+            }
+            self.signed_vote_roots.insert((proposal_id, relayer), &commitment);
+
+            Self::env().emit_event(SignedVotesSettled {
+                proposal_id,
+                submitter: caller,
+                accepted,
+                rejected: (votes.len() as u32).saturating_sub(accepted),
+            });
+            Ok(accepted)
+        }
+
+        #[ink(message)]
+        pub fn get_signed_vote_root(&self, proposal_id: u64, relayer: AccountId) -> Option<SignedVoteRoot> {
+            self.signed_vote_roots.get((proposal_id, relayer))
+        }
+
+        #[ink(message)]
+        pub fn is_relayer(&self, account: AccountId) -> bool {
+            self.relayers.get(account).unwrap_or(false)
+        }
+
+        /// Altera um voto já registrado enquanto a votação estiver aberta
+        #[ink(message)]
+        pub fn change_vote(&mut self, proposal_id: u64, vote: Vote) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+            if !self.is_active { return Err(GovernanceError::GovernanceDisabled); }
+
+            let mut proposal = self.active_proposal(proposal_id, current_time)?;
+            let mut record = self.votes.get((proposal_id, caller)).ok_or(GovernanceError::VoteNotFound)?;
+            if record.vote == vote {
+                return Ok(());
+            }
+
+            Self::apply_vote(&mut proposal, &record.vote, record.weight, false);
+            Self::apply_vote(&mut proposal, &vote, record.weight, true);
+            self.proposals.insert(proposal_id, &proposal);
+
+            let previous = record.vote;
+            record.vote = vote.clone();
+            record.voted_at = current_time;
+            self.votes.insert((proposal_id, caller), &record);
+
+            Self::env().emit_event(VoteChanged { proposal_id, voter: caller, previous, vote });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_vote(&self, proposal_id: u64, voter: AccountId) -> Option<VoteRecord> {
+            self.votes.get((proposal_id, voter))
+        }
+
+        /// Peso próprio que `account` teria ao votar na proposta (snapshot + fórmula)
+        #[ink(message)]
+        pub fn get_voting_power(&self, proposal_id: u64, account: AccountId) -> Balance {
+            let Some(proposal) = self.proposals.get(proposal_id) else { return 0 };
+            let staked = self.fetch_staked(account, proposal.snapshot_block);
+            if staked == 0 {
+                return 0;
+            }
+            self.voting_weight(account, staked, &proposal)
+        }
+
+        #[ink(message)]
+        pub fn get_voter_count(&self, proposal_id: u64) -> u32 {
+            self.voter_count.get(proposal_id).unwrap_or(0)
+        }
+
+        /// Votantes de uma proposta, em ordem de voto (paginado)
+        #[ink(message)]
+        pub fn get_voters(&self, proposal_id: u64, offset: u32, limit: u32) -> Vec<(AccountId, VoteRecord)> {
+            let total = self.get_voter_count(proposal_id);
+            let end = offset.saturating_add(limit.min(100)).min(total);
+            (offset..end)
+                .filter_map(|index| self.voters.get((proposal_id, index)))
+                .filter_map(|voter| self.votes.get((proposal_id, voter)).map(|record| (voter, record)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_proposal(&self, proposal_id: u64) -> Option<Proposal> {
+            self.proposals.get(proposal_id)
+        }
+
+        /// Quantidade de propostas que atendem ao filtro
+        #[ink(message)]
+        pub fn get_proposal_count(&self, status: Option<ProposalStatus>, proposal_type: Option<ProposalType>) -> u32 {
+            self.proposal_index_len.get((status, proposal_type)).unwrap_or(0)
+        }
+
+        /// Propostas filtradas por status e/ou tipo (paginado). Sem filtro de
+        /// status a ordem é a de criação; mudanças de status reordenam os demais índices.
+        /// Os índices são mantidos na escrita, sem varrer ids.
+        #[ink(message)]
+        pub fn list_proposals(
+            &self,
+            status: Option<ProposalStatus>,
+            proposal_type: Option<ProposalType>,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<Proposal> {
+            let filter: ProposalFilter = (status, proposal_type);
+            let end = offset.saturating_add(limit.min(100)).min(self.proposal_index_len.get(&filter).unwrap_or(0));
+            (offset..end)
+                .filter_map(|index| self.proposal_index.get((filter.clone(), index)))
+                .filter_map(|proposal_id| self.proposals.get(proposal_id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_proposer_proposal_count(&self, proposer: AccountId) -> u32 {
+            self.proposer_proposal_count.get(proposer).unwrap_or(0)
+        }
+
+        /// Propostas criadas por `proposer`, em ordem de criação (paginado)
+        #[ink(message)]
+        pub fn proposals_by_proposer(&self, proposer: AccountId, offset: u32, limit: u32) -> Vec<Proposal> {
+            let end = offset.saturating_add(limit.min(100)).min(self.get_proposer_proposal_count(proposer));
+            (offset..end)
+                .filter_map(|index| self.proposer_proposals.get((proposer, index)))
+                .filter_map(|proposal_id| self.proposals.get(proposal_id))
+                .collect()
+        }
+
+        /// Propostas ainda não apuradas (inclui as com votação encerrada à espera de `finalize_proposal`)
+        #[ink(message)]
+        pub fn active_proposals(&self, offset: u32, limit: u32) -> Vec<Proposal> {
+            self.list_proposals(Some(ProposalStatus::Active), None, offset, limit)
+        }
+
+        /// Apura a proposta após o fim da votação: quórum sobre o total em staking no
+        /// snapshot e limiar de aprovação do `ProposalType`. Aprovada com chamadas
+        /// anexadas, agenda-as no Timelock (se configurado).
+        #[ink(message)]
+        pub fn finalize_proposal(&mut self, proposal_id: u64) -> Result<ProposalStatus, GovernanceError> {
+            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Active {
+                return Err(GovernanceError::ProposalNotActive);
+            }
+            if self.env().block_timestamp() <= proposal.voting_end {
+                return Err(GovernanceError::VotingNotFinished);
+            }
+            self.discard_incomplete_roots(&mut proposal)?;
+
+            let quorum_reached = Self::quorum_reached(&proposal);
+            let approved = quorum_reached && Self::threshold_reached(&proposal);
+            self.set_status(&mut proposal, if approved { ProposalStatus::Approved } else { ProposalStatus::Rejected });
+            if approved {
+                self.add_governance_score(proposal.proposer, APPROVED_PROPOSAL_SCORE_POINTS);
+            } else {
+                self.release_fee_escrow(&mut proposal)?;
+            }
+
+            if approved && !proposal.calls.is_empty() {
+                if let Some(timelock) = self.timelock_contract {
+                    // Atraso do tipo fixado na criação, não o default do Timelock
+                    let timelock_period = proposal.execution_time.saturating_sub(proposal.voting_end);
+                    let mut operation_ids = Vec::new();
+                    for call in proposal.calls.iter() {
+                        let operation_id = TimelockCall::schedule(
+                            timelock,
+                            OperationKind::Custom(String::from("Governance")),
+                            call.target,
+                            call.call_data(),
+                            call.transferred_value,
+                            proposal.description.clone(),
+                            Some(timelock_period),
+                        ).ok_or(GovernanceError::TimelockSchedulingFailed)?;
+                        operation_ids.push(operation_id);
+                    }
+                    proposal.execution_time = self.env().block_timestamp().saturating_add(timelock_period);
+                    proposal.timelock_operations = operation_ids.clone();
+                    Self::env().emit_event(ProposalScheduled { proposal_id, operation_ids });
+                }
+            }
+
+            self.proposals.insert(proposal_id, &proposal);
+            Self::env().emit_event(ProposalFinalized {
+                proposal_id,
+                status: proposal.status.clone(),
+                votes_for: proposal.votes_for,
+                votes_against: proposal.votes_against,
+                votes_abstain: proposal.votes_abstain,
+                quorum_reached,
+            });
+            Ok(proposal.status)
+        }
+
+        /// Executa uma proposta aprovada: via Timelock quando agendada, senão
+        /// despacha as chamadas diretamente após `execution_time`. Falhas
+        /// individuais são reportadas em `ProposalCallExecuted`; a proposta só
+        /// passa a `Executed` quando todas tiverem sucesso, e as que falharam
+        /// podem ser re-tentadas (via Timelock, até a operação expirar).
+        /// Operações já executadas direto no Timelock contam como sucesso; se
+        /// alguma expirou ou foi cancelada, a proposta termina como `Expired`.
+        #[ink(message)]
+        pub fn execute_proposal(&mut self, proposal_id: u64) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            match proposal.status {
+                ProposalStatus::Approved => {}
+                ProposalStatus::Executed => return Err(GovernanceError::ProposalAlreadyExecuted),
+                _ => return Err(GovernanceError::ProposalNotApproved),
+            }
+
+            // Status de cada operação pendente no Timelock (vazio sem Timelock)
+            let mut states: Vec<Option<OperationState>> = Vec::new();
+            if !proposal.timelock_operations.is_empty() {
+                let timelock = self.timelock_contract.ok_or(GovernanceError::TimelockSchedulingFailed)?;
+                for (index, &operation_id) in proposal.timelock_operations.iter().enumerate() {
+                    let state = if self.is_call_executed(proposal_id, index as u32) {
+                        None
+                    } else {
+                        TimelockCall::operation_status(timelock, operation_id)
+                    };
+                    if state == Some(OperationState::Scheduled) {
+                        return Err(GovernanceError::TimelockNotExpired);
+                    }
+                    states.push(state);
+                }
+            } else if self.env().block_timestamp() < proposal.execution_time {
+                return Err(GovernanceError::TimelockNotExpired);
+            }
+
+            let mut succeeded: u32 = 0;
+            let mut failed: u32 = 0;
+            let mut lapsed: u32 = 0;
+            for (index, call) in proposal.calls.iter().enumerate() {
+                let index = index as u32;
+                if self.is_call_executed(proposal_id, index) {
+                    continue;
+                }
+                // Marcada antes do despacho; desfeita se a chamada falhar
+                self.executed_calls.insert((proposal_id, index), &true);
+                let success = match (states.get(index as usize), self.timelock_contract) {
+                    (None, _) => dispatch_raw(call.target, &call.call_data(), call.transferred_value),
+                    (Some(Some(OperationState::Executed)), _) => true,
+                    (Some(Some(OperationState::ReadyToExecute)), Some(timelock)) => proposal.timelock_operations
+                        .get(index as usize)
+                        .map(|&id| TimelockCall::execute(timelock, id))
+                        .unwrap_or(false),
+                    // Expirada ou cancelada: não roda mais
+                    (Some(Some(OperationState::Expired | OperationState::Cancelled)), _) => {
+                        lapsed = lapsed.saturating_add(1);
+                        false
+                    }
+                    // Consulta ao Timelock falhou: re-tentável
+                    _ => false,
+                };
+                if success {
+                    succeeded = succeeded.saturating_add(1);
+                } else {
+                    self.executed_calls.remove((proposal_id, index));
+                    failed = failed.saturating_add(1);
+                }
+                Self::env().emit_event(ProposalCallExecuted {
+                    proposal_id,
+                    index,
+                    target: call.target,
+                    success,
+                });
+            }
+
+            // Ações sobre a própria governança não passam por chamada cross-contract
+            let mut index = proposal.calls.len() as u32;
+            for action in proposal.actions.iter() {
+                if let ProposalAction::UpdateGovernanceConfig(config) = action {
+                    if !self.is_call_executed(proposal_id, index) {
+                        self.executed_calls.insert((proposal_id, index), &true);
+                        self.config = config.clone();
+                        succeeded = succeeded.saturating_add(1);
+                        Self::env().emit_event(ProposalCallExecuted {
+                            proposal_id,
+                            index,
+                            target: self.env().account_id(),
+                            success: true,
+                        });
+                    }
+                    index = index.saturating_add(1);
+                }
+            }
+
+            if failed == 0 {
+                self.set_status(&mut proposal, ProposalStatus::Executed);
+                proposal.executed = true;
+                self.proposals.insert(proposal_id, &proposal);
+            } else if failed == lapsed {
+                self.set_status(&mut proposal, ProposalStatus::Expired);
+                self.proposals.insert(proposal_id, &proposal);
+            }
+
+            Self::env().emit_event(ProposalExecuted {
+                proposal_id,
+                executor: caller,
+                succeeded,
+                failed,
+            });
+            Ok(())
+        }
+
+        /// Se a chamada (ou ação) `index` da proposta já foi executada com sucesso
+        #[ink(message)]
+        pub fn is_call_executed(&self, proposal_id: u64, index: u32) -> bool {
+            self.executed_calls.get((proposal_id, index)).unwrap_or(false)
+        }
+
+        /// Cancela a proposta antes do início da votação (apenas o proponente)
+        #[ink(message)]
+        pub fn cancel_proposal(&mut self, proposal_id: u64) -> Result<(), GovernanceError> {
+            let caller = self.env().caller();
+            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            if caller != proposal.proposer {
+                return Err(GovernanceError::Unauthorized);
+            }
+            if proposal.status != ProposalStatus::Active {
+                return Err(GovernanceError::ProposalNotActive);
+            }
+            if self.env().block_timestamp() >= proposal.voting_start {
+                return Err(GovernanceError::VotingStarted);
+            }
+
+            self.set_status(&mut proposal, ProposalStatus::Canceled);
+            self.release_fee_escrow(&mut proposal)?;
+            self.proposals.insert(proposal_id, &proposal);
+
+            Self::env().emit_event(ProposalCanceled { proposal_id, proposer: caller });
+            Ok(())
+        }
+
+        /// Aprovação de veto por um guardião. Atingido o limite, a proposta
+        /// aprovada é vetada e suas operações no Timelock canceladas.
+        /// Retorna `true` quando o veto é efetivado.
+        #[ink(message)]
+        pub fn veto_proposal(&mut self, proposal_id: u64) -> Result<bool, GovernanceError> {
+            let caller = self.env().caller();
+            if !self.guardians.contains(&caller) {
+                return Err(GovernanceError::NotGuardian);
+            }
+            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            match proposal.status {
+                ProposalStatus::Approved => {}
+                ProposalStatus::Executed => return Err(GovernanceError::ProposalAlreadyExecuted),
+                _ => return Err(GovernanceError::ProposalNotApproved),
+            }
+            if self.veto_approvals.get((proposal_id, caller)).unwrap_or(false) {
+                return Err(GovernanceError::VetoAlreadyApproved);
+            }
+
+            self.veto_approvals.insert((proposal_id, caller), &true);
+            let approvals = self.get_veto_approvals(proposal_id);
+            Self::env().emit_event(VetoApproved { proposal_id, guardian: caller, approvals });
+
+            if approvals < self.guardian_threshold {
+                return Ok(false);
+            }
+
+            self.set_status(&mut proposal, ProposalStatus::Vetoed);
+            self.release_fee_escrow(&mut proposal)?;
+            self.proposals.insert(proposal_id, &proposal);
+
+            let mut cancelled_operations: u32 = 0;
+            if let Some(timelock) = self.timelock_contract {
+                for &operation_id in proposal.timelock_operations.iter() {
+                    if TimelockCall::cancel(timelock, operation_id) {
+                        cancelled_operations = cancelled_operations.saturating_add(1);
+                    }
+                }
+            }
+
+            Self::env().emit_event(ProposalVetoed { proposal_id, cancelled_operations });
+            Ok(true)
+        }
+
+        /// Saca a remuneração do votante em uma proposta apurada
+        #[ink(message)]
+        pub fn claim_vote_reward(&mut self, proposal_id: u64) -> Result<Balance, GovernanceError> {
+            let caller = self.env().caller();
+            let proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            if proposal.status == ProposalStatus::Active {
+                return Err(GovernanceError::VotingNotFinished);
+            }
+            if self.reward_claims.get((proposal_id, caller)).unwrap_or(false) {
+                return Err(GovernanceError::RewardAlreadyClaimed);
+            }
+            let amount = self.pending_vote_reward(proposal_id, caller);
+            if amount == 0 {
+                return Err(GovernanceError::NothingToClaim);
+            }
+
+            self.reward_claims.insert((proposal_id, caller), &true);
+            self.remuneration_pool = self.remuneration_pool.saturating_sub(amount);
+            self.call_token_transfer(self.core_contract, caller, amount)?;
+
+            Self::env().emit_event(VoteRewardClaimed { proposal_id, voter: caller, amount });
+            Ok(amount)
+        }
+
+        /// Devolve a taxa FIAPO de criação ao proponente de uma proposta aprovada,
+        /// após `execution_time` (janela de veto). A taxa USDT não é reembolsável.
+        #[ink(message)]
+        pub fn claim_proposal_refund(&mut self, proposal_id: u64) -> Result<Balance, GovernanceError> {
+            let caller = self.env().caller();
+            let mut proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            if caller != proposal.proposer {
+                return Err(GovernanceError::Unauthorized);
+            }
+            if !matches!(proposal.status, ProposalStatus::Approved | ProposalStatus::Executed | ProposalStatus::Expired) {
+                return Err(GovernanceError::ProposalNotApproved);
+            }
+            if self.env().block_timestamp() < proposal.execution_time {
+                return Err(GovernanceError::TimelockNotExpired);
+            }
+            let amount = proposal.fee_escrow;
+            if amount == 0 {
+                return Err(GovernanceError::NothingToClaim);
+            }
+
+            proposal.fee_escrow = 0;
+            self.proposals.insert(proposal_id, &proposal);
+            self.call_token_transfer(self.core_contract, caller, amount)?;
+
+            Self::env().emit_event(ProposalFeeRefunded { proposal_id, proposer: caller, amount });
+            Ok(amount)
+        }
+
+        /// Remuneração ainda sacável por `voter`: pool da proposta pro-rata ao peso
+        /// dos votos que pagaram taxa (votos retransmitidos não recebem)
+        #[ink(message)]
+        pub fn pending_vote_reward(&self, proposal_id: u64, voter: AccountId) -> Balance {
+            let Some(proposal) = self.proposals.get(proposal_id) else { return 0 };
+            if proposal.status == ProposalStatus::Active
+                || self.reward_claims.get((proposal_id, voter)).unwrap_or(false)
+            {
+                return 0;
+            }
+            if self.relayed_votes.get((proposal_id, voter)).unwrap_or(false) {
+                return 0;
+            }
+            let Some(record) = self.votes.get((proposal_id, voter)) else { return 0 };
+            let total_weight = proposal.votes_for
+                .saturating_add(proposal.votes_against)
+                .saturating_add(proposal.votes_abstain)
+                .saturating_sub(self.relayed_weight.get(proposal_id).unwrap_or(0));
+            proposal.voter_pool
+                .saturating_mul(record.weight)
+                .checked_div(total_weight)
+                .unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_remuneration_pool(&self) -> Balance {
+            self.remuneration_pool
+        }
+
+        #[ink(message)]
+        pub fn get_governance_score(&self, account: AccountId) -> u32 {
+            self.governance_scores.get(account).unwrap_or(0)
+        }
+
+        /// Aprovações de veto de guardiões atuais
+        #[ink(message)]
+        pub fn get_veto_approvals(&self, proposal_id: u64) -> u32 {
+            self.guardians.iter()
+                .filter(|&&guardian| self.veto_approvals.get((proposal_id, guardian)).unwrap_or(false))
+                .count() as u32
+        }
+
+        #[ink(message)]
+        pub fn get_guardians(&self) -> (Vec<AccountId>, u32) {
+            (self.guardians.clone(), self.guardian_threshold)
+        }
+
+        #[ink(message)]
+        pub fn get_type_config(&self, proposal_type: ProposalType) -> ProposalTypeConfig {
+            self.type_configs.get(&proposal_type)
+                .unwrap_or_else(|| ProposalTypeConfig::default_for(&proposal_type))
+        }
+
+        #[ink(message)]
+        pub fn timelock_contract(&self) -> Option<AccountId> {
+            self.timelock_contract
+        }
+
+        #[ink(message)]
+        pub fn staking_contract(&self) -> Option<AccountId> {
+            self.staking_contract
+        }
+
+        #[ink(message)]
+        pub fn rewards_contract(&self) -> Option<AccountId> {
+            self.rewards_contract
+        }
+
+        #[ink(message)]
+        pub fn oracle_contract(&self) -> Option<AccountId> {
+            self.oracle_multisig
+        }
+
+        #[ink(message)]
+        pub fn test_ping(&self) -> u32 {
+            if let Some(staking_addr) = self.staking_contract {
+                // Usa StakingRef — selector correto do trait Staking::ping
+                let staking: StakingRef = staking_addr.into();
+                return staking.ping();
+            }
+            0
+        }
+
+        #[ink(message)]
+        pub fn test_ping_manual(&self) -> u32 {
+            // Mesma lógica que test_ping — ambos usam trait selector agora
+            self.test_ping()
+        }
+
+        /// Test getting user positions using StakingRef wrapper
+        #[ink(message)]
+        pub fn test_staking_call(&self, account: AccountId) -> Vec<u64> {
+            if let Some(staking_addr) = self.staking_contract {
+                // Staking implements the Staking trait — selector matches
+                let staking: StakingRef = staking_addr.into();
+                return staking.get_user_positions(account);
+            }
+            Vec::new()
+        }
+
+        // ==================== Private Helpers ====================
+
+        /// Chamada ao setter alvo da ação (`None` para ações locais)
+        fn action_call(&self, action: &ProposalAction) -> Result<Option<ProposalCall>, GovernanceError> {
+            let (target, selector, input) = match action {
+                ProposalAction::UpdateGovernanceConfig(_) => return Ok(None),
+                ProposalAction::SetMarketplaceFee(fee_bps) => {
+                    (self.marketplace_contract, ink::selector_bytes!("set_fee_bps"), fee_bps.encode())
+                }
+                ProposalAction::UpdateAffiliateConfig(config) => {
+                    (self.affiliate_contract, ink::selector_bytes!("update_config"), config.encode())
+                }
+                ProposalAction::UpdateScoringWeights(weights) => {
+                    (self.rewards_contract, ink::selector_bytes!("update_scoring_weights"), weights.encode())
+                }
+                ProposalAction::SetStakingPoolConfig { pool, config } => {
+                    (self.staking_contract, ink::selector_bytes!("set_pool_config"), (pool, config).encode())
+                }
+                ProposalAction::TreasurySpend { recipient, asset, total, schedule } => {
+                    (self.treasury_contract, ink::selector_bytes!("create_grant"), (recipient, asset, total, schedule).encode())
+                }
+                ProposalAction::TreasuryClawBack(grant_id) => {
+                    (self.treasury_contract, ink::selector_bytes!("claw_back"), grant_id.encode())
+                }
+            };
+            Ok(Some(ProposalCall {
+                target: target.ok_or(GovernanceError::ActionTargetNotSet)?,
+                selector,
+                input,
+                transferred_value: 0,
+            }))
+        }
+
+        fn active_proposal(&self, proposal_id: u64, now: u64) -> Result<Proposal, GovernanceError> {
+            let proposal = self.proposals.get(proposal_id).ok_or(GovernanceError::ProposalNotFound)?;
+            if proposal.status != ProposalStatus::Active || now > proposal.voting_end {
+                return Err(GovernanceError::ProposalNotActive);
+            }
+            if now < proposal.voting_start {
+                return Err(GovernanceError::VotingNotStarted);
+            }
+            Ok(proposal)
+        }
+
+        fn index_new_proposal(&mut self, proposal: &Proposal) {
+            let status = proposal.status.clone();
+            let proposal_type = proposal.proposal_type.clone();
+            self.index_proposal((None, None), proposal.id);
+            self.index_proposal((None, Some(proposal_type.clone())), proposal.id);
+            self.index_proposal((Some(status.clone()), None), proposal.id);
+            self.index_proposal((Some(status), Some(proposal_type)), proposal.id);
+
+            let count = self.get_proposer_proposal_count(proposal.proposer);
+            self.proposer_proposals.insert((proposal.proposer, count), &proposal.id);
+            self.proposer_proposal_count.insert(proposal.proposer, &count.saturating_add(1));
+        }
+
+        /// Altera o status movendo a proposta entre os índices por status
+        fn set_status(&mut self, proposal: &mut Proposal, status: ProposalStatus) {
+            for proposal_type in [None, Some(proposal.proposal_type.clone())] {
+                self.unindex_proposal((Some(proposal.status.clone()), proposal_type.clone()), proposal.id);
+                self.index_proposal((Some(status.clone()), proposal_type), proposal.id);
+            }
+            proposal.status = status;
+        }
+
+        fn index_proposal(&mut self, filter: ProposalFilter, proposal_id: u64) {
+            let len = self.proposal_index_len.get(&filter).unwrap_or(0);
+            self.proposal_index.insert((filter.clone(), len), &proposal_id);
+            self.proposal_index_position.insert((filter.clone(), proposal_id), &len);
+            self.proposal_index_len.insert(filter, &len.saturating_add(1));
+        }
+
+        /// Remove por troca com o último (mesmo esquema de `delegators`)
+        fn unindex_proposal(&mut self, filter: ProposalFilter, proposal_id: u64) {
+            let Some(position) = self.proposal_index_position.get((filter.clone(), proposal_id)) else { return };
+            let last = self.proposal_index_len.get(&filter).unwrap_or(1).saturating_sub(1);
+            if position != last {
+                if let Some(moved) = self.proposal_index.get((filter.clone(), last)) {
+                    self.proposal_index.insert((filter.clone(), position), &moved);
+                    self.proposal_index_position.insert((filter.clone(), moved), &position);
+                }
+            }
+            self.proposal_index.remove((filter.clone(), last));
+            self.proposal_index_position.remove((filter.clone(), proposal_id));
+            self.proposal_index_len.insert(filter, &last);
+        }
+
+        /// Stake próprio + delegadores que ainda não votaram, no snapshot
+        fn vote_power(&self, proposal: &Proposal, voter: AccountId) -> (VotePower, Vec<(AccountId, DelegatedVote)>) {
+            let staked = self.fetch_staked(voter, proposal.snapshot_block);
+            let delegated = self.collect_delegated(proposal, voter);
+            let mut power = VotePower {
+                staked,
+                weight: if staked > 0 { self.voting_weight(voter, staked, proposal) } else { 0 },
+                delegated_weight: 0,
+            };
+            for (_, delegated_vote) in delegated.iter() {
+                power.staked = power.staked.saturating_add(delegated_vote.staked);
+                power.delegated_weight = power.delegated_weight.saturating_add(delegated_vote.weight);
+            }
+            power.weight = power.weight.saturating_add(power.delegated_weight);
+            (power, delegated)
+        }
+
+        /// Registra um voto assinado válido; `false` se assinatura, poder ou
+        /// unicidade não conferem
+        /// Antes do fim da janela de liquidação exige todos os roots completos;
+        /// depois, desconta da proposta os votos dos roots incompletos
+        fn discard_incomplete_roots(&mut self, proposal: &mut Proposal) -> Result<(), GovernanceError> {
+            let proposal_id = proposal.id;
+            let settlement_open = self.env().block_timestamp()
+                <= proposal.voting_end.saturating_add(SIGNED_VOTE_SETTLEMENT_PERIOD);
+            let mut incomplete = Vec::new();
+            for index in 0..self.signed_vote_root_count.get(proposal_id).unwrap_or(0) {
+                let Some(relayer) = self.signed_vote_root_relayers.get((proposal_id, index)) else { continue };
+                let Some(commitment) = self.signed_vote_roots.get((proposal_id, relayer)) else { continue };
+                if commitment.settled < commitment.leaf_count {
+                    if settlement_open {
+                        return Err(GovernanceError::SettlementPending);
+                    }
+                    incomplete.push(commitment);
+                }
+            }
+
+            let mut discarded_weight: Balance = 0;
+            for commitment in incomplete {
+                Self::apply_vote(proposal, &Vote::For, commitment.votes_for, false);
+                Self::apply_vote(proposal, &Vote::Against, commitment.votes_against, false);
+                Self::apply_vote(proposal, &Vote::Abstain, commitment.votes_abstain, false);
+                proposal.turnout = proposal.turnout.saturating_sub(commitment.turnout);
+                discarded_weight = discarded_weight.saturating_add(commitment.weight());
+                Self::env().emit_event(SignedVoteRootDiscarded {
+                    proposal_id,
+                    relayer: commitment.relayer,
+                    settled: commitment.settled,
+                    leaf_count: commitment.leaf_count,
+                });
+            }
+            if discarded_weight > 0 {
+                let relayed = self.relayed_weight.get(proposal_id).unwrap_or(0);
+                self.relayed_weight.insert(proposal_id, &relayed.saturating_sub(discarded_weight));
+            }
+            Ok(())
+        }
+
+        /// Registra um voto assinado válido; retorna o poder computado
+        fn settle_signed_vote(&mut self, proposal_id: u64, signed_vote: &SignedVote, now: u64) -> Option<VotePower> {
+            let voter = signed_vote.voter;
+            if self.votes.contains((proposal_id, voter)) || !self.verify_signed_vote(proposal_id, signed_vote) {
+                return None;
+            }
+            let proposal = self.proposals.get(proposal_id)?;
+            let (power, delegated) = self.vote_power(&proposal, voter);
+            if power.staked == 0 || self.record_vote(proposal_id, voter, signed_vote.vote.clone(), power, now).is_err() {
+                return None;
+            }
+            self.mark_relayed(proposal_id, voter, power.weight);
+            for (delegator, delegated_vote) in delegated {
+                self.delegated_votes.insert((proposal_id, delegator), &delegated_vote);
+            }
+            self.add_governance_score(voter, VOTE_SCORE_POINTS);
+            Some(power)
+        }
+
+        /// Exclui do pool de votantes o peso de um voto sem taxa
+        fn mark_relayed(&mut self, proposal_id: u64, voter: AccountId, weight: Balance) {
+            self.relayed_votes.insert((proposal_id, voter), &true);
+            let relayed = self.relayed_weight.get(proposal_id).unwrap_or(0);
+            self.relayed_weight.insert(proposal_id, &relayed.saturating_add(weight));
+        }
+
+        fn verify_signed_vote(&self, proposal_id: u64, signed_vote: &SignedVote) -> bool {
+            let hash = self.signed_vote_hash(proposal_id, signed_vote.voter, signed_vote.vote.clone());
+            Self::recover_key(&signed_vote.signature, &hash)
+                .map(|key| self.signer_account(&key) == signed_vote.voter)
+                .unwrap_or(false)
+        }
+
+        /// Conta vinculada à chave, ou a conta ECDSA derivada dela
+        fn signer_account(&self, key: &[u8; 33]) -> AccountId {
+            let key_hash = Self::blake2_256(key);
+            self.vote_signing_keys.get(key_hash).unwrap_or_else(|| AccountId::from(key_hash))
+        }
+
+        fn recover_key(signature: &[u8; 65], hash: &[u8; 32]) -> Option<[u8; 33]> {
+            let mut key = [0u8; 33];
+            ink::env::ecdsa_recover(signature, hash, &mut key).ok().map(|_| key)
+        }
+
+        fn blake2_256(input: &[u8]) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(input, &mut output);
+            output
+        }
+
+        /// Raiz a partir da folha e da prova (pares ordenados)
+        fn merkle_root(leaf: [u8; 32], proof: &[[u8; 32]]) -> [u8; 32] {
+            proof.iter().fold(leaf, |node, sibling| {
+                let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+                Self::blake2_256(&[left, right].concat())
+            })
+        }
+
+        fn ensure_relayer(&self, account: AccountId) -> Result<(), GovernanceError> {
+            if !self.is_active { return Err(GovernanceError::GovernanceDisabled); }
+            if !self.is_relayer(account) {
+                return Err(GovernanceError::NotRelayer);
+            }
+            Ok(())
+        }
+
+        fn ensure_can_vote(&self, proposal_id: u64, voter: AccountId, now: u64) -> Result<(), GovernanceError> {
+            self.active_proposal(proposal_id, now)?;
+            if self.votes.contains((proposal_id, voter)) {
+                return Err(GovernanceError::AlreadyVoted);
+            }
+            Ok(())
+        }
+
+        /// Registra o primeiro voto da conta e atualiza o placar. Se o votante
+        /// já havia sido computado pelo seu delegado, essa parte é retirada.
+        fn record_vote(&mut self, proposal_id: u64, voter: AccountId, vote: Vote, power: VotePower, now: u64) -> Result<(), GovernanceError> {
+            self.ensure_can_vote(proposal_id, voter, now)?;
+            let mut proposal = self.active_proposal(proposal_id, now)?;
+
+            self.release_delegated_vote(&mut proposal, voter);
+            Self::apply_vote(&mut proposal, &vote, power.weight, true);
+            proposal.turnout = proposal.turnout.saturating_add(power.staked);
+            self.proposals.insert(proposal_id, &proposal);
+
+            let index = self.get_voter_count(proposal_id);
+            self.voters.insert((proposal_id, index), &voter);
+            self.voter_count.insert(proposal_id, &index.saturating_add(1));
+            self.votes.insert((proposal_id, voter), &VoteRecord {
+                vote: vote.clone(),
+                weight: power.weight,
+                delegated_weight: power.delegated_weight,
+                voted_at: now,
+            });
+
+            Self::env().emit_event(VoteCast { proposal_id, voter, vote, weight: power.weight });
+            Ok(())
+        }
+
+        /// Retira do voto do delegado a parte de `delegator` (voto direto sobrescreve)
+        fn release_delegated_vote(&mut self, proposal: &mut Proposal, delegator: AccountId) {
+            let proposal_id = proposal.id;
+            let Some(delegated_vote) = self.delegated_votes.get((proposal_id, delegator)) else { return };
+            self.delegated_votes.remove((proposal_id, delegator));
+
+            if let Some(mut record) = self.votes.get((proposal_id, delegated_vote.delegate)) {
+                Self::apply_vote(proposal, &record.vote, delegated_vote.weight, false);
+                record.weight = record.weight.saturating_sub(delegated_vote.weight);
+                record.delegated_weight = record.delegated_weight.saturating_sub(delegated_vote.weight);
+                self.votes.insert((proposal_id, delegated_vote.delegate), &record);
+                if self.relayed_votes.get((proposal_id, delegated_vote.delegate)).unwrap_or(false) {
+                    let relayed = self.relayed_weight.get(proposal_id).unwrap_or(0);
+                    self.relayed_weight.insert(proposal_id, &relayed.saturating_sub(delegated_vote.weight));
+                }
+                if let Some(relayer) = self.root_votes.get((proposal_id, delegated_vote.delegate)) {
+                    if let Some(mut commitment) = self.signed_vote_roots.get((proposal_id, relayer)) {
+                        commitment.apply(&record.vote, delegated_vote.weight, delegated_vote.staked, false);
+                        self.signed_vote_roots.insert((proposal_id, relayer), &commitment);
+                    }
+                }
+            }
+            proposal.turnout = proposal.turnout.saturating_sub(delegated_vote.staked);
+
+            Self::env().emit_event(DelegatedVoteOverridden {
+                proposal_id,
+                delegator,
+                delegate: delegated_vote.delegate,
+                weight: delegated_vote.weight,
+            });
+        }
+
+        /// Delegadores atuais que delegavam a `delegate` no snapshot, têm stake
+        /// e ainda não votaram diretamente
+        fn collect_delegated(&self, proposal: &Proposal, delegate: AccountId) -> Vec<(AccountId, DelegatedVote)> {
+            let (proposal_id, block) = (proposal.id, proposal.snapshot_block);
+            let mut delegated = Vec::new();
+            for index in 0..self.get_delegator_count(delegate) {
+                let Some(delegator) = self.delegators.get((delegate, index)) else { continue };
+                if self.votes.contains((proposal_id, delegator))
+                    || self.get_delegate_at(delegator, block) != Some(delegate)
+                {
+                    continue;
+                }
+                let staked = self.fetch_staked(delegator, block);
+                if staked == 0 {
+                    continue;
+                }
+                let weight = self.voting_weight(delegator, staked, proposal);
+                delegated.push((delegator, DelegatedVote { delegate, staked, weight }));
+            }
+            delegated
+        }
+
+        fn delegate_account(&mut self, caller: AccountId, to: AccountId) -> Result<(), GovernanceError> {
+            if to == caller {
+                return Err(GovernanceError::InvalidDelegate);
+            }
+            if !self.accepts_delegations(to) {
+                return Err(GovernanceError::DelegationsNotAccepted);
+            }
+            if self.delegates.contains(to) || self.accepts_delegations(caller) || self.get_delegator_count(caller) > 0 {
+                return Err(GovernanceError::DelegationCycle);
+            }
+
+            let previous = self.delegates.get(caller);
+            if previous == Some(to) {
+                return Ok(());
+            }
+            if self.get_delegator_count(to) >= MAX_DELEGATORS {
+                return Err(GovernanceError::TooManyDelegators);
+            }
+
+            if let Some(previous) = previous {
+                self.remove_delegator(previous, caller);
+            }
+            self.add_delegator(to, caller);
+            self.delegates.insert(caller, &to);
+            self.write_delegation_checkpoint(caller, Some(to));
+
+            Self::env().emit_event(DelegateChanged { delegator: caller, from: previous, to: Some(to) });
+            Ok(())
+        }
+
+        fn clear_delegation(&mut self, delegator: AccountId, delegate: AccountId) {
+            self.remove_delegator(delegate, delegator);
+            self.delegates.remove(delegator);
+            self.write_delegation_checkpoint(delegator, None);
+
+            Self::env().emit_event(DelegateChanged { delegator, from: Some(delegate), to: None });
+        }
+
+        fn add_delegator(&mut self, delegate: AccountId, delegator: AccountId) {
+            let index = self.get_delegator_count(delegate);
+            self.delegators.insert((delegate, index), &delegator);
+            self.delegator_index.insert(delegator, &index);
+            self.delegator_count.insert(delegate, &index.saturating_add(1));
+        }
+
+        /// Remoção por swap com o último delegador
+        fn remove_delegator(&mut self, delegate: AccountId, delegator: AccountId) {
+            let count = self.get_delegator_count(delegate);
+            let Some(index) = self.delegator_index.get(delegator) else { return };
+            let last = count.saturating_sub(1);
+            if index != last {
+                if let Some(moved) = self.delegators.get((delegate, last)) {
+                    self.delegators.insert((delegate, index), &moved);
+                    self.delegator_index.insert(moved, &index);
+                }
+            }
+            self.delegators.remove((delegate, last));
+            self.delegator_index.remove(delegator);
+            self.delegator_count.insert(delegate, &last);
+        }
+
+        fn write_delegation_checkpoint(&mut self, account: AccountId, delegate: Option<AccountId>) {
+            let block = self.env().block_number();
+            let count = self.delegation_checkpoint_counts.get(account).unwrap_or(0);
+            if count > 0 {
+                let last = count.saturating_sub(1);
+                if let Some((last_block, _)) = self.delegation_checkpoints.get((account, last)) {
+                    if last_block == block {
+                        self.delegation_checkpoints.insert((account, last), &(block, delegate));
+                        return;
+                    }
+                }
+            }
+            self.delegation_checkpoints.insert((account, count), &(block, delegate));
+            self.delegation_checkpoint_counts.insert(account, &count.saturating_add(1));
+        }
+
+        fn apply_vote(proposal: &mut Proposal, vote: &Vote, weight: Balance, add: bool) {
+            let tally = match vote {
+                Vote::For => &mut proposal.votes_for,
+                Vote::Against => &mut proposal.votes_against,
+                Vote::Abstain => &mut proposal.votes_abstain,
+            };
+            *tally = if add { tally.saturating_add(weight) } else { tally.saturating_sub(weight) };
+        }
+
+        /// Stake participante (inclui abstenções) sobre o total em staking no snapshot
+        fn quorum_reached(proposal: &Proposal) -> bool {
+            let required = proposal.quorum_base.saturating_mul(proposal.quorum_bps as u128);
+            proposal.turnout > 0 && proposal.turnout.saturating_mul(10000) >= required
+        }
+
+        fn threshold_reached(proposal: &Proposal) -> bool {
+            let decisive = proposal.votes_for.saturating_add(proposal.votes_against);
+            proposal.votes_for.saturating_mul(10000)
+                > decisive.saturating_mul(proposal.approval_threshold_bps as u128)
+        }
+
+        /// Aplica a fórmula ao stake mais o poder veFIAPO dos locks (e o saldo FIAPO,
+        /// se configurado) no snapshot da proposta
+        fn voting_weight(&self, account: AccountId, staked: Balance, proposal: &Proposal) -> Balance {
+            let mut power = staked.saturating_add(self.fetch_lock_power(account, proposal.snapshot_time));
+            if self.config.include_token_balance {
+                let core: PSP22VotesRef = self.core_contract.into();
+                power = power.saturating_add(core.balance_of_at(account, proposal.snapshot_block));
+            }
+            self.config.voting_formula.apply(power)
+        }
+
+        fn fetch_lock_power(&self, account: AccountId, at: u64) -> Balance {
+            match self.staking_contract {
+                Some(staking_addr) => {
+                    let staking: StakingRef = staking_addr.into();
+                    staking.voting_power_of(account, at)
+                }
+                None => 0,
+            }
+        }
+
+        fn fetch_staked(&self, account: AccountId, block: u32) -> Balance {
+            match self.staking_contract {
+                Some(staking_addr) => {
+                    let staking: StakingRef = staking_addr.into();
+                    staking.staked_balance_at(account, block)
+                }
+                None => 0,
+            }
+        }
+
+        fn fetch_total_staked(&self, block: u32) -> Balance {
+            match self.staking_contract {
+                Some(staking_addr) => {
+                    let staking: StakingRef = staking_addr.into();
+                    staking.total_staked_at(block)
+                }
+                None => 0,
+            }
+        }
+
+        fn verify_oracle_usdt(&mut self, tx_hash: String, user: AccountId, expected_cents: u64) -> Result<(), GovernanceError> {
+            if self.used_tx_hashes.get(&tx_hash).unwrap_or(false) {
+                return Err(GovernanceError::TxHashAlreadyUsed);
+            }
 
             let oracle_addr = self.oracle_multisig.ok_or(GovernanceError::OraclePaymentNotConfirmed)?;
-            let oracle: OracleRef = oracle_addr.into();
+            let mut oracle: OracleRef = oracle_addr.into();
             
-            // Oracle implements the Oracle trait — selector matches
-            if oracle.is_payment_confirmed(tx_hash.clone(), user, expected_cents, true) {
+            // Oracle implements the Oracle trait — selector matches; consumed there once
+            if oracle.consume_payment(tx_hash.clone(), user, expected_cents, PaymentPurpose::GovernanceDeposit) {
                 self.used_tx_hashes.insert(tx_hash, &true);
                 Ok(())
             } else {
@@ -320,25 +2182,113 @@ mod fiapo_governance {
             }
         }
 
-        fn ensure_has_staking(&self, account: AccountId) -> Result<(), GovernanceError> {
-            if let Some(staking_addr) = self.staking_contract {
-                // Usa StakingRef — selector correto do trait Staking::get_user_positions
-                let staking: StakingRef = staking_addr.into();
-                let positions = staking.get_user_positions(account);
-                if !positions.is_empty() { return Ok(()); }
+        /// Cobra a parte em USDT da taxa pela forma escolhida. Pagamentos on-chain
+        /// seguem a mesma distribuição das taxas FIAPO.
+        fn collect_usdt_fee(&mut self, payer: AccountId, payment: FeePayment, cents: u64, source: String) -> Result<(), GovernanceError> {
+            let (token, amount) = match payment {
+                FeePayment::OracleUsdt(tx_hash) => return self.verify_oracle_usdt(tx_hash, payer, cents),
+                FeePayment::Lusdt => {
+                    let lusdt = self.lusdt_contract.ok_or(GovernanceError::PaymentAssetNotConfigured)?;
+                    (lusdt, Self::cents_to_lusdt(cents))
+                }
+                FeePayment::Fiapo { max_amount } => {
+                    let amount = self.fiapo_for_cents(cents)?;
+                    if amount > max_amount {
+                        return Err(GovernanceError::FeeAboveMaximum);
+                    }
+                    (self.core_contract, amount)
+                }
+            };
+            if amount == 0 {
+                return Ok(());
+            }
+
+            self.call_token_transfer_from(token, payer, self.env().account_id(), amount)?;
+            self.distribute_fees(token, amount, source)?;
+            Self::env().emit_event(UsdtFeePaid { payer, token, amount, usdt_cents: cents });
+            Ok(())
+        }
+
+        fn cents_to_lusdt(cents: u64) -> Balance {
+            (cents as u128).saturating_mul(LUSDT_SCALE) / 100
+        }
+
+        /// FIAPO equivalente a `cents` ao preço do oráculo (LUSDT por FIAPO)
+        fn fiapo_for_cents(&self, cents: u64) -> Result<Balance, GovernanceError> {
+            if cents == 0 {
+                return Ok(0);
             }
-            Err(GovernanceError::StakingRequired)
+            let oracle_addr = self.oracle_multisig.ok_or(GovernanceError::OraclePriceUnavailable)?;
+            let oracle: OracleRef = oracle_addr.into();
+            let price = oracle.fiapo_price().ok_or(GovernanceError::OraclePriceUnavailable)?;
+            Self::lusdt_to_fiapo(Self::cents_to_lusdt(cents), price)
         }
 
-        fn collect_fiapo_fees(&self, from: AccountId, amount: Balance, source: String) -> Result<(), GovernanceError> {
-            if amount > 0 {
-                self.call_token_transfer_from(self.core_contract, from, self.env().account_id(), amount)?;
-                self.distribute_fiapo_fees(amount, source)?;
+        fn lusdt_to_fiapo(lusdt_amount: Balance, price: Balance) -> Result<Balance, GovernanceError> {
+            lusdt_amount.saturating_mul(SCALE)
+                .checked_div(price)
+                .ok_or(GovernanceError::OraclePriceUnavailable)
+        }
+
+        /// Exige stake no snapshot; retorna o valor em staking
+        fn ensure_has_staking(&self, account: AccountId, block: u32) -> Result<Balance, GovernanceError> {
+            match self.fetch_staked(account, block) {
+                0 => Err(GovernanceError::StakingRequired),
+                staked => Ok(staked),
+            }
+        }
+
+        /// Cobra a taxa e distribui o restante; retorna a parte retida para remuneração
+        fn collect_fiapo_fees(&self, from: AccountId, amount: Balance, source: String) -> Result<Balance, GovernanceError> {
+            if amount == 0 {
+                return Ok(0);
+            }
+            self.call_token_transfer_from(self.core_contract, from, self.env().account_id(), amount)?;
+            let retained = self.remuneration_part(amount);
+            self.distribute_fees(self.core_contract, amount.saturating_sub(retained), source)?;
+            Ok(retained)
+        }
+
+        fn remuneration_part(&self, amount: Balance) -> Balance {
+            let share_bps = self.config.remuneration_share_bps.min(MAX_REMUNERATION_SHARE_BPS);
+            amount.saturating_mul(share_bps as u128).checked_div(10000).unwrap_or(0)
+        }
+
+        fn fund_voter_pool(&mut self, proposal_id: u64, amount: Balance) {
+            if amount == 0 {
+                return;
+            }
+            if let Some(mut proposal) = self.proposals.get(proposal_id) {
+                proposal.voter_pool = proposal.voter_pool.saturating_add(amount);
+                self.proposals.insert(proposal_id, &proposal);
+                self.remuneration_pool = self.remuneration_pool.saturating_add(amount);
             }
-            Ok(())
         }
 
-        fn distribute_fiapo_fees(&self, amount: Balance, _source: String) -> Result<(), GovernanceError> {
+        /// Libera a taxa em custódia de uma proposta não aprovada: a parte de
+        /// remuneração vai aos votantes (se houver) e o restante é distribuído
+        fn release_fee_escrow(&mut self, proposal: &mut Proposal) -> Result<(), GovernanceError> {
+            let amount = core::mem::take(&mut proposal.fee_escrow);
+            if amount == 0 {
+                return Ok(());
+            }
+            let retained = if self.get_voter_count(proposal.id) > 0 { self.remuneration_part(amount) } else { 0 };
+            proposal.voter_pool = proposal.voter_pool.saturating_add(retained);
+            self.remuneration_pool = self.remuneration_pool.saturating_add(retained);
+            self.distribute_fees(self.core_contract, amount.saturating_sub(retained), String::from("Proposal"))
+        }
+
+        /// Soma pontos ao score de governança e publica no Rewards (melhor esforço)
+        fn add_governance_score(&mut self, account: AccountId, points: u32) {
+            let score = self.get_governance_score(account).saturating_add(points);
+            self.governance_scores.insert(account, &score);
+            if let Some(rewards) = self.rewards_contract {
+                let _ = RewardsCall::sync_governance_score(rewards, account, score);
+            }
+        }
+
+        /// Distribui `amount` de `token` (FIAPO ou LUSDT); só FIAPO alimenta o fundo do Rewards
+        fn distribute_fees(&self, token: AccountId, amount: Balance, _source: String) -> Result<(), GovernanceError> {
             // Distribuição: 40% Equipe, 25% Staking, 20% Rewards, 5% Noble, 10% Burn
             let team_part = amount.saturating_mul(40).checked_div(100).unwrap_or(0);
             let staking_part = amount.saturating_mul(25).checked_div(100).unwrap_or(0);
@@ -350,25 +2300,27 @@ mod fiapo_governance {
                 .saturating_sub(noble_part); // Approx 10%
 
             if let Some(team) = self.team_wallet {
-                if team_part > 0 { let _ = self.call_token_transfer(self.core_contract, team, team_part); }
+                if team_part > 0 { let _ = self.call_token_transfer(token, team, team_part); }
             }
             if let Some(staking) = self.staking_contract {
-                if staking_part > 0 { let _ = self.call_token_transfer(self.core_contract, staking, staking_part); }
+                if staking_part > 0 { let _ = self.call_token_transfer(token, staking, staking_part); }
             }
             if let Some(rewards) = self.rewards_contract {
                 if rewards_part > 0 { 
-                    let _ = self.call_token_transfer(self.core_contract, rewards, rewards_part); 
-                    let _ = self.call_rewards_add_fund(rewards, rewards_part);
+                    let _ = self.call_token_transfer(token, rewards, rewards_part); 
+                    if token == self.core_contract {
+                        let _ = self.call_rewards_add_fund(rewards, rewards_part);
+                    }
                 }
             }
             if let Some(noble) = self.noble_contract {
                 if noble_part > 0 {
-                     let _ = self.call_token_transfer(self.core_contract, noble, noble_part);
+                     let _ = self.call_token_transfer(token, noble, noble_part);
                 }
             }
             if let Some(burn) = self.burn_wallet {
                 if burn_part > 0 {
-                    let _ = self.call_token_transfer(self.core_contract, burn, burn_part);
+                    let _ = self.call_token_transfer(token, burn, burn_part);
                 }
             }
 
@@ -421,12 +2373,97 @@ mod fiapo_governance {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn set_action_targets(
+            &mut self,
+            marketplace: Option<AccountId>,
+            affiliate: Option<AccountId>,
+            treasury: Option<AccountId>,
+        ) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            self.marketplace_contract = marketplace;
+            self.affiliate_contract = affiliate;
+            self.treasury_contract = treasury;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_relayer(&mut self, relayer: AccountId, authorized: bool) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            if authorized {
+                self.relayers.insert(relayer, &true);
+            } else {
+                self.relayers.remove(relayer);
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_lusdt_contract(&mut self, lusdt: Option<AccountId>) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            self.lusdt_contract = lusdt;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_timelock_contract(&mut self, timelock: Option<AccountId>) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            self.timelock_contract = timelock;
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn update_config(&mut self, config: GovernanceConfig) -> Result<(), GovernanceError> {
             if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            if config.remuneration_share_bps > MAX_REMUNERATION_SHARE_BPS {
+                return Err(GovernanceError::InvalidParameters);
+            }
             self.config = config;
             Ok(())
         }
+
+        /// Aplica-se a propostas criadas depois da alteração
+        #[ink(message)]
+        pub fn set_type_config(&mut self, proposal_type: ProposalType, config: ProposalTypeConfig) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            if config.voting_period == 0 || config.quorum_bps > 10000 || config.approval_threshold_bps > 10000 {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            self.type_configs.insert(&proposal_type, &config);
+            Ok(())
+        }
+
+        /// Define o conselho de guardiões; lista vazia com limite 0 desativa o veto
+        #[ink(message)]
+        pub fn set_guardians(&mut self, guardians: Vec<AccountId>, threshold: u32) -> Result<(), GovernanceError> {
+            if self.env().caller() != self.owner { return Err(GovernanceError::Unauthorized); }
+            let valid_threshold = if guardians.is_empty() {
+                threshold == 0
+            } else {
+                threshold > 0 && threshold as usize <= guardians.len()
+            };
+            let unique = guardians.iter().enumerate().all(|(i, guardian)| !guardians[..i].contains(guardian));
+            if !valid_threshold || !unique || guardians.len() > MAX_GUARDIANS {
+                return Err(GovernanceError::InvalidParameters);
+            }
+            self.guardians = guardians;
+            self.guardian_threshold = threshold;
+            Ok(())
+        }
+    }
+
+    impl IGovernance for FiapoGovernance {
+        #[ink(message)]
+        fn finalize_proposal(&mut self, proposal_id: u64) -> PSP22Result<()> {
+            FiapoGovernance::finalize_proposal(self, proposal_id)
+                .map(|_| ())
+                .map_err(Into::into)
+        }
+
+        #[ink(message)]
+        fn execute_proposal(&mut self, proposal_id: u64) -> PSP22Result<()> {
+            FiapoGovernance::execute_proposal(self, proposal_id).map_err(Into::into)
+        }
     }
 
     #[cfg(test)]
@@ -454,14 +2491,14 @@ mod fiapo_governance {
             let mut gov = FiapoGovernance::new(accounts.alice);
             
             let new_config = GovernanceConfig {
-                quorum_bps: 6000,
-                voting_period: 5 * 24 * 3600,
-                timelock_period: 48 * 3600,
                 proposal_fee_fiapo: 2000 * SCALE,
                 proposal_fee_usdt_cents: 20000,
                 vote_fee_fiapo: 200 * SCALE,
                 vote_fee_usdt_cents: 2000,
                 max_votes_per_hour: 20,
+                voting_formula: VotingFormula::SquareRoot,
+                include_token_balance: true,
+                remuneration_share_bps: 1000,
             };
 
             assert!(gov.update_config(new_config.clone()).is_ok());
@@ -521,5 +2558,670 @@ mod fiapo_governance {
             assert!(count >= gov.config.max_votes_per_hour);
             // This is the condition that triggers RateLimitExceeded
         }
+
+        fn power(staked: Balance, weight: Balance) -> VotePower {
+            VotePower { staked, weight, delegated_weight: 0 }
+        }
+
+        fn insert_proposal(gov: &mut FiapoGovernance, proposer: AccountId, quorum_base: Balance) -> u64 {
+            let id = gov.next_proposal_id;
+            let config = gov.get_type_config(ProposalType::ConfigChange);
+            gov.proposals.insert(id, &Proposal {
+                id,
+                proposer,
+                proposal_type: ProposalType::ConfigChange,
+                description: String::from("Test"),
+                voting_start: 0,
+                voting_end: config.voting_period,
+                execution_time: config.voting_period + config.timelock_period,
+                status: ProposalStatus::Active,
+                votes_for: 0,
+                votes_against: 0,
+                votes_abstain: 0,
+                executed: false,
+                calls: Vec::new(),
+                actions: Vec::new(),
+                timelock_operations: Vec::new(),
+                snapshot_block: 0,
+                snapshot_time: 0,
+                quorum_base,
+                turnout: 0,
+                quorum_bps: config.quorum_bps,
+                approval_threshold_bps: config.approval_threshold_bps,
+                fee_escrow: 0,
+                voter_pool: 0,
+            });
+            gov.index_new_proposal(&gov.proposals.get(id).unwrap());
+            gov.next_proposal_id = id + 1;
+            id
+        }
+
+        #[ink::test]
+        fn finalize_applies_quorum_and_threshold() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let config = gov.get_type_config(ProposalType::ConfigChange);
+
+            let approved = insert_proposal(&mut gov, accounts.bob, 10 * SCALE);
+            let mut proposal = gov.proposals.get(approved).unwrap();
+            proposal.votes_for = 4 * SCALE;
+            proposal.votes_against = SCALE;
+            proposal.votes_abstain = SCALE;
+            proposal.turnout = 6 * SCALE;
+            gov.proposals.insert(approved, &proposal);
+
+            // 5 de 10 FIAPO em staking: abaixo do quórum de 51%
+            let no_quorum = insert_proposal(&mut gov, accounts.bob, 10 * SCALE);
+            let mut proposal = gov.proposals.get(no_quorum).unwrap();
+            proposal.votes_for = 5 * SCALE;
+            proposal.turnout = 5 * SCALE;
+            gov.proposals.insert(no_quorum, &proposal);
+
+            assert_eq!(gov.finalize_proposal(approved), Err(GovernanceError::VotingNotFinished));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(config.voting_period + 1);
+            assert_eq!(gov.finalize_proposal(approved), Ok(ProposalStatus::Approved));
+            assert_eq!(gov.finalize_proposal(no_quorum), Ok(ProposalStatus::Rejected));
+            assert_eq!(gov.finalize_proposal(approved), Err(GovernanceError::ProposalNotActive));
+
+            // Execução só após o timelock da proposta
+            assert_eq!(gov.execute_proposal(approved), Err(GovernanceError::TimelockNotExpired));
+            assert_eq!(gov.execute_proposal(no_quorum), Err(GovernanceError::ProposalNotApproved));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                config.voting_period + config.timelock_period
+            );
+            assert_eq!(IGovernance::execute_proposal(&mut gov, approved), Ok(()));
+            assert_eq!(gov.proposals.get(approved).unwrap().status, ProposalStatus::Executed);
+            assert_eq!(gov.execute_proposal(approved), Err(GovernanceError::ProposalAlreadyExecuted));
+        }
+
+        #[ink::test]
+        fn one_vote_per_account_with_changes() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let id = insert_proposal(&mut gov, accounts.bob, 0);
+
+            assert_eq!(gov.record_vote(id, accounts.bob, Vote::For, power(100, 100), 0), Ok(()));
+            assert_eq!(gov.record_vote(id, accounts.charlie, Vote::Against, power(50, 50), 0), Ok(()));
+            assert_eq!(gov.record_vote(id, accounts.bob, Vote::For, power(100, 100), 0), Err(GovernanceError::AlreadyVoted));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(gov.change_vote(id, Vote::Against), Ok(()));
+            let proposal = gov.proposals.get(id).unwrap();
+            assert_eq!((proposal.votes_for, proposal.votes_against), (0, 150));
+            assert_eq!(proposal.turnout, 150);
+            assert_eq!(gov.get_vote(id, accounts.bob).unwrap().vote, Vote::Against);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            assert_eq!(gov.change_vote(id, Vote::For), Err(GovernanceError::VoteNotFound));
+
+            assert_eq!(gov.get_voter_count(id), 2);
+            let voters = gov.get_voters(id, 1, 10);
+            assert_eq!(voters.len(), 1);
+            assert_eq!(voters[0].0, accounts.charlie);
+
+            // Após o fim da votação não há mudanças
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let voting_period = gov.get_type_config(ProposalType::ConfigChange).voting_period;
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(voting_period + 1);
+            assert_eq!(gov.change_vote(id, Vote::For), Err(GovernanceError::ProposalNotActive));
+        }
+
+        #[ink::test]
+        fn voting_formula_weights_snapshot_stake() {
+            assert_eq!(VotingFormula::Linear.apply(400 * SCALE), 400 * SCALE);
+            assert_eq!(VotingFormula::SquareRoot.apply(400 * SCALE), 20 * SCALE);
+            assert_eq!(VotingFormula::SquareRoot.apply(2 * SCALE), 141_421_356);
+            assert_eq!(VotingFormula::Capped(100 * SCALE).apply(400 * SCALE), 100 * SCALE);
+            assert_eq!(integer_sqrt(0), 0);
+            assert_eq!(integer_sqrt(u128::MAX), u64::MAX as u128);
+
+            // Whale com 10.000 FIAPO contra 4 contas com 900: raiz quadrada inverte o resultado
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            gov.config.voting_formula = VotingFormula::SquareRoot;
+            let id = insert_proposal(&mut gov, accounts.bob, 13_600 * SCALE);
+            let whale = 10_000 * SCALE;
+            gov.record_vote(id, accounts.bob, Vote::Against, power(whale, gov.config.voting_formula.apply(whale)), 0).unwrap();
+            for voter in [accounts.charlie, accounts.django, accounts.eve, accounts.frank] {
+                let stake = 900 * SCALE;
+                gov.record_vote(id, voter, Vote::For, power(stake, gov.config.voting_formula.apply(stake)), 0).unwrap();
+            }
+            let proposal = gov.proposals.get(id).unwrap();
+            assert_eq!((proposal.votes_for, proposal.votes_against), (120 * SCALE, 100 * SCALE));
+            assert_eq!(proposal.turnout, 13_600 * SCALE);
+
+            // Sem staking configurado não há poder de voto
+            assert_eq!(gov.get_voting_power(id, accounts.bob), 0);
+            assert_eq!(gov.ensure_has_staking(accounts.bob, 0), Err(GovernanceError::StakingRequired));
+        }
+
+        #[ink::test]
+        fn delegation_is_single_level_and_overridable() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let set_caller = ink::env::test::set_caller::<ink::env::DefaultEnvironment>;
+
+            // Sem stake no bloco atual não se delega
+            set_caller(accounts.bob);
+            assert_eq!(gov.delegate(accounts.django), Err(GovernanceError::StakingRequired));
+
+            // Só se delega a quem aceita delegações
+            assert_eq!(gov.delegate_account(accounts.bob, accounts.bob), Err(GovernanceError::InvalidDelegate));
+            assert_eq!(gov.delegate_account(accounts.bob, accounts.django), Err(GovernanceError::DelegationsNotAccepted));
+            set_caller(accounts.django);
+            assert_eq!(gov.set_accepts_delegations(true), Ok(()));
+            assert!(gov.accepts_delegations(accounts.django));
+            assert_eq!(gov.delegate_account(accounts.bob, accounts.django), Ok(()));
+            assert_eq!(gov.delegate_account(accounts.charlie, accounts.django), Ok(()));
+
+            // Delegado não delega; não se delega a quem já delegou
+            set_caller(accounts.eve);
+            assert_eq!(gov.set_accepts_delegations(true), Ok(()));
+            assert_eq!(gov.delegate_account(accounts.django, accounts.eve), Err(GovernanceError::DelegationCycle));
+            assert_eq!(gov.delegate_account(accounts.eve, accounts.django), Err(GovernanceError::DelegationCycle));
+            assert_eq!(gov.delegate_account(accounts.eve, accounts.bob), Err(GovernanceError::DelegationsNotAccepted));
+            set_caller(accounts.bob);
+            assert_eq!(gov.set_accepts_delegations(true), Err(GovernanceError::DelegationCycle));
+            set_caller(accounts.eve);
+            assert_eq!(gov.undelegate(), Err(GovernanceError::NotDelegated));
+
+            // Delegado remove qualquer um dos próprios delegadores e pode recusar novos
+            assert_eq!(gov.delegate_account(accounts.frank, accounts.django), Ok(()));
+            assert_eq!(gov.evict_delegators(vec![accounts.frank]), Err(GovernanceError::NotDelegated));
+            set_caller(accounts.django);
+            assert_eq!(gov.evict_delegators(vec![accounts.frank]), Ok(1));
+            assert_eq!(gov.get_delegate(accounts.frank), None);
+            assert_eq!(gov.set_accepts_delegations(false), Ok(()));
+            assert_eq!(gov.delegate_account(accounts.frank, accounts.django), Err(GovernanceError::DelegationsNotAccepted));
+
+            assert_eq!(gov.get_delegate(accounts.bob), Some(accounts.django));
+            assert_eq!(gov.get_delegators(accounts.django, 0, 10), vec![accounts.bob, accounts.charlie]);
+
+            // Checkpoint mantém a delegação do snapshot mesmo após undelegate
+            ink::env::test::advance_block::<ink::env::DefaultEnvironment>();
+            set_caller(accounts.bob);
+            assert_eq!(gov.undelegate(), Ok(()));
+            assert_eq!(gov.get_delegate_at(accounts.bob, 0), Some(accounts.django));
+            assert_eq!(gov.get_delegate_at(accounts.bob, 1), None);
+            assert_eq!(gov.get_delegators(accounts.django, 0, 10), vec![accounts.charlie]);
+
+            // Delegado votou com o poder do charlie; charlie sobrescreve votando direto
+            let id = insert_proposal(&mut gov, accounts.alice, 0);
+            let django_power = VotePower { staked: 300, weight: 300, delegated_weight: 200 };
+            assert_eq!(gov.record_vote(id, accounts.django, Vote::For, django_power, 0), Ok(()));
+            gov.delegated_votes.insert((id, accounts.charlie), &DelegatedVote {
+                delegate: accounts.django,
+                staked: 200,
+                weight: 200,
+            });
+            assert_eq!(gov.record_vote(id, accounts.charlie, Vote::Against, power(200, 200), 0), Ok(()));
+
+            let proposal = gov.proposals.get(id).unwrap();
+            assert_eq!((proposal.votes_for, proposal.votes_against, proposal.turnout), (100, 200, 300));
+            let record = gov.get_vote(id, accounts.django).unwrap();
+            assert_eq!((record.weight, record.delegated_weight), (100, 0));
+            assert_eq!(gov.get_delegated_vote(id, accounts.charlie), None);
+        }
+
+        #[ink::test]
+        fn emergency_track_and_cancellation() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+
+            let emergency = gov.get_type_config(ProposalType::Emergency);
+            let marketing = gov.get_type_config(ProposalType::Marketing);
+            assert!(emergency.voting_period < marketing.voting_period);
+            // Prazos em ms, como `block_timestamp`
+            assert_eq!(emergency.voting_delay, 600_000);
+            assert_eq!(marketing.voting_period, 3 * 86_400_000);
+            assert!(emergency.quorum_bps > marketing.quorum_bps);
+
+            let mut invalid = marketing.clone();
+            invalid.quorum_bps = 10001;
+            assert_eq!(gov.set_type_config(ProposalType::Marketing, invalid), Err(GovernanceError::InvalidParameters));
+            let mut custom = marketing.clone();
+            custom.voting_period = DAY;
+            assert_eq!(gov.set_type_config(ProposalType::Marketing, custom.clone()), Ok(()));
+            assert_eq!(gov.get_type_config(ProposalType::Marketing), custom);
+
+            // Antes de `voting_start`: sem votos, cancelamento só pelo proponente
+            let id = insert_proposal(&mut gov, accounts.bob, 0);
+            let mut proposal = gov.proposals.get(id).unwrap();
+            proposal.voting_start = 100;
+            gov.proposals.insert(id, &proposal);
+            assert_eq!(gov.record_vote(id, accounts.charlie, Vote::For, power(1, 1), 0), Err(GovernanceError::VotingNotStarted));
+            assert_eq!(gov.cancel_proposal(id), Err(GovernanceError::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(100);
+            assert_eq!(gov.cancel_proposal(id), Err(GovernanceError::VotingStarted));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(99);
+            assert_eq!(gov.cancel_proposal(id), Ok(()));
+            assert_eq!(gov.proposals.get(id).unwrap().status, ProposalStatus::Canceled);
+            assert_eq!(gov.cancel_proposal(id), Err(GovernanceError::ProposalNotActive));
+        }
+
+        #[ink::test]
+        fn guardian_council_vetoes_approved_proposal() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let set_caller = ink::env::test::set_caller::<ink::env::DefaultEnvironment>;
+
+            assert_eq!(gov.set_guardians(vec![accounts.bob, accounts.bob], 1), Err(GovernanceError::InvalidParameters));
+            assert_eq!(gov.set_guardians(vec![accounts.bob, accounts.charlie], 3), Err(GovernanceError::InvalidParameters));
+            assert_eq!(gov.set_guardians(vec![accounts.bob, accounts.charlie, accounts.django], 2), Ok(()));
+
+            let id = insert_proposal(&mut gov, accounts.eve, 0);
+            set_caller(accounts.bob);
+            assert_eq!(gov.veto_proposal(id), Err(GovernanceError::ProposalNotApproved));
+
+            let mut proposal = gov.proposals.get(id).unwrap();
+            proposal.status = ProposalStatus::Approved;
+            gov.proposals.insert(id, &proposal);
+
+            set_caller(accounts.eve);
+            assert_eq!(gov.veto_proposal(id), Err(GovernanceError::NotGuardian));
+            set_caller(accounts.bob);
+            assert_eq!(gov.veto_proposal(id), Ok(false));
+            assert_eq!(gov.veto_proposal(id), Err(GovernanceError::VetoAlreadyApproved));
+            set_caller(accounts.charlie);
+            assert_eq!(gov.veto_proposal(id), Ok(true));
+            assert_eq!(gov.get_veto_approvals(id), 2);
+            assert_eq!(gov.proposals.get(id).unwrap().status, ProposalStatus::Vetoed);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(u64::MAX);
+            assert_eq!(gov.execute_proposal(id), Err(GovernanceError::ProposalNotApproved));
+        }
+
+        #[ink::test]
+        fn actions_are_bounded_and_compiled_to_calls() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+
+            let fee = ProposalAction::SetMarketplaceFee(500);
+            assert_eq!(ProposalAction::SetMarketplaceFee(MAX_MARKETPLACE_FEE_BPS + 1).validate(), Err(GovernanceError::InvalidAction));
+            assert_eq!(fee.validate(), Ok(()));
+            assert_eq!(gov.action_call(&fee), Err(GovernanceError::ActionTargetNotSet));
+
+            let weights = ScoringWeightsParams {
+                balance_weight: 25,
+                staking_weight: 30,
+                burn_weight: 20,
+                transaction_weight: 10,
+                affiliate_weight: 10,
+                governance_weight: 10,
+            };
+            assert_eq!(ProposalAction::UpdateScoringWeights(weights).validate(), Err(GovernanceError::InvalidAction));
+
+            let pool_config = PoolConfigParams {
+                apy_bps: 700,
+                min_period_days: 30,
+                early_withdrawal_penalty_bps: 1000,
+                cancellation_penalty_bps: 1000,
+                payment_frequency_days: 30,
+                active: true,
+                unbonding_days: 21,
+                instant_exit_fee_bps: 500,
+                reward_asset: 0,
+                reward_units_per_fiapo: SCALE,
+                fee_asset: 1,
+            };
+            let lunes_fee = ProposalAction::SetStakingPoolConfig { pool: 2, config: pool_config.clone() };
+            assert_eq!(lunes_fee.validate(), Err(GovernanceError::InvalidAction));
+
+            gov.set_linked_contracts(Some(accounts.bob), None, None, None, None, None).unwrap();
+            gov.set_action_targets(Some(accounts.charlie), None, None).unwrap();
+            let call = gov.action_call(&fee).unwrap().unwrap();
+            assert_eq!(call.target, accounts.charlie);
+            assert_eq!(call.call_data(), [&ink::selector_bytes!("set_fee_bps")[..], &500u16.encode()[..]].concat());
+
+            let pool = ProposalAction::SetStakingPoolConfig { pool: 2, config: PoolConfigParams { fee_asset: 2, ..pool_config } };
+            assert_eq!(pool.validate(), Ok(()));
+            let call = gov.action_call(&pool).unwrap().unwrap();
+            assert_eq!(call.target, accounts.bob);
+            assert_eq!(call.input[0], 2);
+            assert_eq!(gov.action_call(&ProposalAction::UpdateGovernanceConfig(GovernanceConfig::default())), Ok(None));
+        }
+
+        #[ink::test]
+        fn governance_config_action_applies_on_execution() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let id = insert_proposal(&mut gov, accounts.bob, 0);
+
+            let config = GovernanceConfig { max_votes_per_hour: 3, ..Default::default() };
+            let mut proposal = gov.proposals.get(id).unwrap();
+            proposal.actions = vec![ProposalAction::UpdateGovernanceConfig(config.clone())];
+            proposal.status = ProposalStatus::Approved;
+            gov.proposals.insert(id, &proposal);
+
+            // Chamada que já teve sucesso numa tentativa anterior não é despachada de novo
+            proposal.calls = vec![ProposalCall {
+                target: accounts.django,
+                selector: [0; 4],
+                input: vec![],
+                transferred_value: 0,
+            }];
+            gov.proposals.insert(id, &proposal);
+            gov.executed_calls.insert((id, 0), &true);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(proposal.execution_time);
+            assert_eq!(gov.execute_proposal(id), Ok(()));
+            assert_eq!(gov.config, config);
+            assert!(gov.is_call_executed(id, 1));
+            assert_eq!(gov.proposals.get(id).unwrap().status, ProposalStatus::Executed);
+            assert_eq!(gov.execute_proposal(id), Err(GovernanceError::ProposalAlreadyExecuted));
+        }
+
+        #[ink::test]
+        fn remuneration_pool_and_proposer_refund() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let set_caller = ink::env::test::set_caller::<ink::env::DefaultEnvironment>;
+            let config = gov.get_type_config(ProposalType::ConfigChange);
+
+            assert_eq!(gov.remuneration_part(100 * SCALE), 20 * SCALE);
+            let greedy = GovernanceConfig { remuneration_share_bps: MAX_REMUNERATION_SHARE_BPS + 1, ..Default::default() };
+            assert_eq!(gov.update_config(greedy.clone()), Err(GovernanceError::InvalidParameters));
+            assert_eq!(ProposalAction::UpdateGovernanceConfig(greedy).validate(), Err(GovernanceError::InvalidAction));
+
+            // Votantes dividem o pool pro-rata ao peso, após a apuração
+            let rejected = insert_proposal(&mut gov, accounts.bob, 0);
+            gov.record_vote(rejected, accounts.bob, Vote::For, power(100, 100), 0).unwrap();
+            gov.record_vote(rejected, accounts.charlie, Vote::Against, power(200, 200), 0).unwrap();
+            // Voto retransmitido não pagou taxa e fica fora do pool
+            gov.record_vote(rejected, accounts.frank, Vote::Against, power(300, 300), 0).unwrap();
+            gov.mark_relayed(rejected, accounts.frank, 300);
+            gov.fund_voter_pool(rejected, 30 * SCALE);
+            assert_eq!(gov.get_remuneration_pool(), 30 * SCALE);
+            assert_eq!(gov.pending_vote_reward(rejected, accounts.bob), 0);
+            set_caller(accounts.bob);
+            assert_eq!(gov.claim_vote_reward(rejected), Err(GovernanceError::VotingNotFinished));
+
+            let approved = insert_proposal(&mut gov, accounts.bob, 0);
+            let mut proposal = gov.proposals.get(approved).unwrap();
+            proposal.fee_escrow = 1000 * SCALE;
+            gov.proposals.insert(approved, &proposal);
+            gov.record_vote(approved, accounts.django, Vote::For, power(100, 100), 0).unwrap();
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(config.voting_period + 1);
+            assert_eq!(gov.finalize_proposal(rejected), Ok(ProposalStatus::Rejected));
+            assert_eq!(gov.finalize_proposal(approved), Ok(ProposalStatus::Approved));
+            assert_eq!(gov.pending_vote_reward(rejected, accounts.bob), 10 * SCALE);
+            assert_eq!(gov.pending_vote_reward(rejected, accounts.charlie), 20 * SCALE);
+            assert_eq!(gov.pending_vote_reward(rejected, accounts.frank), 0);
+            set_caller(accounts.eve);
+            assert_eq!(gov.claim_vote_reward(rejected), Err(GovernanceError::NothingToClaim));
+
+            gov.reward_claims.insert((rejected, accounts.bob), &true);
+            assert_eq!(gov.pending_vote_reward(rejected, accounts.bob), 0);
+            set_caller(accounts.bob);
+            assert_eq!(gov.claim_vote_reward(rejected), Err(GovernanceError::RewardAlreadyClaimed));
+
+            // Reembolso só ao proponente de proposta aprovada, após a janela de veto
+            assert_eq!(gov.claim_proposal_refund(rejected), Err(GovernanceError::ProposalNotApproved));
+            assert_eq!(gov.claim_proposal_refund(approved), Err(GovernanceError::TimelockNotExpired));
+            set_caller(accounts.django);
+            assert_eq!(gov.claim_proposal_refund(approved), Err(GovernanceError::Unauthorized));
+            assert_eq!(gov.proposals.get(approved).unwrap().fee_escrow, 1000 * SCALE);
+
+            // Score de participação: proposta aprovada soma pontos ao proponente
+            assert_eq!(gov.get_governance_score(accounts.bob), APPROVED_PROPOSAL_SCORE_POINTS);
+            gov.add_governance_score(accounts.django, VOTE_SCORE_POINTS);
+            assert_eq!(gov.get_governance_score(accounts.django), VOTE_SCORE_POINTS);
+        }
+
+        #[ink::test]
+        fn usdt_fee_payment_alternatives() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+
+            // 10 USDT = 10 LUSDT; a 0,25 LUSDT por FIAPO = 40 FIAPO
+            assert_eq!(FiapoGovernance::cents_to_lusdt(1000), 10 * LUSDT_SCALE);
+            assert_eq!(FiapoGovernance::lusdt_to_fiapo(10 * LUSDT_SCALE, LUSDT_SCALE / 4), Ok(40 * SCALE));
+            assert_eq!(FiapoGovernance::lusdt_to_fiapo(10 * LUSDT_SCALE, 0), Err(GovernanceError::OraclePriceUnavailable));
+
+            let fee = gov.config.vote_fee_usdt_cents;
+            assert_eq!(
+                gov.collect_usdt_fee(accounts.bob, FeePayment::Lusdt, fee, String::from("Vote")),
+                Err(GovernanceError::PaymentAssetNotConfigured)
+            );
+            assert_eq!(
+                gov.collect_usdt_fee(accounts.bob, FeePayment::Fiapo { max_amount: u128::MAX }, fee, String::from("Vote")),
+                Err(GovernanceError::OraclePriceUnavailable)
+            );
+            assert_eq!(
+                gov.collect_usdt_fee(accounts.bob, FeePayment::OracleUsdt(String::from("tx")), fee, String::from("Vote")),
+                Err(GovernanceError::OraclePaymentNotConfirmed)
+            );
+            // Sem parte em USDT configurada nada é cobrado
+            assert_eq!(gov.collect_usdt_fee(accounts.bob, FeePayment::Fiapo { max_amount: 0 }, 0, String::from("Vote")), Ok(()));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(gov.set_lusdt_contract(Some(accounts.django)), Err(GovernanceError::Unauthorized));
+        }
+
+        #[ink::test]
+        fn treasury_spend_actions_compile_to_grants() {
+            use fiapo_logics::traits::treasury::MilestoneParams;
+
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+
+            let spend = ProposalAction::TreasurySpend {
+                recipient: accounts.django,
+                asset: 2,
+                total: 300,
+                schedule: VestingScheduleParams::Milestones(vec![
+                    MilestoneParams { after: 0, amount: 100 },
+                    MilestoneParams { after: 30 * DAY, amount: 200 },
+                ]),
+            };
+            assert_eq!(spend.validate(), Ok(()));
+            let unbalanced = ProposalAction::TreasurySpend {
+                recipient: accounts.django,
+                asset: 2,
+                total: 301,
+                schedule: VestingScheduleParams::Milestones(vec![MilestoneParams { after: 0, amount: 300 }]),
+            };
+            assert_eq!(unbalanced.validate(), Err(GovernanceError::InvalidAction));
+            let stream = ProposalAction::TreasurySpend {
+                recipient: accounts.django,
+                asset: 3,
+                total: 300,
+                schedule: VestingScheduleParams::Linear { cliff: 0, duration: 1000 },
+            };
+            assert_eq!(stream.validate(), Err(GovernanceError::InvalidAction));
+
+            // Gasto fora de Marketing/Development é recusado antes de qualquer cobrança
+            let result = gov.create_action_proposal(
+                ProposalType::ConfigChange,
+                String::from("Spend"),
+                vec![spend.clone()],
+                FeePayment::Lusdt,
+            );
+            assert_eq!(result, Err(GovernanceError::InvalidAction));
+
+            assert_eq!(gov.action_call(&spend), Err(GovernanceError::ActionTargetNotSet));
+            gov.set_action_targets(None, None, Some(accounts.eve)).unwrap();
+            let call = gov.action_call(&spend).unwrap().unwrap();
+            assert_eq!(call.target, accounts.eve);
+            assert_eq!(call.selector, ink::selector_bytes!("create_grant"));
+            assert_eq!(call.input[..32], *AsRef::<[u8]>::as_ref(&accounts.django));
+
+            let claw_back = gov.action_call(&ProposalAction::TreasuryClawBack(7)).unwrap().unwrap();
+            assert_eq!(claw_back.call_data(), [&ink::selector_bytes!("claw_back")[..], &7u64.encode()[..]].concat());
+        }
+
+        #[ink::test]
+        fn proposal_listing_follows_status_indexes() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let config = gov.get_type_config(ProposalType::ConfigChange);
+            let ids = |proposals: Vec<Proposal>| proposals.iter().map(|p| p.id).collect::<Vec<u64>>();
+
+            let approved = insert_proposal(&mut gov, accounts.bob, 10 * SCALE);
+            let rejected = insert_proposal(&mut gov, accounts.bob, 10 * SCALE);
+            let pending = insert_proposal(&mut gov, accounts.charlie, 10 * SCALE);
+            gov.record_vote(approved, accounts.django, Vote::For, power(6 * SCALE, 6 * SCALE), 0).unwrap();
+            let mut proposal = gov.get_proposal(pending).unwrap();
+            proposal.voting_end = config.voting_period * 2;
+            gov.proposals.insert(pending, &proposal);
+
+            assert_eq!(gov.get_proposal(99), None);
+            assert_eq!(ids(gov.active_proposals(0, 10)), vec![approved, rejected, pending]);
+            assert_eq!(ids(gov.proposals_by_proposer(accounts.bob, 1, 10)), vec![rejected]);
+            assert_eq!(gov.get_proposer_proposal_count(accounts.charlie), 1);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(config.voting_period + 1);
+            assert_eq!(gov.finalize_proposal(approved), Ok(ProposalStatus::Approved));
+            assert_eq!(gov.finalize_proposal(rejected), Ok(ProposalStatus::Rejected));
+
+            // Remoção por troca: a última ativa ocupa a posição liberada
+            assert_eq!(ids(gov.active_proposals(0, 10)), vec![pending]);
+            assert_eq!(ids(gov.list_proposals(Some(ProposalStatus::Approved), Some(ProposalType::ConfigChange), 0, 10)), vec![approved]);
+            assert_eq!(ids(gov.list_proposals(Some(ProposalStatus::Rejected), None, 0, 10)), vec![rejected]);
+            assert_eq!(ids(gov.list_proposals(None, None, 1, 1)), vec![rejected]);
+            assert_eq!(gov.get_proposal_count(None, Some(ProposalType::ConfigChange)), 3);
+            assert_eq!(gov.get_proposal_count(Some(ProposalStatus::Active), Some(ProposalType::Marketing)), 0);
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                config.voting_period + config.timelock_period
+            );
+            assert_eq!(gov.execute_proposal(approved), Ok(()));
+            assert_eq!(gov.get_proposal_count(Some(ProposalStatus::Approved), None), 0);
+            assert_eq!(ids(gov.list_proposals(Some(ProposalStatus::Executed), None, 0, 10)), vec![approved]);
+        }
+
+        fn ecdsa_sign(secret: &secp256k1::SecretKey, hash: [u8; 32]) -> [u8; 65] {
+            let message = secp256k1::Message::from_slice(&hash).unwrap();
+            let (recovery_id, compact) = secp256k1::SECP256K1
+                .sign_ecdsa_recoverable(&message, secret)
+                .serialize_compact();
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&compact);
+            signature[64] = recovery_id.to_i32() as u8;
+            signature
+        }
+
+        #[ink::test]
+        fn signed_votes_verify_domain_and_settle_via_relayer() {
+            let accounts = default_accounts();
+            let mut gov = FiapoGovernance::new(accounts.alice);
+            let set_caller = ink::env::test::set_caller::<ink::env::DefaultEnvironment>;
+            let config = gov.get_type_config(ProposalType::ConfigChange);
+            let id = insert_proposal(&mut gov, accounts.bob, 0);
+            let other = insert_proposal(&mut gov, accounts.bob, 0);
+
+            // Conta ECDSA nativa: AccountId = blake2_256(chave comprimida)
+            let secret = secp256k1::SecretKey::from_slice(&[7u8; 32]).unwrap();
+            let public = secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret).serialize();
+            let voter = AccountId::from(FiapoGovernance::blake2_256(&public));
+            let signed = SignedVote {
+                voter,
+                vote: Vote::For,
+                signature: ecdsa_sign(&secret, gov.signed_vote_hash(id, voter, Vote::For)),
+            };
+            assert!(gov.verify_signed_vote(id, &signed));
+            assert!(!gov.verify_signed_vote(other, &signed));
+            assert!(!gov.verify_signed_vote(id, &SignedVote { vote: Vote::Against, ..signed.clone() }));
+            assert!(!gov.verify_signed_vote(id, &SignedVote { voter: accounts.charlie, ..signed.clone() }));
+
+            // Conta sr25519 vincula a chave com prova de posse
+            set_caller(accounts.charlie);
+            let charlie_vote = SignedVote {
+                voter: accounts.charlie,
+                vote: Vote::Against,
+                signature: ecdsa_sign(&secret, gov.signed_vote_hash(id, accounts.charlie, Vote::Against)),
+            };
+            // Prova assinada para outra conta vincula uma chave que não é a do votante
+            let wrong_account = ecdsa_sign(&secret, gov.signing_key_hash(accounts.django));
+            assert_eq!(gov.register_vote_signing_key(wrong_account), Ok(()));
+            assert!(!gov.verify_signed_vote(id, &charlie_vote));
+            gov.clear_vote_signing_key();
+            let proof_of_key = ecdsa_sign(&secret, gov.signing_key_hash(accounts.charlie));
+            assert_eq!(gov.register_vote_signing_key(proof_of_key), Ok(()));
+            assert!(gov.verify_signed_vote(id, &charlie_vote));
+            assert!(!gov.verify_signed_vote(id, &signed));
+            set_caller(accounts.django);
+            let taken_key = ecdsa_sign(&secret, gov.signing_key_hash(accounts.django));
+            assert_eq!(gov.register_vote_signing_key(taken_key), Err(GovernanceError::InvalidParameters));
+
+            // Só relayers enviam; sem stake no snapshot o voto é rejeitado
+            assert_eq!(gov.submit_signed_votes(id, vec![signed.clone()]), Err(GovernanceError::NotRelayer));
+            assert_eq!(gov.set_relayer(accounts.django, true), Err(GovernanceError::Unauthorized));
+            set_caller(accounts.alice);
+            assert_eq!(gov.set_relayer(accounts.django, true), Ok(()));
+            set_caller(accounts.django);
+            assert_eq!(gov.submit_signed_votes(id, vec![signed.clone(), charlie_vote.clone()]), Ok(0));
+            let oversized = vec![signed.clone(); MAX_SIGNED_VOTES_PER_BATCH + 1];
+            assert_eq!(gov.submit_signed_votes(id, oversized), Err(GovernanceError::InvalidParameters));
+
+            // Merkle root: folhas verificadas com provas de pares ordenados
+            let leaf_a = FiapoGovernance::blake2_256(&signed.encode());
+            let leaf_b = FiapoGovernance::blake2_256(&charlie_vote.encode());
+            let root = FiapoGovernance::merkle_root(leaf_a, &[leaf_b]);
+            assert_eq!(FiapoGovernance::merkle_root(leaf_b, &[leaf_a]), root);
+            assert_eq!(gov.commit_signed_vote_root(id, root, 2), Ok(()));
+            assert_eq!(gov.commit_signed_vote_root(id, root, 2), Err(GovernanceError::RootAlreadyCommitted));
+            assert_eq!(gov.settle_signed_vote_root(other, accounts.django, vec![]), Err(GovernanceError::ProposalNotFound));
+
+            // Cada relayer compromete o próprio root na mesma proposta
+            set_caller(accounts.alice);
+            assert_eq!(gov.set_relayer(accounts.frank, true), Ok(()));
+            set_caller(accounts.frank);
+            assert_eq!(gov.commit_signed_vote_root(id, leaf_a, 2), Ok(()));
+            assert_eq!(gov.get_signed_vote_root(id, accounts.frank).unwrap().root, leaf_a);
+            assert_eq!(gov.get_signed_vote_root(id, accounts.django).unwrap().root, root);
+            // Folhas só são liquidadas após o fim da votação
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.django, vec![]), Err(GovernanceError::VotingNotFinished));
+
+            // Folhas provadas contam uma vez, mesmo com o voto rejeitado
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(config.voting_period + 1);
+            assert_eq!(gov.commit_signed_vote_root(other, root, 2), Err(GovernanceError::ProposalNotActive));
+            set_caller(accounts.eve);
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.django, vec![(signed.clone(), vec![leaf_b])]), Ok(0));
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.django, vec![(signed.clone(), vec![leaf_b])]), Ok(0));
+            assert_eq!(gov.get_signed_vote_root(id, accounts.django).unwrap().settled, 1);
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.django, vec![(charlie_vote.clone(), vec![leaf_a])]), Ok(0));
+            assert_eq!(gov.get_signed_vote_root(id, accounts.django).unwrap().settled, 2);
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.frank, vec![(signed.clone(), vec![])]), Ok(0));
+            assert_eq!(gov.finalize_proposal(other), Ok(ProposalStatus::Rejected));
+
+            // O root de frank declarou 2 folhas mas só liquidou 1: a apuração espera a
+            // janela e então descarta os votos que ele somou
+            let mut commitment = gov.get_signed_vote_root(id, accounts.frank).unwrap();
+            commitment.votes_for = 10 * SCALE;
+            commitment.turnout = 10 * SCALE;
+            gov.signed_vote_roots.insert((id, accounts.frank), &commitment);
+            let mut proposal = gov.proposals.get(id).unwrap();
+            proposal.votes_for = 10 * SCALE;
+            proposal.turnout = 10 * SCALE;
+            gov.proposals.insert(id, &proposal);
+            gov.relayed_weight.insert(id, &(10 * SCALE));
+            assert_eq!(gov.finalize_proposal(id), Err(GovernanceError::SettlementPending));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(
+                config.voting_period + SIGNED_VOTE_SETTLEMENT_PERIOD + 1
+            );
+            assert_eq!(gov.settle_signed_vote_root(id, accounts.frank, vec![]), Err(GovernanceError::ProposalNotActive));
+            assert_eq!(gov.finalize_proposal(id), Ok(ProposalStatus::Rejected));
+            let proposal = gov.proposals.get(id).unwrap();
+            assert_eq!((proposal.votes_for, proposal.turnout), (0, 0));
+            assert_eq!(gov.relayed_weight.get(id), Some(0));
+        }
+
+        #[ink::test]
+        fn proposal_call_data_prefixes_selector() {
+            let call = ProposalCall {
+                target: AccountId::from([1u8; 32]),
+                selector: [0xde, 0xad, 0xbe, 0xef],
+                input: vec![1, 2],
+                transferred_value: 0,
+            };
+            assert_eq!(call.call_data(), vec![0xde, 0xad, 0xbe, 0xef, 1, 2]);
+        }
     }
 }
@@ -7,7 +7,7 @@
 //! - Vesting automático
 //! - Evolução de NFTs (burn + merge)
 //! - Prestige bonus baseado em ordem de mint
-//! - Raridade visual
+//! - Raridade visual (sorteada com a semente do beacon de aleatoriedade após o mint)
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
@@ -21,6 +21,10 @@ mod fiapo_ico {
     
     // Cross-contract: PSP22Ref garante selector correto do trait IPSP22
     use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
+    use fiapo_logics::traits::boost::BoostRegistryCall;
+    use fiapo_logics::traits::staking::{NftHolding, StakingCall, MAX_NFT_HOLDINGS};
+    use fiapo_logics::traits::randomness::{derive_random, random_below, Randomness, RandomnessRef};
+    use fiapo_traits::{BoostSource, BoostTarget};
 
     /// Constantes do sistema
     pub const MINING_PERIOD_DAYS: u64 = 112;
@@ -55,6 +59,9 @@ mod fiapo_ico {
         EvolutionNotAllowed,
         CoreContractError,
         Unauthorized,
+        RandomnessNotConfigured,
+        RandomnessPending,
+        RarityNotPending,
     }
 
     /// Raridade visual do NFT
@@ -287,6 +294,15 @@ mod fiapo_ico {
         visual_rarity: VisualRarity,
     }
 
+    /// Evento de raridade visual revelada pela semente do beacon
+    #[ink(event)]
+    pub struct RarityRevealed {
+        #[ink(topic)]
+        nft_id: u64,
+        visual_rarity: VisualRarity,
+        round: u64,
+    }
+
     /// Evento de tokens minerados
     #[ink(event)]
     pub struct TokensClaimed {
@@ -319,6 +335,10 @@ mod fiapo_ico {
         marketplace_contract: Option<AccountId>,
         /// Contrato Order of the Nobles (Afiliados Especiais)
         noble_contract: Option<AccountId>,
+        /// Boost Registry (boost de mineração agregado)
+        boost_registry: Option<AccountId>,
+        /// Staking (recebe os NFTs ativos de cada usuário para o boost de APY)
+        staking_contract: Option<AccountId>,
         /// Owner do contrato
         owner: AccountId,
         /// Se o ICO está ativo
@@ -367,6 +387,10 @@ mod fiapo_ico {
         rarity_counts: Mapping<VisualRarity, u32>,
         /// Histórico de evoluções por usuário
         user_evolutions: Mapping<AccountId, Vec<EvolutionRecord>>,
+        /// Beacon de aleatoriedade (sem ele a raridade é Common)
+        randomness_contract: Option<AccountId>,
+        /// NFTs com raridade a revelar: nft_id -> rodada do beacon
+        pending_rarity: Mapping<u64, u64>,
     }
 
     impl FiapoICO {
@@ -381,6 +405,8 @@ mod fiapo_ico {
                 oracle_contract: None,
                 marketplace_contract: None,
                 noble_contract: None,
+                boost_registry: None,
+                staking_contract: None,
                 owner: caller,
                 ico_active: true,
                 mining_active: true,
@@ -405,6 +431,8 @@ mod fiapo_ico {
                 total_nfts_burned: 0,
                 rarity_counts: Mapping::default(),
                 user_evolutions: Mapping::default(),
+                randomness_contract: None,
+                pending_rarity: Mapping::default(),
             };
 
             // Inicializa configurações dos tiers
@@ -826,13 +854,15 @@ mod fiapo_ico {
             let nft_id = self.next_nft_id;
             let tier_u8 = tier.to_u8();
 
-            // Determina raridade visual baseada em pseudo-random
-            let visual_rarity = self.determine_rarity(nft_id);
-
-            // Update rarity counts
-            let mut rarity_count = self.rarity_counts.get(&visual_rarity).unwrap_or(0);
-            rarity_count = rarity_count.saturating_add(1);
-            self.rarity_counts.insert(&visual_rarity, &rarity_count);
+            // Raridade visual: Common até a revelação com a rodada pedida ao beacon
+            let visual_rarity = VisualRarity::Common;
+            match self.randomness_contract {
+                Some(randomness) => {
+                    let mut beacon: RandomnessRef = randomness.into();
+                    self.pending_rarity.insert(nft_id, &beacon.request_randomness());
+                }
+                None => self.count_rarity(&visual_rarity),
+            }
 
             // Calcula prestige bonus (primeiros 10% do supply)
             let config = self.tier_configs.get(tier_u8).unwrap();
@@ -866,6 +896,7 @@ mod fiapo_ico {
             let is_new_participant = owner_nfts.is_empty();
             owner_nfts.push(nft_id);
             self.nfts_by_owner.insert(owner, &owner_nfts);
+            self.sync_staking_nfts(owner);
 
             // Atualiza contadores
             self.next_nft_id = self.next_nft_id.saturating_add(1);
@@ -907,6 +938,12 @@ mod fiapo_ico {
                     // Apply Mining Boost
                     let new_boost_end = current_time.saturating_add(MINING_BOOST_DURATION);
                     self.user_mining_boost.insert(owner, &new_boost_end);
+                    if let Some(registry) = self.boost_registry {
+                        let _ = BoostRegistryCall::post_boost(
+                            registry, owner, BoostSource::NftMining, BoostTarget::Mining,
+                            MINING_BOOST_BPS, new_boost_end,
+                        );
+                    }
                 }
             } else {
                 let mint_position = updated_config.minted; // Sale position
@@ -942,12 +979,55 @@ mod fiapo_ico {
             Ok(nft_id)
         }
 
-        /// Determina raridade visual (pseudo-random baseado em block + nft_id)
-        fn determine_rarity(&self, nft_id: u64) -> VisualRarity {
-            // Usa block number + nft_id como seed pseudo-aleatório
-            let block = self.env().block_number();
-            let seed = block.saturating_add(nft_id as u32);
-            let rand = seed % 100;
+        /// Revela a raridade visual com a semente da rodada (qualquer um pode chamar)
+        #[ink(message)]
+        pub fn reveal_rarity(&mut self, nft_id: u64) -> Result<VisualRarity, ICOError> {
+            let round = self.pending_rarity.get(nft_id).ok_or(ICOError::RarityNotPending)?;
+            let beacon: RandomnessRef = self.randomness_contract.ok_or(ICOError::RandomnessNotConfigured)?.into();
+            let seed = beacon.randomness(round).ok_or(ICOError::RandomnessPending)?;
+            self.apply_rarity(nft_id, round, &seed)
+        }
+
+        /// Pede nova rodada quando a do NFT falhou sem entropia
+        #[ink(message)]
+        pub fn rerequest_rarity_randomness(&mut self, nft_id: u64) -> Result<u64, ICOError> {
+            let round = self.pending_rarity.get(nft_id).ok_or(ICOError::RarityNotPending)?;
+            let mut beacon: RandomnessRef = self.randomness_contract.ok_or(ICOError::RandomnessNotConfigured)?.into();
+            if !beacon.round_failed(round) {
+                return Err(ICOError::RandomnessPending);
+            }
+            let round = beacon.request_randomness();
+            self.pending_rarity.insert(nft_id, &round);
+            Ok(round)
+        }
+
+        /// Rodada do beacon que revelará a raridade do NFT, se pendente
+        #[ink(message)]
+        pub fn get_pending_rarity(&self, nft_id: u64) -> Option<u64> {
+            self.pending_rarity.get(nft_id)
+        }
+
+        fn apply_rarity(&mut self, nft_id: u64, round: u64, seed: &[u8; 32]) -> Result<VisualRarity, ICOError> {
+            let mut nft = self.nfts.get(nft_id).ok_or(ICOError::NFTNotFound)?;
+            let visual_rarity = Self::determine_rarity(seed, nft_id);
+            nft.visual_rarity = visual_rarity.clone();
+            self.nfts.insert(nft_id, &nft);
+            self.pending_rarity.remove(nft_id);
+            self.count_rarity(&visual_rarity);
+            self.sync_staking_nfts(nft.owner);
+
+            Self::env().emit_event(RarityRevealed { nft_id, visual_rarity: visual_rarity.clone(), round });
+            Ok(visual_rarity)
+        }
+
+        fn count_rarity(&mut self, visual_rarity: &VisualRarity) {
+            let rarity_count = self.rarity_counts.get(visual_rarity).unwrap_or(0);
+            self.rarity_counts.insert(visual_rarity, &rarity_count.saturating_add(1));
+        }
+
+        /// Determina raridade visual a partir da semente do beacon e do nft_id
+        fn determine_rarity(seed: &[u8; 32], nft_id: u64) -> VisualRarity {
+            let rand = random_below(&derive_random(seed, &nft_id), 100);
 
             match rand {
                 0..=49 => VisualRarity::Common,      // 50%
@@ -996,6 +1076,8 @@ mod fiapo_ico {
             let mut to_nfts = self.nfts_by_owner.get(to).unwrap_or_default();
             to_nfts.push(nft_id);
             self.nfts_by_owner.insert(to, &to_nfts);
+            self.sync_staking_nfts(from);
+            self.sync_staking_nfts(to);
 
             Ok(())
         }
@@ -1048,6 +1130,8 @@ mod fiapo_ico {
             let mut to_nfts = self.nfts_by_owner.get(to).unwrap_or_default();
             to_nfts.push(nft_id);
             self.nfts_by_owner.insert(to, &to_nfts);
+            self.sync_staking_nfts(from);
+            self.sync_staking_nfts(to);
 
             Ok(pending)
         }
@@ -1076,6 +1160,33 @@ mod fiapo_ico {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn set_boost_registry(&mut self, registry: Option<AccountId>) -> Result<(), ICOError> {
+            if self.env().caller() != self.owner {
+                return Err(ICOError::Unauthorized);
+            }
+            self.boost_registry = registry;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_staking_contract(&mut self, staking: Option<AccountId>) -> Result<(), ICOError> {
+            if self.env().caller() != self.owner {
+                return Err(ICOError::Unauthorized);
+            }
+            self.staking_contract = staking;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_randomness_contract(&mut self, randomness: Option<AccountId>) -> Result<(), ICOError> {
+            if self.env().caller() != self.owner {
+                return Err(ICOError::Unauthorized);
+            }
+            self.randomness_contract = randomness;
+            Ok(())
+        }
+
         // ==================== Mining Functions ====================
 
         /// Claim tokens minerados de um NFT
@@ -1124,6 +1235,51 @@ mod fiapo_ico {
             Ok(tokens_to_claim)
         }
 
+        /// Informa ao Staking os NFTs ativos de `user` (boost de APY sem leituras ao ICO)
+        fn sync_staking_nfts(&self, user: AccountId) {
+            let Some(staking) = self.staking_contract else { return };
+            let holdings: Vec<NftHolding> = self.nfts_by_owner.get(user).unwrap_or_default()
+                .into_iter()
+                .filter_map(|id| self.nfts.get(id))
+                .filter(|nft| nft.active)
+                .take(MAX_NFT_HOLDINGS)
+                .map(|nft| NftHolding {
+                    nft_id: nft.id,
+                    tier: nft.tier.to_u8(),
+                    visual_rarity: nft.visual_rarity as u8,
+                })
+                .collect();
+            let _ = StakingCall::sync_nft_holdings(staking, user, holdings);
+        }
+
+        /// Boost de mineração do usuário (bps): agregado do Boost Registry quando
+        /// configurado, senão o boost local de evolução
+        fn user_mining_boost_bps(&self, user: AccountId) -> u32 {
+            if let Some(registry) = self.boost_registry {
+                return BoostRegistryCall::get_boost(registry, user, BoostTarget::Mining);
+            }
+            let boost_end = self.user_mining_boost.get(user).unwrap_or(0);
+            if self.env().block_timestamp() < boost_end {
+                MINING_BOOST_BPS
+            } else {
+                0
+            }
+        }
+
+        /// Boost médio (bps) do usuário em `[from, to)`: cada boost só conta
+        /// enquanto esteve vigente, não o valor do momento do claim
+        fn user_mining_boost_over(&self, user: AccountId, from: u64, to: u64) -> u32 {
+            if let Some(registry) = self.boost_registry {
+                return BoostRegistryCall::get_boost_over(registry, user, BoostTarget::Mining, from, to);
+            }
+            let boost_end = self.user_mining_boost.get(user).unwrap_or(0).min(to);
+            let boost_start = boost_end.saturating_sub(MINING_BOOST_DURATION).max(from);
+            (MINING_BOOST_BPS as u128)
+                .saturating_mul(boost_end.saturating_sub(boost_start) as u128)
+                .checked_div(to.saturating_sub(from) as u128)
+                .unwrap_or(0) as u32
+        }
+
         /// Calcula tokens minerados desde o último claim
         fn calculate_mined_tokens(&self, nft: &NFTData, config: &TierConfig) -> u128 {
             let current_time = self.env().block_timestamp();
@@ -1152,7 +1308,12 @@ mod fiapo_ico {
                 .saturating_mul(nft.mining_bonus_bps as u128)
                 .saturating_div(10000);
 
-            let total_mined = base_mined.saturating_add(bonus);
+            // Aplica o boost de mineração do usuário ponderado pelo período minerado
+            let user_boost = base_mined
+                .saturating_mul(self.user_mining_boost_over(nft.owner, effective_start, effective_end) as u128)
+                .saturating_div(10000);
+
+            let total_mined = base_mined.saturating_add(bonus).saturating_add(user_boost);
 
             // Limita ao máximo minerável
             let max_claimable = config.tokens_per_nft.saturating_sub(nft.tokens_claimed);
@@ -1432,17 +1593,10 @@ mod fiapo_ico {
             let config = self.tier_configs.get(nft.tier.to_u8())?;
             let base_rate = config.daily_mining_rate;
 
-            // Check user boost
-            let boost_end = self.user_mining_boost.get(nft.owner).unwrap_or(0);
-            let current_time = self.env().block_timestamp();
-            
-            let mut rate = base_rate;
-            // Apply User Global Boost (0.1% if active)
-            if current_time < boost_end {
-                // Rate + (Rate * 10 / 10000)
-                let boost = rate.saturating_mul(MINING_BOOST_BPS as u128).saturating_div(10_000);
-                rate = rate.saturating_add(boost);
-            }
+            // Apply User Global Boost
+            let boost_bps = self.user_mining_boost_bps(nft.owner);
+            let boost = base_rate.saturating_mul(boost_bps as u128).saturating_div(10_000);
+            let mut rate = base_rate.saturating_add(boost);
             
             // Add NFT specific bonus (mining_bonus_bps stored in NFT)
             let nft_bonus = base_rate.saturating_mul(nft.mining_bonus_bps as u128).saturating_div(10_000);
@@ -1544,6 +1698,24 @@ mod fiapo_ico {
             assert!(nft.active);
         }
 
+        #[ink::test]
+        fn nft_data_decodes_as_logics_mirror() {
+            use scale::{Decode, Encode};
+            use fiapo_logics::traits::ico::IcoNftInfo;
+
+            let mut contract = create_contract();
+            let nft_id = contract.mint_free().unwrap();
+            let nft = contract.get_nft(nft_id).unwrap();
+
+            // Staking decodifica NFTData via IcoNftInfo; o layout precisa coincidir
+            let mirror = IcoNftInfo::decode(&mut &nft.encode()[..]).unwrap();
+            assert_eq!(mirror.id, nft.id);
+            assert_eq!(mirror.tier, nft.tier.to_u8());
+            assert_eq!(mirror.owner, nft.owner);
+            assert_eq!(mirror.active, nft.active);
+            assert_eq!(mirror.evolved_from, nft.evolved_from);
+        }
+
         #[ink::test]
         fn free_mint_limit_works() {
             let mut contract = create_contract();
@@ -1566,6 +1738,48 @@ mod fiapo_ico {
             assert_eq!(stats.total_nfts_minted, 1);
             assert_eq!(stats.unique_participants, 1);
         }
+
+        #[ink::test]
+        fn rarity_is_revealed_from_beacon_seed() {
+            let mut contract = create_contract();
+
+            // Sem beacon: Common e já contabilizado
+            let plain = contract.mint_free().unwrap();
+            assert_eq!(contract.get_nft(plain).unwrap().visual_rarity, VisualRarity::Common);
+            assert_eq!(contract.get_rarity_stats()[0], (VisualRarity::Common, 1));
+            assert_eq!(contract.reveal_rarity(plain), Err(ICOError::RarityNotPending));
+
+            // Pendente: revelado uma vez a partir da semente da rodada
+            let nft_id = contract.mint_free().unwrap();
+            // Estado de um mint com beacon: pendente e fora da contagem
+            contract.rarity_counts.insert(VisualRarity::Common, &1);
+            contract.pending_rarity.insert(nft_id, &7);
+            let rarity = contract.apply_rarity(nft_id, 7, &[4; 32]).unwrap();
+            assert_eq!(rarity, FiapoICO::determine_rarity(&[4; 32], nft_id));
+            assert_eq!(contract.get_nft(nft_id).unwrap().visual_rarity, rarity);
+            assert_eq!(contract.get_pending_rarity(nft_id), None);
+            let total: u32 = contract.get_rarity_stats().iter().map(|(_, count)| count).sum();
+            assert_eq!(total, 2);
+
+            // Distribuição aproximada: 50% Common em 1000 NFTs
+            let common = (1..=1000u64)
+                .filter(|id| FiapoICO::determine_rarity(&[4; 32], *id) == VisualRarity::Common)
+                .count();
+            assert!((400..600).contains(&common));
+        }
+
+        #[ink::test]
+        fn mining_boost_counts_only_while_active() {
+            let accounts = default_accounts();
+            let mut contract = create_contract();
+
+            // Boost de evolução obtido no fim de um intervalo de 4 durações
+            let end = 4 * MINING_BOOST_DURATION;
+            contract.user_mining_boost.insert(accounts.alice, &end);
+            assert_eq!(contract.user_mining_boost_over(accounts.alice, 0, end), MINING_BOOST_BPS / 4);
+            assert_eq!(contract.user_mining_boost_over(accounts.alice, end - MINING_BOOST_DURATION, end), MINING_BOOST_BPS);
+            assert_eq!(contract.user_mining_boost_over(accounts.alice, end, 2 * end), 0);
+        }
     }
 }
 
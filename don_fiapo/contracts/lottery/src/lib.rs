@@ -4,6 +4,14 @@
 //! - Sorteio mensal "God looked at you" - 5% das taxas mensais
 //! - Sorteio de Natal - 5% das taxas anuais
 //! - Exclui whales (top 100 carteiras)
+//! - Participantes: compradores de tickets on-chain e holders que se inscrevem
+//!   (`enroll`), com peso pelo número de tickets (mínimo 1, opcionalmente multiplicado
+//!   pelo saldo FIAPO). Elegibilidade e exclusão de whales usam o checkpoint do
+//!   fiapo-core no bloco do pedido, não saldos informados pelo owner.
+//! - Duas fases: o pedido do sorteio reserva o fundo, congela os participantes e pede
+//!   aleatoriedade ao beacon; os participantes são processados em páginas
+//!   (`process_draw_page`) e, após a rodada, qualquer um liquida (`settle_draw`).
+//!   Ganhadores sorteados por peso acumulado, sem reposição, reproduzíveis pela semente.
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
@@ -11,10 +19,18 @@ use fiapo_traits::{AccountId, Balance};
 
 #[ink::contract]
 mod fiapo_lottery {
-    use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
+    use fiapo_logics::traits::psp22::{PSP22, PSP22Ref, PSP22Votes, PSP22VotesRef};
+    use fiapo_logics::traits::randomness::{derive_random, random_below_u128, Randomness, RandomnessRef};
     use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
 
+    /// Casas decimais do FIAPO (peso por saldo em FIAPO inteiros)
+    pub const SCALE: u128 = 100_000_000;
+    /// Participantes processados por chamada de `process_draw_page`
+    pub const MAX_DRAW_PAGE: u32 = 100;
+    /// Ganhadores por sorteio
+    pub const WINNERS_PER_DRAW: u32 = 3;
+
     /// Tipo de sorteio
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
@@ -34,6 +50,13 @@ mod fiapo_lottery {
         TooEarlyForDraw,
         Unauthorized,
         AlreadyExecuted,
+        RandomnessNotConfigured,
+        RandomnessPending,
+        DrawPending,
+        NoPendingDraw,
+        ParticipantsPending,
+        AlreadyEnrolled,
+        BalanceOutOfRange,
     }
 
     /// Configuração do sorteio
@@ -50,6 +73,8 @@ mod fiapo_lottery {
         pub min_balance: Balance,
         /// Saldo máximo (acima é whale)
         pub max_balance: Balance,
+        /// Peso = tickets × saldo em FIAPO inteiros (senão, apenas tickets)
+        pub balance_weighted: bool,
     }
 
     impl Default for LotteryConfig {
@@ -60,6 +85,7 @@ mod fiapo_lottery {
                 third_place_bps: 2000,  // 20%
                 min_balance: 1000 * 100_000_000,       // 1000 FIAPO
                 max_balance: 10_000_000 * 100_000_000, // 10M FIAPO
+                balance_weighted: false,
             }
         }
     }
@@ -83,6 +109,47 @@ mod fiapo_lottery {
         pub total_fund: Balance,
         pub participants: u32,
         pub executed_at: u64,
+        /// Semente do beacon: reproduz a escolha com `get_draw_entries`
+        pub seed: [u8; 32],
+        pub total_weight: u128,
+    }
+
+    /// Participante elegível com seu intervalo de peso acumulado
+    /// `[cumulative - weight, cumulative)`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct DrawEntry {
+        pub account: AccountId,
+        pub weight: u128,
+        pub cumulative: u128,
+    }
+
+    /// Sorteio aguardando o processamento dos participantes e a rodada do beacon
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PendingDraw {
+        pub id: u64,
+        pub lottery_type: LotteryType,
+        /// Época de tickets congelada no pedido
+        pub epoch: u64,
+        pub fund: Balance,
+        pub round: u64,
+        pub requested_at: u64,
+        /// Bloco dos saldos usados no filtro e no peso
+        pub snapshot_block: u32,
+        pub participants: u32,
+        pub processed: u32,
+        pub eligible: u32,
+        pub total_weight: u128,
+    }
+
+    /// Evento de sorteio pedido ao beacon
+    #[ink(event)]
+    pub struct LotteryRequested {
+        #[ink(topic)]
+        lottery_id: u64,
+        lottery_type: LotteryType,
+        round: u64,
     }
 
     /// Evento de sorteio executado
@@ -120,10 +187,21 @@ mod fiapo_lottery {
         monthly_fund: Balance,
         /// Fundo acumulado anual
         annual_fund: Balance,
-        /// Tickets por usuário para o próximo sorteio
-        user_tickets: Mapping<AccountId, u32>,
-        /// Lista de participantes do próximo sorteio
-        participants: Vec<AccountId>,
+        /// Época de tickets atual; avança a cada pedido de sorteio
+        ticket_epoch: u64,
+        /// Tickets por (época, usuário)
+        user_tickets: Mapping<(u64, AccountId), u32>,
+        /// Participantes por (época, posição)
+        participants: Mapping<(u64, u32), AccountId>,
+        participant_count: Mapping<u64, u32>,
+        /// Participação por (época, usuário)
+        joined: Mapping<(u64, AccountId), ()>,
+        /// Elegíveis processados por (sorteio, posição)
+        draw_entries: Mapping<(u64, u32), DrawEntry>,
+        /// Beacon de aleatoriedade
+        randomness_contract: Option<AccountId>,
+        /// Sorteio pedido e ainda não liquidado
+        pending_draw: Option<PendingDraw>,
     }
 
     impl FiapoLottery {
@@ -149,8 +227,14 @@ mod fiapo_lottery {
                 last_christmas: 0,
                 monthly_fund: 0,
                 annual_fund: 0,
+                ticket_epoch: 0,
                 user_tickets: Mapping::default(),
-                participants: Vec::new(),
+                participants: Mapping::default(),
+                participant_count: Mapping::default(),
+                joined: Mapping::default(),
+                draw_entries: Mapping::default(),
+                randomness_contract: None,
+                pending_draw: None,
             }
         }
 
@@ -232,17 +316,59 @@ mod fiapo_lottery {
                 return Err(LotteryError::NotEnoughParticipants);
             }
 
-            // Adiciona tickets ao usuário
-            let current_tickets = self.user_tickets.get(user).unwrap_or(0);
-            if current_tickets == 0 {
-                // Novo participante
-                self.participants.push(user);
+            // Adiciona tickets ao usuário na época atual
+            let epoch = self.ticket_epoch;
+            let current_tickets = self.user_tickets.get((epoch, user)).unwrap_or(0);
+            self.join(epoch, user);
+            self.user_tickets.insert((epoch, user), &current_tickets.saturating_add(quantity));
+
+            Ok(())
+        }
+
+        /// Inscreve o holder no próximo sorteio com peso base de 1 ticket.
+        /// O saldo atual só barra spam; vale o checkpoint no bloco do pedido.
+        #[ink(message)]
+        pub fn enroll(&mut self) -> Result<(), LotteryError> {
+            let caller = self.env().caller();
+            if self.joined.contains((self.ticket_epoch, caller)) {
+                return Err(LotteryError::AlreadyEnrolled);
+            }
+            let psp22: PSP22Ref = self.core_contract.into();
+            let balance = psp22.balance_of(caller);
+            // A época alimenta o sorteio mensal e o de Natal: vale a faixa mais ampla
+            let min_balance = self.monthly_config.min_balance.min(self.christmas_config.min_balance);
+            let max_balance = self.monthly_config.max_balance.max(self.christmas_config.max_balance);
+            if balance < min_balance || balance > max_balance {
+                return Err(LotteryError::BalanceOutOfRange);
             }
-            self.user_tickets.insert(user, &current_tickets.saturating_add(quantity));
+            self.enroll_account(caller)
+        }
 
+        /// Indica se o usuário participa do próximo sorteio
+        #[ink(message)]
+        pub fn is_enrolled(&self, user: AccountId) -> bool {
+            self.joined.contains((self.ticket_epoch, user))
+        }
+
+        fn enroll_account(&mut self, user: AccountId) -> Result<(), LotteryError> {
+            if !self.join(self.ticket_epoch, user) {
+                return Err(LotteryError::AlreadyEnrolled);
+            }
             Ok(())
         }
 
+        /// Adiciona o usuário aos participantes da época; false se já estava
+        fn join(&mut self, epoch: u64, user: AccountId) -> bool {
+            if self.joined.contains((epoch, user)) {
+                return false;
+            }
+            let count = self.participant_count.get(epoch).unwrap_or(0);
+            self.participants.insert((epoch, count), &user);
+            self.participant_count.insert(epoch, &count.saturating_add(1));
+            self.joined.insert((epoch, user), &());
+            true
+        }
+
         /// Configura contrato Oracle (apenas owner)
         #[ink(message)]
         pub fn set_oracle_contract(&mut self, oracle: AccountId) -> Result<(), LotteryError> {
@@ -253,26 +379,38 @@ mod fiapo_lottery {
             Ok(())
         }
 
-        /// Retorna tickets de um usuário
+        /// Retorna tickets de um usuário para o próximo sorteio
         #[ink(message)]
         pub fn get_user_tickets(&self, user: AccountId) -> u32 {
-            self.user_tickets.get(user).unwrap_or(0)
+            self.user_tickets.get((self.ticket_epoch, user)).unwrap_or(0)
         }
 
-        /// Retorna total de participantes
+        /// Retorna total de participantes do próximo sorteio
         #[ink(message)]
         pub fn get_participants_count(&self) -> u32 {
-            self.participants.len() as u32
+            self.participant_count.get(self.ticket_epoch).unwrap_or(0)
+        }
+
+        /// Participantes de uma época com seus tickets (paginado)
+        #[ink(message)]
+        pub fn get_participants(&self, epoch: u64, offset: u32, limit: u32) -> Vec<(AccountId, u32)> {
+            let end = offset.saturating_add(limit.min(MAX_DRAW_PAGE)).min(self.participant_count.get(epoch).unwrap_or(0));
+            (offset..end)
+                .filter_map(|index| self.participants.get((epoch, index)))
+                .map(|user| (user, self.user_tickets.get((epoch, user)).unwrap_or(0)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_ticket_epoch(&self) -> u64 {
+            self.ticket_epoch
         }
 
         // ==================== Draw Functions ====================
 
-        /// Executa sorteio mensal
+        /// Pede o sorteio mensal; retorna a rodada do beacon que o liquidará
         #[ink(message)]
-        pub fn execute_monthly_draw(
-            &mut self,
-            eligible_wallets: Vec<(AccountId, Balance)>,
-        ) -> Result<DrawResult, LotteryError> {
+        pub fn request_monthly_draw(&mut self) -> Result<u64, LotteryError> {
             let caller = self.env().caller();
             let current = self.env().block_timestamp();
 
@@ -290,25 +428,17 @@ mod fiapo_lottery {
                 return Err(LotteryError::NoFundsAvailable);
             }
 
-            let result = self.execute_draw(
-                eligible_wallets,
-                self.monthly_fund,
-                LotteryType::Monthly,
-                &self.monthly_config.clone(),
-            )?;
+            let round = self.request_draw(self.monthly_fund, LotteryType::Monthly)?;
 
             self.monthly_fund = 0;
             self.last_monthly = current;
 
-            Ok(result)
+            Ok(round)
         }
 
-        /// Executa sorteio de Natal
+        /// Pede o sorteio de Natal; retorna a rodada do beacon que o liquidará
         #[ink(message)]
-        pub fn execute_christmas_draw(
-            &mut self,
-            eligible_wallets: Vec<(AccountId, Balance)>,
-        ) -> Result<DrawResult, LotteryError> {
+        pub fn request_christmas_draw(&mut self) -> Result<u64, LotteryError> {
             let caller = self.env().caller();
             let current = self.env().block_timestamp();
 
@@ -326,69 +456,199 @@ mod fiapo_lottery {
                 return Err(LotteryError::NoFundsAvailable);
             }
 
-            let result = self.execute_draw(
-                eligible_wallets,
-                self.annual_fund,
-                LotteryType::Christmas,
-                &self.christmas_config.clone(),
-            )?;
+            let round = self.request_draw(self.annual_fund, LotteryType::Christmas)?;
 
             self.annual_fund = 0;
             self.last_christmas = current;
 
-            Ok(result)
+            Ok(round)
         }
 
-        /// Executa sorteio genérico
-        fn execute_draw(
-            &mut self,
-            wallets: Vec<(AccountId, Balance)>,
-            fund: Balance,
-            lottery_type: LotteryType,
-            config: &LotteryConfig,
-        ) -> Result<DrawResult, LotteryError> {
-            // Filtra elegíveis
-            let eligible: Vec<_> = wallets.into_iter()
-                .filter(|(_, bal)| *bal >= config.min_balance && *bal <= config.max_balance)
-                .collect();
+        /// Processa até `limit` participantes do sorteio pendente: filtra pelo saldo
+        /// no snapshot e acumula os pesos. Retorna quantos faltam (qualquer um pode chamar).
+        #[ink(message)]
+        pub fn process_draw_page(&mut self, limit: u32) -> Result<u32, LotteryError> {
+            let core = self.core_contract;
+            self.process_page(limit, |account, block| {
+                let votes: PSP22VotesRef = core.into();
+                votes.balance_of_at(account, block)
+            })
+        }
+
+        /// Liquida o sorteio pendente com a semente da rodada (qualquer um pode chamar)
+        #[ink(message)]
+        pub fn settle_draw(&mut self) -> Result<DrawResult, LotteryError> {
+            let pending = self.pending_draw.clone().ok_or(LotteryError::NoPendingDraw)?;
+            if pending.processed < pending.participants {
+                return Err(LotteryError::ParticipantsPending);
+            }
+            let beacon: RandomnessRef = self.randomness_contract.ok_or(LotteryError::RandomnessNotConfigured)?.into();
+            let seed = beacon.randomness(pending.round).ok_or(LotteryError::RandomnessPending)?;
+            self.pending_draw = None;
+            self.execute_draw(pending, seed)
+        }
+
+        /// Pede nova rodada quando a do sorteio pendente falhou sem entropia
+        #[ink(message)]
+        pub fn rerequest_draw_randomness(&mut self) -> Result<u64, LotteryError> {
+            let mut pending = self.pending_draw.clone().ok_or(LotteryError::NoPendingDraw)?;
+            let mut beacon: RandomnessRef = self.randomness_contract.ok_or(LotteryError::RandomnessNotConfigured)?.into();
+            if !beacon.round_failed(pending.round) {
+                return Err(LotteryError::RandomnessPending);
+            }
+            pending.round = beacon.request_randomness();
+            self.pending_draw = Some(pending.clone());
+            Ok(pending.round)
+        }
 
-            if eligible.len() < 3 {
+        /// Retorna o sorteio aguardando liquidação
+        #[ink(message)]
+        pub fn get_pending_draw(&self) -> Option<PendingDraw> {
+            self.pending_draw.clone()
+        }
+
+        /// Elegíveis de um sorteio com seus intervalos de peso (paginado)
+        #[ink(message)]
+        pub fn get_draw_entries(&self, draw_id: u64, offset: u32, limit: u32) -> Vec<DrawEntry> {
+            let end = offset.saturating_add(limit.min(MAX_DRAW_PAGE));
+            (offset..end)
+                .map_while(|index| self.draw_entries.get((draw_id, index)))
+                .collect()
+        }
+
+        /// Configura o beacon de aleatoriedade (apenas owner)
+        #[ink(message)]
+        pub fn set_randomness_contract(&mut self, randomness: AccountId) -> Result<(), LotteryError> {
+            if self.env().caller() != self.owner {
+                return Err(LotteryError::Unauthorized);
+            }
+            self.randomness_contract = Some(randomness);
+            Ok(())
+        }
+
+        /// Congela a época de tickets, reserva o fundo e pede a rodada ao beacon
+        fn request_draw(&mut self, fund: Balance, lottery_type: LotteryType) -> Result<u64, LotteryError> {
+            if self.pending_draw.is_some() {
+                return Err(LotteryError::DrawPending);
+            }
+            let randomness = self.randomness_contract.ok_or(LotteryError::RandomnessNotConfigured)?;
+
+            let epoch = self.ticket_epoch;
+            let participants = self.participant_count.get(epoch).unwrap_or(0);
+            if participants < WINNERS_PER_DRAW {
                 return Err(LotteryError::NotEnoughParticipants);
             }
 
-            // Seleciona 3 ganhadores pseudo-aleatórios
-            let winners = self.select_winners(eligible.clone(), 3);
+            let mut beacon: RandomnessRef = randomness.into();
+            let round = beacon.request_randomness();
+            let id = self.next_lottery_id;
+            self.next_lottery_id = self.next_lottery_id.saturating_add(1);
+            // Novos tickets vão para o próximo sorteio
+            self.ticket_epoch = epoch.saturating_add(1);
+            self.pending_draw = Some(PendingDraw {
+                id,
+                lottery_type: lottery_type.clone(),
+                epoch,
+                fund,
+                round,
+                requested_at: self.env().block_timestamp(),
+                snapshot_block: self.env().block_number().saturating_sub(1),
+                participants,
+                processed: 0,
+                eligible: 0,
+                total_weight: 0,
+            });
+
+            Self::env().emit_event(LotteryRequested { lottery_id: id, lottery_type, round });
+            Ok(round)
+        }
+
+        /// Processa uma página de participantes com os saldos do snapshot
+        fn process_page<F>(&mut self, limit: u32, balance_of: F) -> Result<u32, LotteryError>
+        where
+            F: Fn(AccountId, u32) -> Balance,
+        {
+            let mut pending = self.pending_draw.clone().ok_or(LotteryError::NoPendingDraw)?;
+            let config = match pending.lottery_type {
+                LotteryType::Monthly => self.monthly_config.clone(),
+                LotteryType::Christmas => self.christmas_config.clone(),
+            };
+
+            let end = pending.processed
+                .saturating_add(limit.min(MAX_DRAW_PAGE))
+                .min(pending.participants);
+            for index in pending.processed..end {
+                let Some(account) = self.participants.get((pending.epoch, index)) else { continue };
+                // Inscritos sem tickets entram com peso base 1
+                let tickets = self.user_tickets.get((pending.epoch, account)).unwrap_or(0).max(1) as u128;
+                let balance = balance_of(account, pending.snapshot_block);
+                if balance < config.min_balance || balance > config.max_balance {
+                    continue;
+                }
+                let weight = if config.balance_weighted {
+                    tickets.saturating_mul(balance.checked_div(SCALE).unwrap_or(0))
+                } else {
+                    tickets
+                };
+                if weight == 0 {
+                    continue;
+                }
+                pending.total_weight = pending.total_weight.saturating_add(weight);
+                self.draw_entries.insert(
+                    (pending.id, pending.eligible),
+                    &DrawEntry { account, weight, cumulative: pending.total_weight },
+                );
+                pending.eligible = pending.eligible.saturating_add(1);
+            }
+            pending.processed = end;
+            let remaining = pending.participants.saturating_sub(end);
+            self.pending_draw = Some(pending);
+            Ok(remaining)
+        }
+
+        /// Executa o sorteio pedido com a semente da rodada
+        fn execute_draw(&mut self, pending: PendingDraw, seed: [u8; 32]) -> Result<DrawResult, LotteryError> {
+            let PendingDraw { id, lottery_type, fund, eligible, total_weight, .. } = pending;
+            let config = match lottery_type {
+                LotteryType::Monthly => self.monthly_config.clone(),
+                LotteryType::Christmas => self.christmas_config.clone(),
+            };
+
+            // Seleciona até 3 ganhadores a partir da semente
+            let winners = self.select_winners(id, eligible, total_weight, WINNERS_PER_DRAW, &seed);
 
             // Calcula prêmios
-            let first_prize = fund.saturating_mul(config.first_place_bps as u128).saturating_div(10000);
-            let second_prize = fund.saturating_mul(config.second_place_bps as u128).saturating_div(10000);
-            let third_prize = fund.saturating_mul(config.third_place_bps as u128).saturating_div(10000);
+            let prizes = [config.first_place_bps, config.second_place_bps, config.third_place_bps]
+                .map(|bps| fund.saturating_mul(bps as u128).saturating_div(10000));
 
-            let winner_list = ink::prelude::vec![
-                Winner { wallet: winners[0], prize: first_prize, position: 1 },
-                Winner { wallet: winners[1], prize: second_prize, position: 2 },
-                Winner { wallet: winners[2], prize: third_prize, position: 3 },
-            ];
+            let winner_list: Vec<Winner> = winners.iter()
+                .zip(prizes.iter())
+                .enumerate()
+                .map(|(i, (wallet, prize))| Winner { wallet: *wallet, prize: *prize, position: (i as u8).saturating_add(1) })
+                .collect();
 
             // Transfere prêmios para os ganhadores via cross-contract call
             for winner in &winner_list {
                 let _ = self.call_core_transfer_prize(winner.wallet, winner.prize);
             }
 
-            // Limpa tickets dos participantes
-            for (wallet, _) in &eligible {
-                self.user_tickets.insert(*wallet, &0);
+            // Prêmios sem ganhador voltam ao fundo
+            let unawarded: Balance = prizes.iter().skip(winner_list.len()).sum();
+            match lottery_type {
+                LotteryType::Monthly => self.monthly_fund = self.monthly_fund.saturating_add(unawarded),
+                LotteryType::Christmas => self.annual_fund = self.annual_fund.saturating_add(unawarded),
             }
-            self.participants.clear();
 
             let current = self.env().block_timestamp();
             let result = DrawResult {
-                id: self.next_lottery_id,
+                id,
                 lottery_type: lottery_type.clone(),
                 winners: winner_list.clone(),
                 total_fund: fund,
-                participants: eligible.len() as u32,
+                participants: eligible,
                 executed_at: current,
+                seed,
+                total_weight,
             };
 
             // Salva histórico (máximo 50)
@@ -396,14 +656,15 @@ mod fiapo_lottery {
                 self.history.remove(0);
             }
             self.history.push(result.clone());
-            self.next_lottery_id = self.next_lottery_id.saturating_add(1);
 
-            Self::env().emit_event(LotteryExecuted {
-                lottery_id: result.id,
-                lottery_type,
-                first_winner: winner_list[0].wallet,
-                first_prize,
-            });
+            if let Some(first) = winner_list.first() {
+                Self::env().emit_event(LotteryExecuted {
+                    lottery_id: result.id,
+                    lottery_type,
+                    first_winner: first.wallet,
+                    first_prize: first.prize,
+                });
+            }
 
             Ok(result)
         }
@@ -417,25 +678,45 @@ mod fiapo_lottery {
             }
         }
 
-        /// Seleciona ganhadores pseudo-aleatórios
-        fn select_winners(&self, mut wallets: Vec<(AccountId, Balance)>, count: usize) -> Vec<AccountId> {
+        /// Sorteia por peso acumulado, sem reposição: cada sorteio cai em
+        /// `[0, peso restante)` e pula os intervalos dos já escolhidos
+        fn select_winners(&self, draw_id: u64, eligible: u32, total_weight: u128, count: u32, seed: &[u8; 32]) -> Vec<AccountId> {
             let mut winners = Vec::new();
-            let block = self.env().block_number();
-            let time = self.env().block_timestamp();
+            // Intervalos já sorteados (início, peso), ordenados pelo início
+            let mut excluded: Vec<(u128, u128)> = Vec::new();
+            let mut remaining = total_weight;
 
-            for i in 0..count {
-                if wallets.is_empty() {
+            for i in 0..count.min(eligible) {
+                if remaining == 0 {
                     break;
                 }
-                
-                let seed = (block as u64).saturating_add(time).saturating_add(i as u64);
-                if wallets.is_empty() {
-                    break;
+                let random = derive_random(seed, &(draw_id, i));
+                let mut target = random_below_u128(&random, remaining);
+                for (start, weight) in &excluded {
+                    if target < *start {
+                        break;
+                    }
+                    target = target.saturating_add(*weight);
                 }
-                let len = wallets.len() as u64;
-                let idx = (seed.checked_rem(len).unwrap_or(0)) as usize;
-                let (winner, _) = wallets.remove(idx);
-                winners.push(winner);
+
+                // Primeira entrada com acumulado > alvo
+                let (mut low, mut high) = (0u32, eligible);
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    let cumulative = self.draw_entries.get((draw_id, mid)).map(|e| e.cumulative).unwrap_or(u128::MAX);
+                    if cumulative > target {
+                        high = mid;
+                    } else {
+                        low = mid + 1;
+                    }
+                }
+                let Some(entry) = self.draw_entries.get((draw_id, low)) else { break };
+
+                let start = entry.cumulative.saturating_sub(entry.weight);
+                let position = excluded.partition_point(|(other, _)| *other < start);
+                excluded.insert(position, (start, entry.weight));
+                remaining = remaining.saturating_sub(entry.weight);
+                winners.push(entry.account);
             }
 
             winners
@@ -471,6 +752,166 @@ mod fiapo_lottery {
             assert_eq!(config.second_place_bps, 3000);
             assert_eq!(config.third_place_bps, 2000);
         }
+
+        fn buy(contract: &mut FiapoLottery, oracle: AccountId, user: AccountId, quantity: u32) {
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(oracle);
+            contract.buy_tickets_for(user, quantity).unwrap();
+        }
+
+        /// Insere o sorteio pendente diretamente (sem chamada ao beacon)
+        fn pending(contract: &mut FiapoLottery, id: u64, epoch: u64) {
+            contract.pending_draw = Some(PendingDraw {
+                id,
+                lottery_type: LotteryType::Monthly,
+                epoch,
+                fund: 1000,
+                round: 1,
+                requested_at: 0,
+                snapshot_block: 7,
+                participants: contract.participant_count.get(epoch).unwrap_or(0),
+                processed: 0,
+                eligible: 0,
+                total_weight: 0,
+            });
+        }
+
+        #[ink::test]
+        fn draw_requires_beacon_and_processed_participants() {
+            let accounts = default_accounts();
+            let mut contract = FiapoLottery::new(accounts.charlie);
+            contract.add_monthly_fund(1000).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(30 * 24 * 60 * 60 * 1000);
+
+            assert_eq!(contract.request_monthly_draw(), Err(LotteryError::RandomnessNotConfigured));
+            assert_eq!(contract.settle_draw(), Err(LotteryError::NoPendingDraw));
+            assert_eq!(contract.process_draw_page(10), Err(LotteryError::NoPendingDraw));
+
+            contract.set_oracle_contract(accounts.django).unwrap();
+            buy(&mut contract, accounts.django, accounts.alice, 1);
+            pending(&mut contract, 0, 0);
+            assert_eq!(contract.settle_draw(), Err(LotteryError::ParticipantsPending));
+        }
+
+        #[ink::test]
+        fn participant_pages_filter_snapshot_balances() {
+            let accounts = default_accounts();
+            let mut contract = FiapoLottery::new(accounts.charlie);
+            contract.set_oracle_contract(accounts.django).unwrap();
+            let min = contract.get_monthly_config().min_balance;
+            let max = contract.get_monthly_config().max_balance;
+
+            buy(&mut contract, accounts.django, accounts.alice, 2);
+            buy(&mut contract, accounts.django, accounts.bob, 3);
+            buy(&mut contract, accounts.django, accounts.eve, 5);
+            buy(&mut contract, accounts.django, accounts.frank, 4);
+            buy(&mut contract, accounts.django, accounts.alice, 1);
+            assert_eq!(contract.get_participants_count(), 4);
+            assert_eq!(contract.get_user_tickets(accounts.alice), 3);
+            pending(&mut contract, 0, 0);
+            contract.ticket_epoch = 1;
+            assert_eq!(contract.get_user_tickets(accounts.alice), 0);
+            assert_eq!(contract.get_participants(0, 0, 10).len(), 4);
+
+            // Bob é whale e Eve está abaixo do mínimo no bloco do snapshot
+            let balances = move |account: AccountId, block: u32| {
+                assert_eq!(block, 7);
+                if account == accounts.bob { max + 1 } else if account == accounts.eve { min - 1 } else { min }
+            };
+            assert_eq!(contract.process_page(3, balances), Ok(1));
+            assert_eq!(contract.process_page(3, balances), Ok(0));
+
+            let entries = contract.get_draw_entries(0, 0, 10);
+            assert_eq!(entries, ink::prelude::vec![
+                DrawEntry { account: accounts.alice, weight: 3, cumulative: 3 },
+                DrawEntry { account: accounts.frank, weight: 4, cumulative: 7 },
+            ]);
+            let draw = contract.get_pending_draw().unwrap();
+            assert_eq!((draw.processed, draw.eligible, draw.total_weight), (4, 2, 7));
+
+            // Só dois elegíveis: sobram dois ganhadores para três prêmios
+            let winners = contract.select_winners(0, draw.eligible, draw.total_weight, WINNERS_PER_DRAW, &[9; 32]);
+            assert_eq!(winners.len(), 2);
+        }
+
+        #[ink::test]
+        fn enrolled_holders_are_checked_against_snapshot() {
+            let accounts = default_accounts();
+            let mut contract = FiapoLottery::new(accounts.charlie);
+            contract.set_oracle_contract(accounts.django).unwrap();
+            let max = contract.get_monthly_config().max_balance;
+
+            // Inscrição sem tickets; comprar depois não duplica o participante
+            contract.enroll_account(accounts.alice).unwrap();
+            assert_eq!(contract.enroll_account(accounts.alice), Err(LotteryError::AlreadyEnrolled));
+            contract.enroll_account(accounts.bob).unwrap();
+            buy(&mut contract, accounts.django, accounts.bob, 2);
+            contract.enroll_account(accounts.eve).unwrap();
+            assert!(contract.is_enrolled(accounts.bob));
+            assert_eq!(contract.get_participants_count(), 3);
+
+            pending(&mut contract, 0, 0);
+            contract.ticket_epoch = 1;
+            assert!(!contract.is_enrolled(accounts.alice));
+
+            // Eve virou whale até o snapshot
+            let balances = move |account: AccountId, _| if account == accounts.eve { max + 1 } else { max };
+            assert_eq!(contract.process_page(10, balances), Ok(0));
+            let entries = contract.get_draw_entries(0, 0, 10);
+            assert_eq!(entries, ink::prelude::vec![
+                DrawEntry { account: accounts.alice, weight: 1, cumulative: 1 },
+                DrawEntry { account: accounts.bob, weight: 2, cumulative: 3 },
+            ]);
+        }
+
+        #[ink::test]
+        fn weighted_selection_is_reproducible_without_replacement() {
+            let accounts = default_accounts();
+            let mut contract = FiapoLottery::new(accounts.charlie);
+            let wallets = [accounts.alice, accounts.bob, accounts.django, accounts.eve, accounts.frank];
+            let mut cumulative = 0;
+            for (index, (account, weight)) in wallets.iter().zip([1u128, 1, 98, 1, 1]).enumerate() {
+                cumulative += weight;
+                contract.draw_entries.insert((1, index as u32), &DrawEntry { account: *account, weight, cumulative });
+            }
+
+            let winners = contract.select_winners(1, 5, cumulative, 3, &[7; 32]);
+            assert_eq!(winners, contract.select_winners(1, 5, cumulative, 3, &[7; 32]));
+            assert_eq!(winners.len(), 3);
+            assert!(winners.iter().all(|winner| winners.iter().filter(|other| *other == winner).count() == 1));
+
+            // Django concentra 98% do peso: primeiro lugar quase sempre
+            let firsts = (0..200u8)
+                .filter(|i| contract.select_winners(1, 5, cumulative, 1, &[*i; 32])[0] == accounts.django)
+                .count();
+            assert!(firsts > 180);
+
+            // Todos os elegíveis saem quando o número de prêmios cobre o conjunto
+            let mut all = contract.select_winners(1, 5, cumulative, 5, &[3; 32]);
+            all.sort();
+            let mut expected = wallets.to_vec();
+            expected.sort();
+            assert_eq!(all, expected);
+        }
+
+        #[ink::test]
+        fn balance_weighting_multiplies_tickets() {
+            let accounts = default_accounts();
+            let mut contract = FiapoLottery::new(accounts.charlie);
+            contract.set_oracle_contract(accounts.django).unwrap();
+            let mut config = contract.get_monthly_config();
+            config.balance_weighted = true;
+            contract.monthly_config = config;
+
+            buy(&mut contract, accounts.django, accounts.alice, 2);
+            buy(&mut contract, accounts.django, accounts.bob, 1);
+            pending(&mut contract, 0, 0);
+            let balances = move |account: AccountId, _| {
+                if account == accounts.alice { 1_500 * SCALE } else { 5_000 * SCALE + 1 }
+            };
+            assert_eq!(contract.process_page(10, balances), Ok(0));
+            let weights: Vec<u128> = contract.get_draw_entries(0, 0, 10).iter().map(|e| e.weight).collect();
+            assert_eq!(weights, ink::prelude::vec![3_000, 5_000]);
+        }
     }
 }
 
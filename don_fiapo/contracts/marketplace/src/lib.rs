@@ -20,6 +20,9 @@ mod fiapo_marketplace {
     // Cross-contract: PSP22Ref garante selector correto do trait IPSP22
     use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
 
+    /// Teto das taxas de venda e troca (10%)
+    pub const MAX_FEE_BPS: u16 = 1000;
+
     // ==================== Errors ====================
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -43,6 +46,8 @@ mod fiapo_marketplace {
         TransferFailed,
         InsufficientPayment,
         NativeTransferFailed,
+        StakingNotConfigured,
+        InvalidFee,
     }
 
     // ==================== Types ====================
@@ -108,6 +113,24 @@ mod fiapo_marketplace {
         tokens_claimed_for_seller: u128,
     }
 
+    #[ink(event)]
+    pub struct PositionListed {
+        #[ink(topic)]
+        position_id: u64,
+        seller: AccountId,
+        price: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PositionSold {
+        #[ink(topic)]
+        position_id: u64,
+        seller: AccountId,
+        buyer: AccountId,
+        price: Balance,
+        rewards_settled_for_seller: u128,
+    }
+
     #[ink(event)]
     pub struct AuctionCreated {
         #[ink(topic)]
@@ -173,12 +196,20 @@ mod fiapo_marketplace {
         noble_contract: Option<AccountId>,
         owner: AccountId,
         team_wallet: AccountId,
+        /// Governança pode ajustar taxas
+        governance_contract: Option<AccountId>,
+        /// Timelock que aplica as taxas votadas
+        timelock_contract: Option<AccountId>,
 
         // --- Listings (venda direta) ---
         listings: Mapping<u64, Listing>,
         active_listings: Vec<u64>,
         fee_bps: u16,
 
+        // --- Listings de posições de staking (NFT de posição) ---
+        position_listings: Mapping<u64, Listing>,
+        active_position_listings: Vec<u64>,
+
         // --- Auctions (leilão) ---
         auctions: Mapping<u64, Auction>,
         active_auctions: Vec<u64>,
@@ -212,9 +243,13 @@ mod fiapo_marketplace {
                 noble_contract: None,
                 owner: caller,
                 team_wallet: caller,
+                governance_contract: None,
+                timelock_contract: None,
                 listings: Mapping::default(),
                 active_listings: Vec::new(),
                 fee_bps: 600,
+                position_listings: Mapping::default(),
+                active_position_listings: Vec::new(),
                 auctions: Mapping::default(),
                 active_auctions: Vec::new(),
                 next_auction_id: 1,
@@ -283,7 +318,10 @@ mod fiapo_marketplace {
         /// Configura taxa de venda (em bps, ex: 600 = 6%)
         #[ink(message)]
         pub fn set_fee_bps(&mut self, fee: u16) -> Result<(), MarketplaceError> {
-            self.ensure_owner()?;
+            self.ensure_owner_or_governance()?;
+            if fee > MAX_FEE_BPS {
+                return Err(MarketplaceError::InvalidFee);
+            }
             self.fee_bps = fee;
             Ok(())
         }
@@ -291,11 +329,28 @@ mod fiapo_marketplace {
         /// Configura taxa de troca P2P (em bps, ex: 300 = 3%)
         #[ink(message)]
         pub fn set_trade_fee_bps(&mut self, fee: u16) -> Result<(), MarketplaceError> {
-            self.ensure_owner()?;
+            self.ensure_owner_or_governance()?;
+            if fee > MAX_FEE_BPS {
+                return Err(MarketplaceError::InvalidFee);
+            }
             self.trade_fee_bps = fee;
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn set_governance_contract(&mut self, governance: Option<AccountId>) -> Result<(), MarketplaceError> {
+            self.ensure_owner()?;
+            self.governance_contract = governance;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_timelock_contract(&mut self, timelock: Option<AccountId>) -> Result<(), MarketplaceError> {
+            self.ensure_owner()?;
+            self.timelock_contract = timelock;
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), MarketplaceError> {
             self.ensure_owner()?;
@@ -310,6 +365,17 @@ mod fiapo_marketplace {
             Ok(())
         }
 
+        fn ensure_owner_or_governance(&self) -> Result<(), MarketplaceError> {
+            let caller = self.env().caller();
+            if caller != self.owner
+                && Some(caller) != self.governance_contract
+                && Some(caller) != self.timelock_contract
+            {
+                return Err(MarketplaceError::Unauthorized);
+            }
+            Ok(())
+        }
+
         // ==================== View Functions ====================
 
         #[ink(message)]
@@ -327,6 +393,16 @@ mod fiapo_marketplace {
             self.active_listings.clone()
         }
 
+        #[ink(message)]
+        pub fn get_position_listing(&self, position_id: u64) -> Option<Listing> {
+            self.position_listings.get(position_id)
+        }
+
+        #[ink(message)]
+        pub fn get_active_position_listings(&self) -> Vec<u64> {
+            self.active_position_listings.clone()
+        }
+
         #[ink(message)]
         pub fn get_auction(&self, auction_id: u64) -> Option<Auction> {
             self.auctions.get(auction_id)
@@ -442,6 +518,30 @@ mod fiapo_marketplace {
                 return Err(MarketplaceError::ListingNotFound);
             }
 
+            let total_fee = self.collect_sale_payment(buyer, &listing, affiliate_code)?;
+
+            // Transfere NFT com auto-claim (seller recebe tokens pendentes de mining)
+            let claimed = self.call_ico_marketplace_transfer(listing.seller, buyer, nft_id)?;
+
+            listing.active = false;
+            self.listings.insert(nft_id, &listing);
+            self.active_listings.retain(|&id| id != nft_id);
+            self.total_volume = self.total_volume.saturating_add(listing.price);
+            self.total_fees_collected = self.total_fees_collected.saturating_add(total_fee);
+
+            Self::env().emit_event(NFTSold {
+                nft_id,
+                seller: listing.seller,
+                buyer,
+                price: listing.price,
+                tokens_claimed_for_seller: claimed,
+            });
+
+            Ok(())
+        }
+
+        /// Cobra o comprador e distribui o valor da venda (seller + taxas)
+        fn collect_sale_payment(&self, buyer: AccountId, listing: &Listing, affiliate_code: Option<Hash>) -> Result<Balance, MarketplaceError> {
             // currency da listagem: 0=LUNES(nativo), 1=FIAPO(PSP22)
             let is_lunes = listing.currency == 0;
 
@@ -491,37 +591,114 @@ mod fiapo_marketplace {
                 }
             }
 
-            // Transfere NFT com auto-claim (seller recebe tokens pendentes de mining)
-            let claimed = self.call_ico_marketplace_transfer(listing.seller, buyer, nft_id)?;
+            Ok(total_fee)
+        }
 
+        #[ink(message)]
+        pub fn cancel_listing(&mut self, nft_id: u64) -> Result<(), MarketplaceError> {
+            let caller = self.env().caller();
+            let mut listing = self.listings.get(nft_id)
+                .ok_or(MarketplaceError::ListingNotFound)?;
+            if listing.seller != caller {
+                return Err(MarketplaceError::Unauthorized);
+            }
             listing.active = false;
             self.listings.insert(nft_id, &listing);
             self.active_listings.retain(|&id| id != nft_id);
+            Ok(())
+        }
+
+        // ==================== Posições de Staking ====================
+
+        /// Lista uma posição de staking (NFT de posição) para venda.
+        /// currency: 0=LUNES, 1=FIAPO (FIAPO só permitido após ICO)
+        #[ink(message)]
+        pub fn list_position(&mut self, position_id: u64, price: Balance, currency: u8) -> Result<(), MarketplaceError> {
+            let caller = self.env().caller();
+            if self.staking_contract.is_none() {
+                return Err(MarketplaceError::StakingNotConfigured);
+            }
+            // Só o owner atual da posição pode listá-la
+            if self.call_staking_owner_of(position_id)? != Some(caller) {
+                return Err(MarketplaceError::NFTNotOwned);
+            }
+
+            self.insert_position_listing(caller, position_id, price, currency);
+            Ok(())
+        }
+
+        fn insert_position_listing(&mut self, caller: AccountId, position_id: u64, price: Balance, currency: u8) {
+            let effective_currency = if !self.ico_sales_completed { 0 } else { currency };
+
+            let listing = Listing {
+                nft_id: position_id,
+                seller: caller,
+                price,
+                nft_tier: 0,
+                currency: effective_currency,
+                active: true,
+            };
+
+            self.position_listings.insert(position_id, &listing);
+            if !self.active_position_listings.contains(&position_id) {
+                self.active_position_listings.push(position_id);
+            }
+
+            Self::env().emit_event(PositionListed {
+                position_id,
+                seller: caller,
+                price,
+            });
+        }
+
+        /// Compra uma posição listada. As recompensas pendentes são liquidadas
+        /// para o seller pelo contrato de staking.
+        #[ink(message, payable)]
+        pub fn buy_position(&mut self, position_id: u64) -> Result<(), MarketplaceError> {
+            let buyer = self.env().caller();
+
+            let mut listing = self.position_listings.get(position_id)
+                .ok_or(MarketplaceError::ListingNotFound)?;
+            if !listing.active {
+                return Err(MarketplaceError::ListingNotFound);
+            }
+            // Listing obsoleto: a posição mudou de dono depois de listada
+            if self.call_staking_owner_of(position_id)? != Some(listing.seller) {
+                return Err(MarketplaceError::NFTNotOwned);
+            }
+
+            let total_fee = self.collect_sale_payment(buyer, &listing, None)?;
+
+            let settled = self.call_staking_marketplace_transfer(listing.seller, buyer, position_id)?;
+
+            listing.active = false;
+            self.position_listings.insert(position_id, &listing);
+            self.active_position_listings.retain(|&id| id != position_id);
             self.total_volume = self.total_volume.saturating_add(listing.price);
             self.total_fees_collected = self.total_fees_collected.saturating_add(total_fee);
 
-            Self::env().emit_event(NFTSold {
-                nft_id,
+            Self::env().emit_event(PositionSold {
+                position_id,
                 seller: listing.seller,
                 buyer,
                 price: listing.price,
-                tokens_claimed_for_seller: claimed,
+                rewards_settled_for_seller: settled,
             });
 
             Ok(())
         }
 
         #[ink(message)]
-        pub fn cancel_listing(&mut self, nft_id: u64) -> Result<(), MarketplaceError> {
+        pub fn cancel_position_listing(&mut self, position_id: u64) -> Result<(), MarketplaceError> {
             let caller = self.env().caller();
-            let mut listing = self.listings.get(nft_id)
+            let mut listing = self.position_listings.get(position_id)
                 .ok_or(MarketplaceError::ListingNotFound)?;
             if listing.seller != caller {
                 return Err(MarketplaceError::Unauthorized);
             }
             listing.active = false;
-            self.listings.insert(nft_id, &listing);
-            self.active_listings.retain(|&id| id != nft_id);
+            self.position_listings.insert(position_id, &listing);
+            self.active_position_listings.retain(|&id| id != position_id);
             Ok(())
         }
 
@@ -988,6 +1165,66 @@ mod fiapo_marketplace {
             }
         }
 
+        /// Staking: owner_of (dono atual da posição)
+        #[cfg(not(test))]
+        fn call_staking_owner_of(&self, position_id: u64) -> Result<Option<AccountId>, MarketplaceError> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            let staking = self.staking_contract.ok_or(MarketplaceError::StakingNotConfigured)?;
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(staking)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("owner_of")))
+                        .push_arg(position_id),
+                )
+                .returns::<Option<AccountId>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(owner)) => Ok(owner),
+                _ => Err(MarketplaceError::NFTNotOwned),
+            }
+        }
+
+        /// Staking: marketplace_transfer_position (auto-claim + transfer)
+        #[cfg(not(test))]
+        fn call_staking_marketplace_transfer(&self, from: AccountId, to: AccountId, position_id: u64) -> Result<u128, MarketplaceError> {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            let staking = self.staking_contract.ok_or(MarketplaceError::StakingNotConfigured)?;
+            let result = build_call::<ink::env::DefaultEnvironment>()
+                .call(staking)
+                .gas_limit(0)
+                .transferred_value(0)
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!("marketplace_transfer_position")))
+                        .push_arg(from)
+                        .push_arg(to)
+                        .push_arg(position_id),
+                )
+                .returns::<Result<u128, u8>>()
+                .try_invoke();
+            match result {
+                Ok(Ok(Ok(settled))) => Ok(settled),
+                _ => Err(MarketplaceError::NFTNotOwned),
+            }
+        }
+
+        /// Off-chain não há chamadas cross-contract: as posições vêm de `tests::staking_owner`
+        #[cfg(test)]
+        fn call_staking_owner_of(&self, position_id: u64) -> Result<Option<AccountId>, MarketplaceError> {
+            self.staking_contract.ok_or(MarketplaceError::StakingNotConfigured)?;
+            Ok(tests::staking_owner(position_id))
+        }
+
+        #[cfg(test)]
+        fn call_staking_marketplace_transfer(&self, from: AccountId, to: AccountId, position_id: u64) -> Result<u128, MarketplaceError> {
+            if self.call_staking_owner_of(position_id)? != Some(from) {
+                return Err(MarketplaceError::NFTNotOwned);
+            }
+            tests::set_staking_owner(position_id, to);
+            Ok(0)
+        }
+
         /// Transferência nativa de LUNES do saldo do contrato
         fn native_transfer(&self, to: AccountId, amount: Balance) -> Result<(), MarketplaceError> {
             self.env().transfer(to, amount)
@@ -1005,6 +1242,20 @@ mod fiapo_marketplace {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use std::{cell::RefCell, collections::BTreeMap};
+
+        std::thread_local! {
+            /// Donos das posições no staking simulado
+            static STAKING_OWNERS: RefCell<BTreeMap<u64, AccountId>> = const { RefCell::new(BTreeMap::new()) };
+        }
+
+        pub(super) fn staking_owner(position_id: u64) -> Option<AccountId> {
+            STAKING_OWNERS.with(|owners| owners.borrow().get(&position_id).copied())
+        }
+
+        pub(super) fn set_staking_owner(position_id: u64, owner: AccountId) {
+            STAKING_OWNERS.with(|owners| owners.borrow_mut().insert(position_id, owner));
+        }
 
         #[ink::test]
         fn constructor_works() {
@@ -1031,6 +1282,77 @@ mod fiapo_marketplace {
             assert!(result.is_ok());
         }
 
+        #[ink::test]
+        fn position_listing_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoMarketplace::new(accounts.charlie, accounts.django);
+
+            // Sem contrato de staking configurado não há posições para listar
+            assert_eq!(contract.list_position(7, 1000, 0), Err(MarketplaceError::StakingNotConfigured));
+
+            contract.set_staking_contract(accounts.eve).unwrap();
+            set_staking_owner(7, accounts.alice);
+
+            // Só o owner da posição no staking pode listá-la
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.list_position(7, 1000, 0), Err(MarketplaceError::NFTNotOwned));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.list_position(7, 1000, 0).unwrap();
+            assert_eq!(contract.get_active_position_listings(), vec![7]);
+            // IDs de posição não colidem com listings de NFTs do ICO
+            assert!(contract.get_listing(7).is_none());
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.cancel_position_listing(7), Err(MarketplaceError::Unauthorized));
+        }
+
+        #[ink::test]
+        fn buy_position_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoMarketplace::new(accounts.charlie, accounts.django);
+            contract.set_staking_contract(accounts.eve).unwrap();
+            set_staking_owner(7, accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.list_position(7, 1000, 0).unwrap();
+
+            // A posição mudou de dono depois de listada: a compra reverte antes do pagamento
+            set_staking_owner(7, accounts.frank);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(1000);
+            assert_eq!(contract.buy_position(7), Err(MarketplaceError::NFTNotOwned));
+            assert!(contract.get_position_listing(7).unwrap().active);
+            assert_eq!(contract.total_volume(), 0);
+
+            // Com o seller ainda dono, a posição passa para o comprador
+            set_staking_owner(7, accounts.alice);
+            let marketplace = ink::env::account_id::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(marketplace, 1000);
+            contract.buy_position(7).unwrap();
+            assert_eq!(staking_owner(7), Some(accounts.bob));
+            assert!(contract.get_active_position_listings().is_empty());
+            assert!(!contract.get_position_listing(7).unwrap().active);
+            assert_eq!(contract.total_volume(), 1000);
+
+            // Listing vendido não pode ser comprado de novo
+            assert_eq!(contract.buy_position(7), Err(MarketplaceError::ListingNotFound));
+        }
+
+        #[ink::test]
+        fn cancel_position_listing_works() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoMarketplace::new(accounts.charlie, accounts.django);
+            contract.set_staking_contract(accounts.eve).unwrap();
+            set_staking_owner(7, accounts.alice);
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.list_position(7, 1000, 0).unwrap();
+            contract.cancel_position_listing(7).unwrap();
+            assert!(contract.get_active_position_listings().is_empty());
+            assert!(!contract.get_position_listing(7).unwrap().active);
+        }
+
         #[ink::test]
         fn min_price_disabled_after_ico() {
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
@@ -1083,5 +1405,29 @@ mod fiapo_marketplace {
             contract.set_ico_sales_completed(true).unwrap();
             assert_eq!(contract.payment_mode(), 2); // Both LUNES+FIAPO after ICO
         }
+
+        #[ink::test]
+        fn governance_can_set_bounded_fees() {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoMarketplace::new(accounts.charlie, accounts.django);
+            contract.set_governance_contract(Some(accounts.eve)).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_fee_bps(500), Err(MarketplaceError::Unauthorized));
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.set_fee_bps(MAX_FEE_BPS + 1), Err(MarketplaceError::InvalidFee));
+            assert_eq!(contract.set_fee_bps(500), Ok(()));
+            assert_eq!(contract.fee_bps, 500);
+            assert_eq!(contract.set_governance_contract(None), Err(MarketplaceError::Unauthorized));
+
+            // Com Timelock, ambos seguem autorizados
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_timelock_contract(Some(accounts.frank)).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.set_fee_bps(400), Ok(()));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.set_fee_bps(300), Ok(()));
+        }
     }
 }
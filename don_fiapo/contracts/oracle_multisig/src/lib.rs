@@ -2,10 +2,12 @@
 //! 
 //! Sistema de consenso multi-oracle para confirmação de pagamentos externos
 //! (Solana USDT/SPL). Requer M de N confirmações para processar um pagamento.
+//! Também publica o preço FIAPO/USDT: mediana dos reportes recentes de ao menos
+//! M oráculos (usado para cobrar taxas em FIAPO).
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-use fiapo_logics::traits::oracle::Oracle;
+use fiapo_logics::traits::oracle::{Oracle, PaymentPurpose};
 
 #[ink::contract]
 mod fiapo_oracle_multisig {
@@ -14,6 +16,9 @@ mod fiapo_oracle_multisig {
     use ink::storage::Mapping;
     use ink::env::call::{build_call, ExecutionInput, Selector};
 
+    /// Validade de um reporte de preço (ms)
+    pub const PRICE_MAX_AGE_MS: u64 = 3600 * 1000;
+
     // ==================== Tipos e Enums ====================
 
     /// Erros do sistema Oracle
@@ -45,6 +50,8 @@ mod fiapo_oracle_multisig {
         NFTPurchase { tier: u8 },
         LotteryTicket { quantity: u32 },
         GovernanceDeposit,
+        /// Parte fixa da taxa de saída Don Burn de uma posição de staking
+        StakingExitFee { position_id: u64 },
         Custom(String),
     }
 
@@ -56,6 +63,8 @@ mod fiapo_oracle_multisig {
         Confirmed,
         Rejected,
         Expired,
+        /// Já usado por um contrato consumidor
+        Consumed,
     }
 
     /// Detalhes de um pagamento pendente
@@ -91,6 +100,21 @@ mod fiapo_oracle_multisig {
         payment_type: PaymentType,
     }
 
+    #[ink(event)]
+    pub struct PaymentConsumed {
+        #[ink(topic)]
+        tx_hash: String,
+        #[ink(topic)]
+        consumer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FiapoPriceSubmitted {
+        #[ink(topic)]
+        oracle: AccountId,
+        price: Balance,
+    }
+
     // ==================== Storage ====================
 
     #[ink(storage)]
@@ -108,6 +132,9 @@ mod fiapo_oracle_multisig {
         staking_contract: Option<AccountId>,
         lottery_contract: Option<AccountId>,
         governance_contract: Option<AccountId>,
+        /// Último preço reportado por oráculo: (LUSDT por FIAPO, timestamp)
+        price_reports: Mapping<AccountId, (Balance, Timestamp)>,
+        price_reporters: Vec<AccountId>,
     }
 
     impl FiapoOracleMultisig {
@@ -136,6 +163,8 @@ mod fiapo_oracle_multisig {
                 staking_contract: None,
                 lottery_contract: None,
                 governance_contract: None,
+                price_reports: Mapping::default(),
+                price_reporters: Vec::new(),
             }
         }
 
@@ -301,6 +330,25 @@ mod fiapo_oracle_multisig {
             }
         }
 
+        /// Reporta o preço de 1 FIAPO em unidades LUSDT (6 casas decimais)
+        #[ink(message)]
+        pub fn submit_fiapo_price(&mut self, price: Balance) -> Result<(), OracleError> {
+            let caller = self.env().caller();
+            self.ensure_oracle(&caller)?;
+            self.ensure_active()?;
+            if price == 0 {
+                return Err(OracleError::InvalidConfiguration);
+            }
+
+            self.price_reports.insert(caller, &(price, self.env().block_timestamp()));
+            if !self.price_reporters.contains(&caller) {
+                self.price_reporters.push(caller);
+            }
+
+            self.env().emit_event(FiapoPriceSubmitted { oracle: caller, price });
+            Ok(())
+        }
+
         // ==================== Lógica Interna ====================
 
         fn process_confirmed_payment(&self, payment: &PendingPayment) -> Result<(), OracleError> {
@@ -314,9 +362,8 @@ mod fiapo_oracle_multisig {
                 PaymentType::LotteryTicket { quantity } => {
                     self.call_lottery_buy_tickets_for(payment.beneficiary, *quantity)?;
                 }
-                PaymentType::GovernanceDeposit => {
-                    // A lógica de depósito de governança pode ser mais complexa
-                    // e talvez precise de uma chamada específica.
+                PaymentType::GovernanceDeposit | PaymentType::StakingExitFee { .. } => {
+                    // Consumidos pelo contrato de destino via `consume_payment`
                 }
                 PaymentType::Custom(_) => {
                     // Lógica para ações customizadas
@@ -325,6 +372,30 @@ mod fiapo_oracle_multisig {
             Ok(())
         }
 
+        /// Pagamento confirmado, não consumido e do tipo exigido pelo propósito
+        fn payment_matches(&self, tx_hash: &String, user: AccountId, amount_cents: u64, purpose: &PaymentPurpose) -> bool {
+            let Some(payment) = self.pending_payments.get(tx_hash) else { return false };
+            payment.status == PaymentStatus::Confirmed
+                && payment.beneficiary == user
+                && payment.amount_usdt == amount_cents
+                && match (&payment.payment_type, purpose) {
+                    (PaymentType::GovernanceDeposit, PaymentPurpose::GovernanceDeposit) => true,
+                    (
+                        PaymentType::StakingExitFee { position_id },
+                        PaymentPurpose::StakingExitFee { position_id: expected },
+                    ) => position_id == expected,
+                    _ => false,
+                }
+        }
+
+        /// Contrato autorizado a consumir pagamentos de cada propósito
+        fn purpose_consumer(&self, purpose: &PaymentPurpose) -> Option<AccountId> {
+            match purpose {
+                PaymentPurpose::GovernanceDeposit => self.governance_contract,
+                PaymentPurpose::StakingExitFee { .. } => self.staking_contract,
+            }
+        }
+
         // --- Chamadas Cross-Contract ---
 
         fn call_ico_mint_for(&self, user: AccountId, tier: u8) -> Result<(), OracleError> {
@@ -468,20 +539,49 @@ mod fiapo_oracle_multisig {
             tx_hash: String,
             user: AccountId,
             amount_cents: u64,
-            is_governance_deposit: bool,
+            purpose: PaymentPurpose,
         ) -> bool {
-            if let Some(payment) = self.pending_payments.get(&tx_hash) {
-                if payment.status == PaymentStatus::Confirmed
-                   && payment.beneficiary == user
-                   && payment.amount_usdt == amount_cents
-                {
-                    return match payment.payment_type {
-                        PaymentType::GovernanceDeposit => is_governance_deposit,
-                        _ => !is_governance_deposit,
-                    }
-                }
+            self.payment_matches(&tx_hash, user, amount_cents, &purpose)
+        }
+
+        #[ink(message)]
+        fn consume_payment(
+            &mut self,
+            tx_hash: String,
+            user: AccountId,
+            amount_cents: u64,
+            purpose: PaymentPurpose,
+        ) -> bool {
+            let caller = self.env().caller();
+            if self.purpose_consumer(&purpose) != Some(caller)
+                || !self.payment_matches(&tx_hash, user, amount_cents, &purpose)
+            {
+                return false;
+            }
+            let Some(mut payment) = self.pending_payments.get(&tx_hash) else { return false };
+            payment.status = PaymentStatus::Consumed;
+            self.pending_payments.insert(&tx_hash, &payment);
+            self.env().emit_event(PaymentConsumed { tx_hash, consumer: caller });
+            true
+        }
+
+        #[ink(message)]
+        fn fiapo_price(&self) -> Option<Balance> {
+            if !self.is_active {
+                return None;
             }
-            false
+            let now = self.env().block_timestamp();
+            let mut prices: Vec<Balance> = self.price_reporters.iter()
+                .filter(|&&oracle| self.is_oracle(oracle))
+                .filter_map(|&oracle| self.price_reports.get(oracle))
+                .filter(|&(_, reported_at)| now.saturating_sub(reported_at) <= PRICE_MAX_AGE_MS)
+                .map(|(price, _)| price)
+                .collect();
+            if prices.is_empty() || prices.len() < self.required_confirmations as usize {
+                return None;
+            }
+            prices.sort_unstable();
+            prices.get(prices.len().saturating_sub(1) / 2).copied()
         }
     }
 
@@ -568,6 +668,70 @@ mod fiapo_oracle_multisig {
             let payment = contract.get_pending_payment(String::from("tx123")).unwrap();
             assert_eq!(payment.status, PaymentStatus::Confirmed);
         }
+
+        #[ink::test]
+        fn payments_are_consumed_once_for_their_purpose() {
+            let accounts = default_accounts();
+            let mut contract = FiapoOracleMultisig::new(vec![accounts.alice], 1);
+            contract.set_contract_address(String::from("staking"), accounts.django).unwrap();
+            contract.set_contract_address(String::from("governance"), accounts.frank).unwrap();
+            let exit_fee = PaymentPurpose::StakingExitFee { position_id: 7 };
+
+            contract.submit_confirmation(
+                String::from("nft"), String::from("Sol"), 1000, accounts.eve, PaymentType::Custom(String::from("nft")),
+            ).unwrap();
+            contract.submit_confirmation(
+                String::from("exit"), String::from("Sol"), 1000, accounts.eve, PaymentType::StakingExitFee { position_id: 7 },
+            ).unwrap();
+
+            // Outros tipos e outras posições não quitam a taxa de saída
+            assert!(!contract.is_payment_confirmed(String::from("nft"), accounts.eve, 1000, exit_fee.clone()));
+            assert!(!contract.is_payment_confirmed(
+                String::from("exit"), accounts.eve, 1000, PaymentPurpose::StakingExitFee { position_id: 8 },
+            ));
+            assert!(!contract.is_payment_confirmed(String::from("exit"), accounts.eve, 1000, PaymentPurpose::GovernanceDeposit));
+            assert!(contract.is_payment_confirmed(String::from("exit"), accounts.eve, 1000, exit_fee.clone()));
+
+            // Só o staking consome, e uma única vez
+            set_caller(accounts.frank);
+            assert!(!contract.consume_payment(String::from("exit"), accounts.eve, 1000, exit_fee.clone()));
+            set_caller(accounts.django);
+            assert!(contract.consume_payment(String::from("exit"), accounts.eve, 1000, exit_fee.clone()));
+            assert!(!contract.consume_payment(String::from("exit"), accounts.eve, 1000, exit_fee.clone()));
+            assert!(!contract.is_payment_confirmed(String::from("exit"), accounts.eve, 1000, exit_fee));
+            assert_eq!(contract.get_pending_payment(String::from("exit")).unwrap().status, PaymentStatus::Consumed);
+        }
+
+        #[ink::test]
+        fn fiapo_price_is_median_of_fresh_reports() {
+            let accounts = default_accounts();
+            let oracles = vec![accounts.alice, accounts.bob, accounts.charlie];
+            let mut contract = FiapoOracleMultisig::new(oracles, 2);
+
+            set_caller(accounts.eve);
+            assert_eq!(contract.submit_fiapo_price(1_000), Err(OracleError::UnauthorizedOracle));
+            set_caller(accounts.alice);
+            assert_eq!(contract.submit_fiapo_price(0), Err(OracleError::InvalidConfiguration));
+            assert_eq!(contract.submit_fiapo_price(1_000), Ok(()));
+            // Um reporte não basta com 2 confirmações exigidas
+            assert_eq!(contract.fiapo_price(), None);
+
+            set_caller(accounts.bob);
+            contract.submit_fiapo_price(1_200).unwrap();
+            assert_eq!(contract.fiapo_price(), Some(1_000));
+
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(PRICE_MAX_AGE_MS);
+            set_caller(accounts.charlie);
+            contract.submit_fiapo_price(5_000).unwrap();
+            assert_eq!(contract.fiapo_price(), Some(1_200));
+
+            // Reportes de alice e bob expiram; bob renova o seu
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(PRICE_MAX_AGE_MS + 1);
+            assert_eq!(contract.fiapo_price(), None);
+            set_caller(accounts.bob);
+            contract.submit_fiapo_price(1_300).unwrap();
+            assert_eq!(contract.fiapo_price(), Some(1_300));
+        }
     }
 }
 
@@ -0,0 +1,659 @@
+//! # Fiapo Randomness Beacon
+//!
+//! Beacon de aleatoriedade compartilhado (loteria, roleta, raridade de NFTs).
+//! - Rodadas de `round_duration` ms: na primeira metade participantes enviam
+//!   `commit` do hash de um segredo com caução em LUNES; na segunda, revelam.
+//!   Só a rodada atual e a seguinte aceitam commits; não há limite de participantes
+//!   (cada commit/reveal é O(1)), então ninguém monopoliza uma rodada.
+//! - Entrada do oráculo: cadeia de hashes ancorada enquanto a primeira rodada servida
+//!   ainda aceita commits. O valor da rodada r é a pré-imagem única da última entrada
+//!   publicada, revelada só após o fim dos commits; o oráculo não consegue escolhê-lo
+//!   nem moê-lo.
+//! - Após o fim da rodada qualquer um finaliza. A semente só vale com ao menos
+//!   `min_revealers` segredos revelados e, com oráculo configurado, a entrada dele;
+//!   senão a rodada falha e os consumidores pedem outra. Semente = hash do XOR dos
+//!   segredos revelados + entrada do oráculo. Cauções de quem não revelou são
+//!   divididas entre os que revelaram (sem reveladores, acumulam para a próxima).
+//! - Consumidores pedem aleatoriedade para uma rodada futura (`request_randomness`)
+//!   e liquidam depois com `randomness(round)`.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod fiapo_randomness {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use scale::Encode;
+    use fiapo_logics::traits::randomness::Randomness;
+
+    /// Duração mínima de uma rodada (1 minuto)
+    pub const MIN_ROUND_DURATION_MS: u64 = 60_000;
+    /// Segredos revelados exigidos por padrão para a semente valer
+    pub const DEFAULT_MIN_REVEALERS: u32 = 2;
+    /// Rodadas que a entrada do oráculo pode pular na cadeia de hashes
+    pub const MAX_ORACLE_CHAIN_GAP: u64 = 1_000;
+    /// Committers por página em `get_committers`
+    pub const MAX_COMMITTERS_PAGE: u32 = 100;
+    /// Separação de domínio da semente e dos segredos
+    pub const SEED_DOMAIN: &[u8] = b"DonFiapo:randomness:seed:v2";
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RandomnessError {
+        Unauthorized,
+        InvalidBond,
+        CommitClosed,
+        RoundNotOpen,
+        AlreadyCommitted,
+        NotRevealPhase,
+        CommitmentNotFound,
+        AlreadyRevealed,
+        InvalidReveal,
+        OracleNotConfigured,
+        InvalidOracleInput,
+        InvalidConfiguration,
+        RoundNotOver,
+        AlreadyFinalized,
+        RoundNotFinalized,
+        BondForfeited,
+        AlreadyClaimed,
+        TransferFailed,
+        RequestsPending,
+    }
+
+    /// Compromisso de um participante: hash de (rodada, conta, segredo)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Commitment {
+        pub hash: [u8; 32],
+        pub bond: Balance,
+        pub secret: Option<[u8; 32]>,
+        pub claimed: bool,
+    }
+
+    /// Acumulado de uma rodada em andamento
+    #[derive(Debug, Clone, Default, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RoundState {
+        pub committed: u32,
+        pub revealed: u32,
+        pub bonded: Balance,
+        pub revealed_bond: Balance,
+        /// XOR dos hashes (rodada, conta, segredo) revelados: independe da ordem
+        pub entropy: [u8; 32],
+    }
+
+    /// Resultado de uma rodada finalizada; `seed` vazio = rodada sem entropia suficiente
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct RoundResult {
+        pub seed: Option<[u8; 32]>,
+        pub committed: u32,
+        pub revealed: u32,
+        pub oracle_input: bool,
+        /// Cauções perdidas por quem não revelou
+        pub slashed: Balance,
+        /// Parte das penalidades somada à caução de cada revelador
+        pub reward_per_revealer: Balance,
+        pub finalized_at: u64,
+    }
+
+    #[ink(event)]
+    pub struct RandomnessRequested {
+        #[ink(topic)]
+        round: u64,
+        #[ink(topic)]
+        consumer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Committed {
+        #[ink(topic)]
+        round: u64,
+        #[ink(topic)]
+        committer: AccountId,
+        bond: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Revealed {
+        #[ink(topic)]
+        round: u64,
+        #[ink(topic)]
+        committer: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct OracleInputSubmitted {
+        #[ink(topic)]
+        round: u64,
+        input: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct OracleChainAnchored {
+        round: u64,
+        anchor: [u8; 32],
+    }
+
+    #[ink(event)]
+    pub struct RoundFinalized {
+        #[ink(topic)]
+        round: u64,
+        seed: Option<[u8; 32]>,
+        revealed: u32,
+        slashed: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct FiapoRandomness {
+        owner: AccountId,
+        /// Fixo após o deploy (a numeração das rodadas depende dele)
+        round_duration: u64,
+        commit_bond: Balance,
+        /// Última entrada conhecida da cadeia do oráculo: (rodada, valor).
+        /// A entrada da rodada r > rodada é a pré-imagem de ordem (r - rodada) do valor.
+        oracle_chain: Option<(u64, [u8; 32])>,
+        min_revealers: u32,
+        commitments: Mapping<(u64, AccountId), Commitment>,
+        /// Committers por (rodada, posição), para consulta
+        round_committers: Mapping<(u64, u32), AccountId>,
+        round_states: Mapping<u64, RoundState>,
+        oracle_inputs: Mapping<u64, [u8; 32]>,
+        rounds: Mapping<u64, RoundResult>,
+        requests: Mapping<u64, u32>,
+        /// Rodadas com pedidos ainda não finalizadas (travam a troca da âncora)
+        pending_request_rounds: u32,
+        /// Penalidades de rodadas sem reveladores, somadas à próxima com reveladores
+        penalty_pool: Balance,
+    }
+
+    impl FiapoRandomness {
+        #[ink(constructor)]
+        pub fn new(round_duration: u64, commit_bond: Balance) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                round_duration: round_duration.max(MIN_ROUND_DURATION_MS),
+                commit_bond,
+                oracle_chain: None,
+                min_revealers: DEFAULT_MIN_REVEALERS,
+                commitments: Mapping::default(),
+                round_committers: Mapping::default(),
+                round_states: Mapping::default(),
+                oracle_inputs: Mapping::default(),
+                rounds: Mapping::default(),
+                requests: Mapping::default(),
+                pending_request_rounds: 0,
+                penalty_pool: 0,
+            }
+        }
+
+        // ==================== Commit-Reveal ====================
+
+        /// Compromete o hash de um segredo para `round`, com a caução exata em LUNES
+        #[ink(message, payable)]
+        pub fn commit(&mut self, round: u64, hash: [u8; 32]) -> Result<(), RandomnessError> {
+            let caller = self.env().caller();
+            let bond = self.env().transferred_value();
+            if bond != self.commit_bond {
+                return Err(RandomnessError::InvalidBond);
+            }
+            if self.env().block_timestamp() >= self.reveal_start(round) {
+                return Err(RandomnessError::CommitClosed);
+            }
+            // Só a rodada atual e a seguinte: nada de reservar rodadas distantes
+            if round > self.current_round().saturating_add(1) {
+                return Err(RandomnessError::RoundNotOpen);
+            }
+            if self.commitments.contains((round, caller)) {
+                return Err(RandomnessError::AlreadyCommitted);
+            }
+
+            let mut state = self.round_states.get(round).unwrap_or_default();
+            self.round_committers.insert((round, state.committed), &caller);
+            state.committed = state.committed.saturating_add(1);
+            state.bonded = state.bonded.saturating_add(bond);
+            self.round_states.insert(round, &state);
+            self.commitments.insert((round, caller), &Commitment { hash, bond, secret: None, claimed: false });
+            Self::env().emit_event(Committed { round, committer: caller, bond });
+            Ok(())
+        }
+
+        /// Revela o segredo durante a segunda metade da rodada
+        #[ink(message)]
+        pub fn reveal(&mut self, round: u64, secret: [u8; 32]) -> Result<(), RandomnessError> {
+            let caller = self.env().caller();
+            self.ensure_reveal_phase(round)?;
+            let mut commitment = self.commitments.get((round, caller)).ok_or(RandomnessError::CommitmentNotFound)?;
+            if commitment.secret.is_some() {
+                return Err(RandomnessError::AlreadyRevealed);
+            }
+            if commitment.hash != self.commitment_hash(round, caller, secret) {
+                return Err(RandomnessError::InvalidReveal);
+            }
+
+            commitment.secret = Some(secret);
+            self.commitments.insert((round, caller), &commitment);
+
+            let mut state = self.round_states.get(round).unwrap_or_default();
+            let share = Self::blake2_256(&(SEED_DOMAIN, round, caller, secret).encode());
+            for (byte, other) in state.entropy.iter_mut().zip(share.iter()) {
+                *byte ^= other;
+            }
+            state.revealed = state.revealed.saturating_add(1);
+            state.revealed_bond = state.revealed_bond.saturating_add(commitment.bond);
+            self.round_states.insert(round, &state);
+            Self::env().emit_event(Revealed { round, committer: caller });
+            Ok(())
+        }
+
+        /// Entrada do oráculo: próxima pré-imagem da cadeia de hashes, enviada por
+        /// qualquer um durante a revelação (após o fim dos commits)
+        #[ink(message)]
+        pub fn submit_oracle_input(&mut self, round: u64, input: [u8; 32]) -> Result<(), RandomnessError> {
+            let (last_round, last_value) = self.oracle_chain.ok_or(RandomnessError::OracleNotConfigured)?;
+            self.ensure_reveal_phase(round)?;
+            if self.oracle_inputs.contains(round) {
+                return Err(RandomnessError::AlreadyRevealed);
+            }
+            let gap = round.saturating_sub(last_round);
+            if gap == 0 || gap > MAX_ORACLE_CHAIN_GAP {
+                return Err(RandomnessError::InvalidOracleInput);
+            }
+            let mut value = input;
+            for _ in 0..gap {
+                value = Self::blake2_256(&value);
+            }
+            if value != last_value {
+                return Err(RandomnessError::InvalidOracleInput);
+            }
+
+            self.oracle_chain = Some((round, input));
+            self.oracle_inputs.insert(round, &input);
+            Self::env().emit_event(OracleInputSubmitted { round, input });
+            Ok(())
+        }
+
+        /// Fecha a rodada e publica a semente (qualquer um, após o fim da rodada)
+        #[ink(message)]
+        pub fn finalize_round(&mut self, round: u64) -> Result<Option<[u8; 32]>, RandomnessError> {
+            if self.env().block_timestamp() < self.round_end(round) {
+                return Err(RandomnessError::RoundNotOver);
+            }
+            if self.rounds.contains(round) {
+                return Err(RandomnessError::AlreadyFinalized);
+            }
+
+            if self.requests.contains(round) {
+                self.pending_request_rounds = self.pending_request_rounds.saturating_sub(1);
+            }
+
+            let state = self.round_states.get(round).unwrap_or_default();
+            let oracle_input = self.oracle_inputs.get(round);
+            let slashed = state.bonded.saturating_sub(state.revealed_bond);
+            let revealed = state.revealed;
+
+            // Penalidades divididas entre os reveladores; o resto fica no pool
+            let penalties = self.penalty_pool.saturating_add(slashed);
+            let reward_per_revealer = penalties.checked_div(revealed as u128).unwrap_or(0);
+            self.penalty_pool = penalties.saturating_sub(reward_per_revealer.saturating_mul(revealed as u128));
+
+            // Sem reveladores suficientes ou sem a entrada exigida do oráculo a rodada falha
+            let enough_revealers = revealed >= self.min_revealers;
+            let oracle_ready = self.oracle_chain.is_none() || oracle_input.is_some();
+            let seed = (enough_revealers && oracle_ready).then(|| {
+                Self::blake2_256(&(SEED_DOMAIN, self.env().account_id(), round, state.entropy, oracle_input).encode())
+            });
+            self.rounds.insert(round, &RoundResult {
+                seed,
+                committed: state.committed,
+                revealed,
+                oracle_input: oracle_input.is_some(),
+                slashed,
+                reward_per_revealer,
+                finalized_at: self.env().block_timestamp(),
+            });
+            Self::env().emit_event(RoundFinalized { round, seed, revealed, slashed });
+            Ok(seed)
+        }
+
+        /// Devolve a caução (mais a parte das penalidades) a quem revelou
+        #[ink(message)]
+        pub fn claim_bond(&mut self, round: u64) -> Result<Balance, RandomnessError> {
+            let caller = self.env().caller();
+            let result = self.rounds.get(round).ok_or(RandomnessError::RoundNotFinalized)?;
+            let mut commitment = self.commitments.get((round, caller)).ok_or(RandomnessError::CommitmentNotFound)?;
+            if commitment.secret.is_none() {
+                return Err(RandomnessError::BondForfeited);
+            }
+            if commitment.claimed {
+                return Err(RandomnessError::AlreadyClaimed);
+            }
+
+            commitment.claimed = true;
+            self.commitments.insert((round, caller), &commitment);
+            let amount = commitment.bond.saturating_add(result.reward_per_revealer);
+            if amount > 0 {
+                self.env().transfer(caller, amount).map_err(|_| RandomnessError::TransferFailed)?;
+            }
+            Ok(amount)
+        }
+
+        // ==================== Views ====================
+
+        /// Hash a comprometer: blake2_256 de (rodada, conta, segredo) codificados
+        #[ink(message)]
+        pub fn commitment_hash(&self, round: u64, committer: AccountId, secret: [u8; 32]) -> [u8; 32] {
+            Self::blake2_256(&(round, committer, secret).encode())
+        }
+
+        #[ink(message)]
+        pub fn current_round(&self) -> u64 {
+            self.env().block_timestamp().checked_div(self.round_duration).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn round_duration(&self) -> u64 {
+            self.round_duration
+        }
+
+        #[ink(message)]
+        pub fn commit_bond(&self) -> Balance {
+            self.commit_bond
+        }
+
+        #[ink(message)]
+        pub fn get_round(&self, round: u64) -> Option<RoundResult> {
+            self.rounds.get(round)
+        }
+
+        #[ink(message)]
+        pub fn get_commitment(&self, round: u64, committer: AccountId) -> Option<Commitment> {
+            self.commitments.get((round, committer))
+        }
+
+        #[ink(message)]
+        pub fn get_round_state(&self, round: u64) -> RoundState {
+            self.round_states.get(round).unwrap_or_default()
+        }
+
+        /// Committers da rodada em ordem de commit (paginado)
+        #[ink(message)]
+        pub fn get_committers(&self, round: u64, offset: u32, limit: u32) -> Vec<AccountId> {
+            let end = offset.saturating_add(limit.min(MAX_COMMITTERS_PAGE));
+            (offset..end)
+                .map_while(|index| self.round_committers.get((round, index)))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn request_count(&self, round: u64) -> u32 {
+            self.requests.get(round).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn penalty_pool(&self) -> Balance {
+            self.penalty_pool
+        }
+
+        #[ink(message)]
+        pub fn oracle_chain(&self) -> Option<(u64, [u8; 32])> {
+            self.oracle_chain
+        }
+
+        #[ink(message)]
+        pub fn min_revealers(&self) -> u32 {
+            self.min_revealers
+        }
+
+        // ==================== Admin ====================
+
+        #[ink(message)]
+        pub fn set_commit_bond(&mut self, bond: Balance) -> Result<(), RandomnessError> {
+            if self.env().caller() != self.owner {
+                return Err(RandomnessError::Unauthorized);
+            }
+            self.commit_bond = bond;
+            Ok(())
+        }
+
+        /// Ancora a cadeia do oráculo: `anchor` vale como entrada da rodada `round`.
+        /// A primeira rodada servida (`round + 1`) ainda precisa estar na fase de
+        /// commit, e uma âncora existente só é trocada ou removida (`None`) sem
+        /// rodadas com pedidos pendentes.
+        #[ink(message)]
+        pub fn set_oracle_chain(&mut self, anchor: Option<(u64, [u8; 32])>) -> Result<(), RandomnessError> {
+            if self.env().caller() != self.owner {
+                return Err(RandomnessError::Unauthorized);
+            }
+            if self.oracle_chain.is_some() && self.pending_request_rounds > 0 {
+                return Err(RandomnessError::RequestsPending);
+            }
+            if let Some((round, value)) = anchor {
+                if self.env().block_timestamp() >= self.reveal_start(round.saturating_add(1)) {
+                    return Err(RandomnessError::InvalidConfiguration);
+                }
+                Self::env().emit_event(OracleChainAnchored { round, anchor: value });
+            }
+            self.oracle_chain = anchor;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_min_revealers(&mut self, min_revealers: u32) -> Result<(), RandomnessError> {
+            if self.env().caller() != self.owner {
+                return Err(RandomnessError::Unauthorized);
+            }
+            if min_revealers == 0 {
+                return Err(RandomnessError::InvalidConfiguration);
+            }
+            self.min_revealers = min_revealers;
+            Ok(())
+        }
+
+        // ==================== Internal ====================
+
+        fn reveal_start(&self, round: u64) -> u64 {
+            round.saturating_mul(self.round_duration).saturating_add(self.round_duration / 2)
+        }
+
+        fn round_end(&self, round: u64) -> u64 {
+            round.saturating_add(1).saturating_mul(self.round_duration)
+        }
+
+        fn ensure_reveal_phase(&self, round: u64) -> Result<(), RandomnessError> {
+            let now = self.env().block_timestamp();
+            if now < self.reveal_start(round) || now >= self.round_end(round) {
+                return Err(RandomnessError::NotRevealPhase);
+            }
+            Ok(())
+        }
+
+        fn blake2_256(input: &[u8]) -> [u8; 32] {
+            let mut output = [0u8; 32];
+            ink::env::hash_bytes::<ink::env::hash::Blake2x256>(input, &mut output);
+            output
+        }
+    }
+
+    // ==================== Randomness Trait Implementation ====================
+    // Required for cross-contract calls via RandomnessRef (contract_ref!(Randomness))
+
+    impl Randomness for FiapoRandomness {
+        /// Próxima rodada: seus commits ainda podem chegar e nada foi revelado
+        #[ink(message)]
+        fn request_randomness(&mut self) -> u64 {
+            let round = self.current_round().saturating_add(1);
+            let count = self.request_count(round);
+            if count == 0 {
+                self.pending_request_rounds = self.pending_request_rounds.saturating_add(1);
+            }
+            self.requests.insert(round, &count.saturating_add(1));
+            Self::env().emit_event(RandomnessRequested { round, consumer: self.env().caller() });
+            round
+        }
+
+        #[ink(message)]
+        fn randomness(&self, round: u64) -> Option<[u8; 32]> {
+            self.rounds.get(round).and_then(|result| result.seed)
+        }
+
+        #[ink(message)]
+        fn round_failed(&self, round: u64) -> bool {
+            self.rounds.get(round).is_some_and(|result| result.seed.is_none())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::DefaultEnvironment;
+
+        const ROUND: u64 = MIN_ROUND_DURATION_MS;
+
+        fn set_caller(caller: AccountId) {
+            ink::env::test::set_caller::<DefaultEnvironment>(caller);
+        }
+
+        fn set_time(timestamp: u64) {
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(timestamp);
+        }
+
+        fn commit_as(beacon: &mut FiapoRandomness, committer: AccountId, round: u64, secret: [u8; 32]) -> Result<(), RandomnessError> {
+            set_caller(committer);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(beacon.commit_bond());
+            let hash = beacon.commitment_hash(round, committer, secret);
+            beacon.commit(round, hash)
+        }
+
+        #[ink::test]
+        fn commit_reveal_round_slashes_non_revealers() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let mut beacon = FiapoRandomness::new(0, 100);
+            assert_eq!(beacon.round_duration(), ROUND);
+            ink::env::test::set_account_balance::<DefaultEnvironment>(ink::env::account_id::<DefaultEnvironment>(), 300);
+
+            assert_eq!(beacon.request_randomness(), 1);
+            assert_eq!(beacon.request_count(1), 1);
+            assert_eq!(commit_as(&mut beacon, accounts.bob, 1, [1; 32]), Ok(()));
+            assert_eq!(commit_as(&mut beacon, accounts.charlie, 1, [2; 32]), Ok(()));
+            assert_eq!(commit_as(&mut beacon, accounts.django, 1, [3; 32]), Ok(()));
+            assert_eq!(commit_as(&mut beacon, accounts.bob, 1, [1; 32]), Err(RandomnessError::AlreadyCommitted));
+            // Rodadas distantes ainda não aceitam commits
+            assert_eq!(commit_as(&mut beacon, accounts.bob, 2, [1; 32]), Err(RandomnessError::RoundNotOpen));
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(99);
+            assert_eq!(beacon.commit(1, [0; 32]), Err(RandomnessError::InvalidBond));
+            assert_eq!(beacon.get_committers(1, 1, 10), ink::prelude::vec![accounts.charlie, accounts.django]);
+
+            // Revelação só na segunda metade da rodada e com o segredo comprometido
+            assert_eq!(beacon.reveal(1, [1; 32]), Err(RandomnessError::NotRevealPhase));
+            set_time(ROUND + ROUND / 2);
+            assert_eq!(commit_as(&mut beacon, accounts.eve, 1, [4; 32]), Err(RandomnessError::CommitClosed));
+            set_caller(accounts.charlie);
+            assert_eq!(beacon.reveal(1, [9; 32]), Err(RandomnessError::InvalidReveal));
+            assert_eq!(beacon.reveal(1, [2; 32]), Ok(()));
+            set_caller(accounts.bob);
+            assert_eq!(beacon.reveal(1, [1; 32]), Ok(()));
+            assert_eq!(beacon.finalize_round(1), Err(RandomnessError::RoundNotOver));
+            assert_eq!(beacon.randomness(1), None);
+
+            set_time(2 * ROUND);
+            let seed = beacon.finalize_round(1).unwrap().unwrap();
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            let share = |committer: AccountId, secret: [u8; 32]| FiapoRandomness::blake2_256(&(SEED_DOMAIN, 1u64, committer, secret).encode());
+            let (bob, charlie) = (share(accounts.bob, [1; 32]), share(accounts.charlie, [2; 32]));
+            let entropy: [u8; 32] = core::array::from_fn(|i| bob[i] ^ charlie[i]);
+            let expected = FiapoRandomness::blake2_256(&(SEED_DOMAIN, contract, 1u64, entropy, None::<[u8; 32]>).encode());
+            assert_eq!(seed, expected);
+            assert_eq!(beacon.randomness(1), Some(seed));
+            assert_eq!(beacon.finalize_round(1), Err(RandomnessError::AlreadyFinalized));
+
+            // Caução de django dividida entre bob e charlie
+            let result = beacon.get_round(1).unwrap();
+            assert_eq!((result.committed, result.revealed, result.slashed, result.reward_per_revealer), (3, 2, 100, 50));
+            assert_eq!(beacon.claim_bond(1), Ok(150));
+            assert_eq!(beacon.claim_bond(1), Err(RandomnessError::AlreadyClaimed));
+            set_caller(accounts.django);
+            assert_eq!(beacon.claim_bond(1), Err(RandomnessError::BondForfeited));
+
+            // Um único revelador não basta: a rodada falha e deve ser pedida de novo
+            assert_eq!(commit_as(&mut beacon, accounts.eve, 2, [5; 32]), Ok(()));
+            set_time(2 * ROUND + ROUND / 2);
+            assert_eq!(beacon.reveal(2, [5; 32]), Ok(()));
+            set_time(3 * ROUND);
+            assert_eq!(beacon.finalize_round(2), Ok(None));
+            assert!(beacon.round_failed(2));
+            assert!(!beacon.round_failed(1));
+            assert_eq!(beacon.claim_bond(2), Ok(100));
+        }
+
+        #[ink::test]
+        fn oracle_hash_chain_is_required_and_verified() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let mut beacon = FiapoRandomness::new(ROUND, 0);
+            // Cadeia do oráculo: entrada da rodada r = blake2^(10 - r)(s)
+            let chain = |round: u64| (round..10).fold([7u8; 32], |value, _| FiapoRandomness::blake2_256(&value));
+
+            assert_eq!(beacon.submit_oracle_input(3, chain(3)), Err(RandomnessError::OracleNotConfigured));
+            set_caller(accounts.bob);
+            assert_eq!(beacon.set_oracle_chain(Some((0, chain(0)))), Err(RandomnessError::Unauthorized));
+            assert_eq!(beacon.set_min_revealers(1), Err(RandomnessError::Unauthorized));
+            set_caller(accounts.alice);
+            assert_eq!(beacon.set_min_revealers(0), Err(RandomnessError::InvalidConfiguration));
+            assert_eq!(beacon.set_min_revealers(1), Ok(()));
+            // A rodada servida pela âncora precisa estar com commits abertos
+            set_time(ROUND + ROUND / 2);
+            assert_eq!(beacon.set_oracle_chain(Some((0, chain(0)))), Err(RandomnessError::InvalidConfiguration));
+            assert_eq!(beacon.set_oracle_chain(Some((1, chain(1)))), Ok(()));
+
+            // Revelador presente, mas sem a entrada do oráculo a rodada 2 falha
+            set_time(2 * ROUND);
+            assert_eq!(commit_as(&mut beacon, accounts.bob, 2, [1; 32]), Ok(()));
+            set_time(2 * ROUND + ROUND / 2);
+            assert_eq!(beacon.reveal(2, [1; 32]), Ok(()));
+            set_time(3 * ROUND);
+            assert_eq!(beacon.finalize_round(2), Ok(None));
+
+            assert_eq!(commit_as(&mut beacon, accounts.bob, 3, [2; 32]), Ok(()));
+            set_time(3 * ROUND + ROUND / 2);
+            assert_eq!(beacon.reveal(3, [2; 32]), Ok(()));
+            set_caller(accounts.eve);
+            assert_eq!(beacon.submit_oracle_input(3, chain(4)), Err(RandomnessError::InvalidOracleInput));
+            assert_eq!(beacon.submit_oracle_input(3, [9; 32]), Err(RandomnessError::InvalidOracleInput));
+            assert_eq!(beacon.submit_oracle_input(3, chain(3)), Ok(()));
+            assert_eq!(beacon.submit_oracle_input(3, chain(3)), Err(RandomnessError::AlreadyRevealed));
+            assert_eq!(beacon.oracle_chain(), Some((3, chain(3))));
+
+            set_time(4 * ROUND);
+            let seed = beacon.finalize_round(3).unwrap().unwrap();
+            let entropy = FiapoRandomness::blake2_256(&(SEED_DOMAIN, 3u64, accounts.bob, [2u8; 32]).encode());
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            let expected = FiapoRandomness::blake2_256(&(SEED_DOMAIN, contract, 3u64, entropy, Some(chain(3))).encode());
+            assert_eq!(seed, expected);
+            assert!(beacon.get_round(3).unwrap().oracle_input);
+        }
+
+        #[ink::test]
+        fn reanchoring_during_reveal_is_rejected() {
+            let mut beacon = FiapoRandomness::new(ROUND, 0);
+            assert_eq!(beacon.set_oracle_chain(Some((0, [1; 32]))), Ok(()));
+            assert_eq!(beacon.request_randomness(), 1);
+
+            // Com os segredos da rodada 1 públicos, o owner não reancora em 0 com um
+            // valor moído, nem troca ou remove a âncora enquanto ela tem pedidos
+            let ground = FiapoRandomness::blake2_256(&[9; 32]);
+            set_time(ROUND + ROUND / 2);
+            assert_eq!(beacon.set_oracle_chain(Some((0, ground))), Err(RandomnessError::RequestsPending));
+            assert_eq!(beacon.set_oracle_chain(Some((1, ground))), Err(RandomnessError::RequestsPending));
+            assert_eq!(beacon.set_oracle_chain(None), Err(RandomnessError::RequestsPending));
+
+            // Sem pedidos pendentes, ainda não reancora numa rodada já em revelação
+            set_time(2 * ROUND + ROUND / 2);
+            assert_eq!(beacon.finalize_round(1), Ok(None));
+            assert_eq!(beacon.set_oracle_chain(Some((0, ground))), Err(RandomnessError::InvalidConfiguration));
+            assert_eq!(beacon.set_oracle_chain(Some((1, ground))), Err(RandomnessError::InvalidConfiguration));
+            assert_eq!(beacon.set_oracle_chain(Some((2, ground))), Ok(()));
+            assert_eq!(beacon.set_oracle_chain(None), Ok(()));
+        }
+    }
+}
@@ -183,6 +183,11 @@ mod fiapo_rewards {
         total_distributed: Balance,
         /// Último ranking mensal
         last_monthly_ranking: u64,
+        /// Governança pode ajustar os pesos e publica o score de participação
+        /// em `update_wallet_data`
+        governance_contract: Option<AccountId>,
+        /// Timelock das propostas aprovadas: também ajusta os pesos
+        timelock_contract: Option<AccountId>,
     }
 
     impl FiapoRewards {
@@ -200,6 +205,8 @@ mod fiapo_rewards {
                 rewards_fund: 0,
                 total_distributed: 0,
                 last_monthly_ranking: 0,
+                governance_contract: None,
+                timelock_contract: None,
             };
             
             // Inicializa configurações padrão
@@ -319,7 +326,8 @@ mod fiapo_rewards {
             governance_score: u32,
         ) -> Result<(), RewardsError> {
             // Apenas owner ou contratos autorizados podem atualizar
-            if self.env().caller() != self.owner && self.env().caller() != self.core_contract {
+            let caller = self.env().caller();
+            if caller != self.owner && caller != self.core_contract && Some(caller) != self.governance_contract {
                 return Err(RewardsError::Unauthorized);
             }
 
@@ -480,7 +488,11 @@ mod fiapo_rewards {
         /// Atualiza pesos de scoring
         #[ink(message)]
         pub fn update_scoring_weights(&mut self, weights: ScoringWeights) -> Result<(), RewardsError> {
-            if self.env().caller() != self.owner {
+            let caller = self.env().caller();
+            if caller != self.owner
+                && Some(caller) != self.governance_contract
+                && Some(caller) != self.timelock_contract
+            {
                 return Err(RewardsError::Unauthorized);
             }
             // Valida que soma dos pesos é 100
@@ -496,6 +508,24 @@ mod fiapo_rewards {
             self.scoring_weights = weights;
             Ok(())
         }
+
+        #[ink(message)]
+        pub fn set_governance_contract(&mut self, governance: Option<AccountId>) -> Result<(), RewardsError> {
+            if self.env().caller() != self.owner {
+                return Err(RewardsError::Unauthorized);
+            }
+            self.governance_contract = governance;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_timelock_contract(&mut self, timelock: Option<AccountId>) -> Result<(), RewardsError> {
+            if self.env().caller() != self.owner {
+                return Err(RewardsError::Unauthorized);
+            }
+            self.timelock_contract = timelock;
+            Ok(())
+        }
     }
 
     #[cfg(test)]
@@ -508,6 +538,68 @@ mod fiapo_rewards {
             let contract = FiapoRewards::new(accounts.charlie);
             assert_eq!(contract.total_distributed(), 0);
         }
+
+        #[ink::test]
+        fn governance_updates_scoring_weights_via_mirror() {
+            use fiapo_logics::traits::rewards::ScoringWeightsParams;
+            use scale::{Decode, Encode};
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoRewards::new(accounts.charlie);
+            contract.set_governance_contract(Some(accounts.eve)).unwrap();
+
+            let params = ScoringWeightsParams {
+                balance_weight: 20,
+                staking_weight: 40,
+                burn_weight: 20,
+                transaction_weight: 10,
+                affiliate_weight: 5,
+                governance_weight: 5,
+            };
+            let weights = ScoringWeights::decode(&mut &params.encode()[..]).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.update_scoring_weights(weights.clone()), Err(RewardsError::Unauthorized));
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.update_scoring_weights(weights.clone()), Ok(()));
+            assert_eq!(contract.scoring_weights, weights);
+        }
+
+        #[ink::test]
+        fn governance_pushes_governance_score() {
+            use fiapo_logics::traits::rewards::WalletDataInfo;
+            use scale::{Decode, Encode};
+
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            let mut contract = FiapoRewards::new(accounts.charlie);
+            contract.update_wallet_data(accounts.django, 50 * SCALE, 10 * SCALE, 0, 2, 0).unwrap();
+
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(
+                contract.update_wallet_data(accounts.django, 50 * SCALE, 10 * SCALE, 0, 2, 7),
+                Err(RewardsError::Unauthorized)
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            contract.set_governance_contract(Some(accounts.eve)).unwrap();
+
+            // Governança relê os dados pelo espelho e troca apenas o score de governança
+            let mut data = WalletDataInfo::decode(&mut &contract.get_wallet_data(accounts.django).encode()[..]).unwrap();
+            let before = data.total_score;
+            data.governance_score = 7;
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.update_wallet_data(
+                accounts.django,
+                data.balance,
+                data.staking_balance,
+                data.burn_volume,
+                data.affiliate_count,
+                data.governance_score,
+            ), Ok(()));
+
+            let updated = contract.get_wallet_data(accounts.django);
+            assert_eq!((updated.balance, updated.affiliate_count, updated.governance_score), (50 * SCALE, 2, 7));
+            assert_eq!(updated.total_score, before + 7 * contract.scoring_weights.governance_weight as u128);
+        }
     }
 }
 
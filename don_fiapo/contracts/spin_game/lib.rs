@@ -5,12 +5,16 @@ pub mod royal_wheel {
     use ink::storage::Mapping;
     use ink::prelude::string::String;
     use ink::storage::traits::StorageLayout;
+    use fiapo_logics::traits::boost::BoostRegistryCall;
+    use fiapo_logics::traits::randomness::{derive_random, random_below, Randomness, RandomnessRef};
+    use fiapo_traits::{BoostSource, BoostTarget};
 
     // --- Constants ---
     const MAX_DAILY_USDT_CENTS: u32 = 200_00; // 200.00 USDT
     const MAX_DAILY_JACKPOTS: u8 = 1;
     const MAX_CAMPAIGN_USDT_CENTS: u32 = 6000_00; // 6000.00 USDT
     const BOOST_DURATION_MS: u64 = 5 * 60 * 60 * 1000; // 5 hours
+    const BOOST_APY_BPS: u32 = 100; // +1% staking APY while the boost is active
 
     // --- Enums ---
 
@@ -21,6 +25,9 @@ pub mod royal_wheel {
         InsufficientSpins,
         TransferFailed,
         ContractPaused,
+        RandomnessNotConfigured,
+        RandomnessPending,
+        SpinNotFound,
     }
 
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy, Default)]
@@ -65,10 +72,30 @@ pub mod royal_wheel {
         pub description: String,
     }
 
+    /// Spin paid for and waiting for its randomness beacon round
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, StorageLayout))]
+    pub struct PendingSpin {
+        pub player: AccountId,
+        pub package: SpinPackage,
+        pub round: u64,
+    }
+
     // --- Events ---
 
+    #[ink(event)]
+    pub struct SpinRequested {
+        #[ink(topic)]
+        spin_id: u64,
+        #[ink(topic)]
+        player: AccountId,
+        round: u64,
+    }
+
     #[ink(event)]
     pub struct SpinExecuted {
+        #[ink(topic)]
+        spin_id: u64,
         #[ink(topic)]
         player: AccountId,
         package: SpinPackage,
@@ -116,8 +143,13 @@ pub mod royal_wheel {
         daily_limits: DailyLimits,
         campaign_usdt_cents: u32,
         
-        // Nonce for randomness
-        nonce: u64,
+        // Randomness beacon: spins are requested now and settled after the round
+        randomness_contract: Option<AccountId>,
+        pending_spins: Mapping<u64, PendingSpin>,
+        next_spin_id: u64,
+
+        // Boost Registry (staking reads the aggregated boost)
+        boost_registry: Option<AccountId>,
     }
 
     impl RoyalWheel {
@@ -132,7 +164,10 @@ pub mod royal_wheel {
                 user_boost_end_time: Mapping::default(),
                 daily_limits: DailyLimits::default(),
                 campaign_usdt_cents: 0,
-                nonce: 0,
+                randomness_contract: None,
+                pending_spins: Mapping::default(),
+                next_spin_id: 1,
+                boost_registry: None,
             }
         }
 
@@ -145,6 +180,20 @@ pub mod royal_wheel {
             Ok(())
         }
 
+        #[ink(message)]
+        pub fn set_boost_registry(&mut self, registry: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.boost_registry = registry;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_randomness_contract(&mut self, randomness: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.randomness_contract = randomness;
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn set_paused(&mut self, paused: bool) -> Result<(), Error> {
             self.ensure_owner()?;
@@ -181,8 +230,10 @@ pub mod royal_wheel {
             Ok(())
         }
 
+        /// Consumes a spin and requests randomness for a future beacon round.
+        /// Returns the spin id to settle once that round is finalized.
         #[ink(message)]
-        pub fn spin(&mut self) -> Result<SpinResult, Error> {
+        pub fn spin(&mut self) -> Result<u64, Error> {
             if self.paused { return Err(Error::ContractPaused); }
             
             let player = self.env().caller();
@@ -191,30 +242,50 @@ pub mod royal_wheel {
             if balance == 0 {
                 return Err(Error::InsufficientSpins);
             }
+            let randomness = self.randomness_contract.ok_or(Error::RandomnessNotConfigured)?;
 
             // Consume spin
             self.spins_balance.insert(player, &balance.saturating_sub(1));
 
-            // Get context
+            // Package is fixed at request time
             let package = self.user_active_package.get(player).unwrap_or(SpinPackage::One);
-            self.check_daily_reset();
+            let mut beacon: RandomnessRef = randomness.into();
+            let round = beacon.request_randomness();
 
-            // Draw Reward
-            let reward = self.draw_reward(player, package);
+            let spin_id = self.next_spin_id;
+            self.next_spin_id = self.next_spin_id.saturating_add(1);
+            self.pending_spins.insert(spin_id, &PendingSpin { player, package, round });
 
-            // Apply Reward Logic (Storage updates, Limits updates)
-            self.apply_reward(&player, &reward);
+            self.env().emit_event(SpinRequested { spin_id, player, round });
 
-            // Emit Event
-            self.env().emit_event(SpinExecuted {
-                player,
-                package,
-                reward_type: reward.reward_type,
-                amount: reward.amount,
-                wheel_index: reward.wheel_index,
-            });
+            Ok(spin_id)
+        }
+
+        /// Draws and applies the reward of a pending spin with its round seed (anyone can call)
+        #[ink(message)]
+        pub fn settle_spin(&mut self, spin_id: u64) -> Result<SpinResult, Error> {
+            let pending = self.pending_spins.get(spin_id).ok_or(Error::SpinNotFound)?;
+            let beacon: RandomnessRef = self.randomness_contract.ok_or(Error::RandomnessNotConfigured)?.into();
+            let seed = beacon.randomness(pending.round).ok_or(Error::RandomnessPending)?;
+            Ok(self.complete_spin(spin_id, pending, &seed))
+        }
 
-            Ok(reward)
+        /// Moves a pending spin to a new round when its round failed without entropy
+        #[ink(message)]
+        pub fn rerequest_spin_randomness(&mut self, spin_id: u64) -> Result<u64, Error> {
+            let mut pending = self.pending_spins.get(spin_id).ok_or(Error::SpinNotFound)?;
+            let mut beacon: RandomnessRef = self.randomness_contract.ok_or(Error::RandomnessNotConfigured)?.into();
+            if !beacon.round_failed(pending.round) {
+                return Err(Error::RandomnessPending);
+            }
+            pending.round = beacon.request_randomness();
+            self.pending_spins.insert(spin_id, &pending);
+            Ok(pending.round)
+        }
+
+        #[ink(message)]
+        pub fn get_pending_spin(&self, spin_id: u64) -> Option<PendingSpin> {
+            self.pending_spins.get(spin_id)
         }
 
         #[ink(message)]
@@ -265,25 +336,37 @@ pub mod royal_wheel {
             }
         }
 
-        fn weighted_random(&mut self, max: u32) -> u32 {
-            let block_number = self.env().block_number();
-            let timestamp = self.env().block_timestamp();
-            let caller = self.env().caller();
-            self.nonce = self.nonce.wrapping_add(1);
+        fn complete_spin(&mut self, spin_id: u64, pending: PendingSpin, seed: &[u8; 32]) -> SpinResult {
+            self.pending_spins.remove(spin_id);
+            self.check_daily_reset();
 
-            let seed_data = (block_number, timestamp, caller, self.nonce);
-            let mut output = <[u8; 32]>::default();
-            ink::env::hash_encoded::<ink::env::hash::Keccak256, _>(&seed_data, &mut output);
-            
-            let random_val = u32::from_le_bytes([output[0], output[1], output[2], output[3]]);
-            if max == 0 {
-                return 0;
-            }
-            random_val.checked_rem(max).unwrap_or(0)
+            // Draw Reward
+            let reward = self.draw_reward(pending.package, seed, spin_id);
+
+            // Apply Reward Logic (Storage updates, Limits updates)
+            self.apply_reward(&pending.player, &reward);
+
+            // Emit Event
+            self.env().emit_event(SpinExecuted {
+                spin_id,
+                player: pending.player,
+                package: pending.package,
+                reward_type: reward.reward_type,
+                amount: reward.amount,
+                wheel_index: reward.wheel_index,
+            });
+
+            reward
         }
 
-        fn draw_reward(&mut self, _player: AccountId, package: SpinPackage) -> SpinResult {
-            let rng = self.weighted_random(10000); // 0-9999
+        /// Value in `0..max` for the `draw`-th roll of a spin, derived from the round seed
+        fn weighted_random(seed: &[u8; 32], spin_id: u64, draw: u8, max: u32) -> u32 {
+            let random = derive_random(seed, &(spin_id, draw));
+            random_below(&random, max as u64) as u32
+        }
+
+        fn draw_reward(&self, package: SpinPackage, seed: &[u8; 32], spin_id: u64) -> SpinResult {
+            let rng = Self::weighted_random(seed, spin_id, 0, 10000); // 0-9999
 
             // 1. Check Jackpot (0.05% -> < 5)
             // Global check: Max 1/day, 3/campaign (not implemented fully here but daily is)
@@ -309,7 +392,7 @@ pub mod royal_wheel {
             if rng < usdt_end {
                 // USDT Hit
                 // Determine 1 vs 5 USDT (Internal weight: 20% chances for 5 USDT)
-                let sub_rng = self.weighted_random(100);
+                let sub_rng = Self::weighted_random(seed, spin_id, 1, 100);
                 let (reward_id, amount_cents) = if sub_rng < 20 { (3, 500) } else { (6, 100) };
 
                 // Anti-Drain Check
@@ -327,12 +410,12 @@ pub mod royal_wheel {
                 // 1000 FIAPO (5%)
                 // 100 FIAPO (25%)
                 // 0.5 FIAPO (70%)
-                let sub_rng = self.weighted_random(100);
+                let sub_rng = Self::weighted_random(seed, spin_id, 1, 100);
                 if sub_rng < 5 { return self.create_reward(4); }
                 if sub_rng < 30 { return self.create_reward(9); }
                 
                 // 0.5 FIAPO - Randomize index for visual variety (2, 5, 8, 10)
-                let visual_rng = self.weighted_random(4);
+                let visual_rng = Self::weighted_random(seed, spin_id, 2, 4);
                 let idx = match visual_rng {
                     0 => 2,
                     1 => 5,
@@ -394,6 +477,14 @@ pub mod royal_wheel {
                     let start_time = if current_end > now { current_end } else { now };
                     let new_end = start_time.saturating_add(BOOST_DURATION_MS);
                     self.user_boost_end_time.insert(player, &new_end);
+
+                    // Publish to the registry so staking applies it
+                    if let Some(registry) = self.boost_registry {
+                        let _ = BoostRegistryCall::post_boost(
+                            registry, *player, BoostSource::SpinGame, BoostTarget::StakingApy,
+                            BOOST_APY_BPS, new_end,
+                        );
+                    }
                     
                     self.env().emit_event(BoostActivated {
                         player: *player,
@@ -494,15 +585,21 @@ pub mod royal_wheel {
             set_caller(accounts.bob);
             contract.credit_spins(accounts.charlie, 5, 0).unwrap();
             
-            // Charlie gira
+            // Sem beacon o giro não é aceito nem consumido
             set_caller(accounts.charlie);
             set_block(2);
             set_timestamp(2000);
-            let result = contract.spin();
-            assert!(result.is_ok());
-            
-            // Verifica que saldo diminuiu
-            assert_eq!(contract.get_spins(accounts.charlie), 4);
+            assert_eq!(contract.spin(), Err(Error::RandomnessNotConfigured));
+            assert_eq!(contract.get_spins(accounts.charlie), 5);
+            assert_eq!(contract.settle_spin(1), Err(Error::SpinNotFound));
+
+            // Giro pendente liquidado com a semente da rodada
+            let pending = PendingSpin { player: accounts.charlie, package: SpinPackage::One, round: 1 };
+            contract.pending_spins.insert(1, &pending);
+            let reward = contract.complete_spin(1, pending.clone(), &[3; 32]);
+            assert_eq!(reward, contract.draw_reward(SpinPackage::One, &[3; 32], 1));
+            assert!(reward.wheel_index <= 11);
+            assert_eq!(contract.get_pending_spin(1), None);
         }
 
         /// Testa spin sem saldo
@@ -591,24 +688,27 @@ pub mod royal_wheel {
             
             let mut contract = RoyalWheel::new(accounts.bob);
             
-            // Oracle credita 3 spins
-            set_caller(accounts.bob);
-            contract.credit_spins(accounts.charlie, 3, 2).unwrap();
-            
-            // Charlie gira 3 vezes
-            set_caller(accounts.charlie);
-            for i in 0..3 {
-                set_block(i + 2);
-                set_timestamp((i as u64 + 2) * 1000);
-                let result = contract.spin();
-                assert!(result.is_ok());
+            // Sementes diferentes: mesmos giros reproduzíveis, resultados variados
+            let rolls: Vec<u8> = (0..50u8)
+                .map(|i| contract.draw_reward(SpinPackage::TwentyFive, &[i; 32], 1).wheel_index)
+                .collect();
+            let replay: Vec<u8> = (0..50u8)
+                .map(|i| contract.draw_reward(SpinPackage::TwentyFive, &[i; 32], 1).wheel_index)
+                .collect();
+            assert_eq!(rolls, replay);
+            assert!(rolls.iter().any(|index| *index != rolls[0]));
+
+            // Cada giro pendente é liquidado uma única vez
+            for spin_id in 1..=3u64 {
+                let pending = PendingSpin { player: accounts.charlie, package: SpinPackage::TwentyFive, round: 1 };
+                contract.pending_spins.insert(spin_id, &pending);
+                set_timestamp((spin_id + 2) * 1000);
+                contract.complete_spin(spin_id, pending, &[9; 32]);
             }
-            
-            // Verifica que saldo zerou
-            assert_eq!(contract.get_spins(accounts.charlie), 0);
-            
-            // Tentar girar novamente deve falhar
-            set_block(10);
+            assert!((1..=3u64).all(|spin_id| contract.get_pending_spin(spin_id).is_none()));
+
+            // Sem saldo o giro falha antes de pedir aleatoriedade
+            set_caller(accounts.charlie);
             let result = contract.spin();
             assert_eq!(result.unwrap_err(), Error::InsufficientSpins);
         }
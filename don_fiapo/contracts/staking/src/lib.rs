@@ -6,11 +6,19 @@
 //! - Don Lunes: APY 6-37%, pagamento semanal
 //! - Don Fiapo: APY 7-70%, pagamento mensal
 //!
+//! Posições podem ser travadas (veFIAPO) de 1 mês a 4 anos: multiplicador de APY
+//! e poder de voto que decai até o fim do lock, registrado em checkpoints por
+//! instante (`voting_power_of`).
+//! O stake de cada usuário e o total são registrados em checkpoints por bloco
+//! (`staked_balance_at` / `total_staked_at`) para snapshots da governança.
+//!
 //! Integrações:
 //! - Core: Transferência de tokens (PSP22)
 //! - Affiliate: Boost de APY e registro de atividade
+//! - ICO: Boost de APY por NFTs mineradores em posse do staker (informados pelo ICO)
 //! - Rewards: Distribuição de taxas para o fundo de recompensas
 //! - Oracle: Stake em nome de terceiros
+//! - LUSDT (PSP22) e LUNES nativo: ativos alternativos de recompensa e taxa
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
@@ -23,14 +31,39 @@ mod fiapo_staking {
     // Cross-contract references (pure ink!, no OpenBrush)
     use fiapo_logics::traits::rewards::RewardsCall;
     use fiapo_logics::traits::affiliate::AffiliateCall;
+    use fiapo_logics::traits::boost::BoostRegistryCall;
+    use fiapo_logics::traits::ico::IcoCall;
+    use fiapo_traits::BoostTarget;
     use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
-    use fiapo_logics::traits::staking::Staking;
+    use fiapo_logics::traits::staking::{NftHolding, Staking, MAX_NFT_HOLDINGS};
+    use fiapo_logics::traits::oracle::{Oracle, OracleRef, PaymentPurpose};
 
 
     /// Constantes
     pub const SECONDS_PER_DAY: u64 = 86400;
     pub const SCALE: u128 = 100_000_000;
     pub const LUSDT_SCALE: u128 = 1_000_000;
+    pub const MAX_PENALTY_STEPS: usize = 16;
+    /// Parte fixa da penalidade de saída antecipada do Don Burn (10 USDT)
+    pub const DEFAULT_BURN_EXIT_FEE_USDT_CENTS: u64 = 1_000;
+    /// Duração máxima de lock (4 anos)
+    pub const MAX_LOCK_DAYS: u32 = 1460;
+    pub const MAX_LOCK_TIERS: usize = 16;
+    /// Cooldown máximo de unbonding (limita a varredura dos buckets de saída)
+    pub const MAX_UNBONDING_DAYS: u32 = 365;
+    /// NFTs do usuário lidos do ICO por `refresh_nft_boost` (ativos ou não)
+    pub const MAX_NFT_BOOST_SCAN: usize = 100;
+    /// Limites de `set_nft_boost_config`
+    pub const MAX_NFTS_COUNTED: u32 = MAX_NFT_HOLDINGS as u32;
+    pub const MAX_NFT_BOOST_BPS: u32 = 5_000;
+    pub const MAX_RARITY_MULTIPLIER_BPS: u32 = 50_000;
+
+    /// Stake registrado ao fim de um bloco: (bloco, valor)
+    pub type Checkpoint = (u32, Balance);
+    /// (usuário ou `None` = total, índice do checkpoint)
+    pub type CheckpointKey = (Option<AccountId>, u32);
+    /// Locks vigentes de um usuário a partir de um instante: (timestamp, [(principal, lock)])
+    pub type LockCheckpoint = (u64, Vec<(Balance, PositionLock)>);
 
     /// Resultado do cálculo de taxa de entrada
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -58,10 +91,33 @@ mod fiapo_staking {
         PoolNotActive,
         AffiliateCallFailed,
         RewardsCallFailed,
+        ClaimWindowClosed,
+        InvalidPoolConfig,
+        UnbondingNotFound,
+        UnbondingNotReady,
+        NotApproved,
+        SelfTransfer,
+        InsufficientRewardReserve,
+        AssetNotConfigured,
+        EmergencyModeActive,
+        EmergencyModeInactive,
+        /// Parte fixa em USDT da penalidade Don Burn ainda não paga
+        ExitFeeNotSettled,
+        ExitFeeNotRequired,
+        ExitFeeAlreadyPaid,
+        OraclePaymentNotConfirmed,
+        TxHashAlreadyUsed,
+        /// Posição com lock ativo não pode ser sacada
+        PositionLocked,
+        InvalidLockDuration,
+        /// Locks só podem ser estendidos
+        LockCannotShorten,
+        /// Recompensas em FIAPO não usam reserva
+        UnsupportedRewardAsset,
     }
 
     /// Tipo de pool de staking
-    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum PoolType {
         DonBurn = 0,   
@@ -78,7 +134,7 @@ mod fiapo_staking {
                 _ => None,
             }
         }
-        pub fn to_u8(&self) -> u8 {
+        pub fn to_u8(self) -> u8 {
             match self {
                 PoolType::DonBurn => 0,
                 PoolType::DonLunes => 1,
@@ -87,12 +143,36 @@ mod fiapo_staking {
         }
     }
 
+    /// Ativos suportados para recompensas e taxas de entrada
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Asset {
+        /// Token FIAPO (fiapo-core)
+        Fiapo = 0,
+        /// LUNES nativo da chain
+        Lunes = 1,
+        /// LUSDT (PSP22)
+        Lusdt = 2,
+    }
+
+    impl Asset {
+        pub fn index(&self) -> usize {
+            match self {
+                Asset::Fiapo => 0,
+                Asset::Lunes => 1,
+                Asset::Lusdt => 2,
+            }
+        }
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
     pub enum PositionStatus {
         Active,
         Cancelled,
         Completed,
+        /// Saque solicitado, aguardando o fim do cooldown
+        Unbonding,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -104,6 +184,16 @@ mod fiapo_staking {
         pub cancellation_penalty_bps: u16,
         pub payment_frequency_days: u32,
         pub active: bool,
+        /// Cooldown entre `request_unstake` e `withdraw_unbonded`
+        pub unbonding_days: u32,
+        /// Taxa extra para saída instantânea (sem cooldown)
+        pub instant_exit_fee_bps: u16,
+        /// Ativo em que as recompensas são pagas (o stake é sempre em FIAPO)
+        pub reward_asset: Asset,
+        /// Unidades do ativo de recompensa por 1 FIAPO (SCALE) de recompensa calculada
+        pub reward_units_per_fiapo: Balance,
+        /// Ativo da taxa de entrada: FIAPO (deduzida do stake) ou LUSDT (cobrada à parte)
+        pub fee_asset: Asset,
     }
 
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -120,6 +210,233 @@ mod fiapo_staking {
         pub status: PositionStatus,
     }
 
+    /// Degrau de uma curva de penalidade: a partir de `from_day` dias em stake,
+    /// aplica `scale_bps` da penalidade base do pool
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PenaltyStep {
+        pub from_day: u32,
+        pub scale_bps: u16,
+    }
+
+    /// Como a penalidade de saída antecipada evolui até a maturidade (`min_period_days`)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum PenaltyCurve {
+        /// Penalidade integral até a maturidade, zero depois
+        #[default]
+        Cliff,
+        /// Decai linearmente até zero na maturidade
+        Linear,
+        /// Tabela de degraus em ordem crescente de `from_day`
+        Steps(Vec<PenaltyStep>),
+    }
+
+    /// Destino da penalidade (mesma divisão aplicada em `distribute_funds`)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PenaltySplit {
+        pub team: Balance,
+        pub rewards_fund: Balance,
+        pub burn: Balance,
+        pub staking: Balance,
+    }
+
+    /// Prévia exata de um `request_unstake` feito agora
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UnstakePreview {
+        pub position_id: u64,
+        pub is_early: bool,
+        /// Fração da penalidade base aplicada pela curva (10000 = integral)
+        pub penalty_scale_bps: u32,
+        pub principal: Balance,
+        /// Recompensas pendentes, no ativo de recompensa do pool
+        pub rewards: Balance,
+        pub principal_penalty: Balance,
+        pub rewards_penalty: Balance,
+        /// Divisão da parte da penalidade distribuída em FIAPO
+        pub split: PenaltySplit,
+        /// Valor líquido em FIAPO (inclui recompensas quando o pool paga em FIAPO)
+        pub net_amount: Balance,
+        pub reward_asset: Asset,
+        /// Recompensas líquidas em ativo externo (LUNES/LUSDT)
+        pub reward_amount: Balance,
+        pub release_at: u64,
+        /// Parte fixa em USDT (centavos) exigida antes do saque; 0 se não se aplica
+        pub fixed_fee_usdt_cents: u64,
+        pub fixed_fee_settled: bool,
+    }
+
+    /// Faixa da tabela de lock: locks com duração total >= `min_days` recebem os multiplicadores
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct LockTier {
+        pub min_days: u32,
+        /// Multiplicador de APY (10000 = 1x)
+        pub apy_multiplier_bps: u32,
+        /// Multiplicador do poder de voto no início do lock (10000 = 1x)
+        pub voting_multiplier_bps: u32,
+    }
+
+    /// Lock (veFIAPO) de uma posição: o poder de voto decai linearmente até `end`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct PositionLock {
+        pub start: u64,
+        pub end: u64,
+        pub apy_multiplier_bps: u32,
+        pub voting_multiplier_bps: u32,
+    }
+
+    /// Saque na fila de unbonding (valor já líquido de penalidades)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct UnbondingRequest {
+        pub position_id: u64,
+        pub user: AccountId,
+        pub pool_type: PoolType,
+        /// Principal em FIAPO (inclui recompensas quando o pool paga em FIAPO)
+        pub amount: Balance,
+        /// Parte de `amount` que são recompensas em FIAPO
+        pub fiapo_rewards: Balance,
+        pub penalty: Balance,
+        pub requested_at: u64,
+        pub release_at: u64,
+        pub reward_asset: Asset,
+        /// Recompensas reservadas em ativo externo (LUNES/LUSDT)
+        pub reward_amount: Balance,
+    }
+
+    /// Visão agregada da fila de unbonding (planejamento de tesouraria)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UnbondingSummary {
+        pub total_unbonding: Balance,
+        pub unbonding_per_pool: [Balance; 3],
+        pub releasable_now: Balance,
+        pub queue_length: u32,
+    }
+
+    /// Boost de APY por NFTs do ICO (tier ponderado, multiplicador opcional de raridade)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct NftBoostConfig {
+        pub enabled: bool,
+        /// Boost por NFT ativo, indexado pelo tier (0 = Free ... 6 = Tier7)
+        pub tier_boost_bps: [u32; 7],
+        pub rarity_enabled: bool,
+        /// Multiplicador por raridade (10000 = 1x), Common ... Legendary
+        pub rarity_multiplier_bps: [u32; 5],
+        pub max_boost_bps: u32,
+        /// Limite de NFTs considerados por usuário (controle de gas)
+        pub max_nfts_counted: u32,
+    }
+
+    impl Default for NftBoostConfig {
+        fn default() -> Self {
+            Self {
+                enabled: true,
+                tier_boost_bps: [10, 25, 50, 75, 100, 150, 200],
+                rarity_enabled: true,
+                rarity_multiplier_bps: [10000, 11000, 12500, 15000, 20000],
+                max_boost_bps: 500,
+                max_nfts_counted: 20,
+            }
+        }
+    }
+
+    /// Contribuição de um NFT para o boost
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct NftBoostItem {
+        pub nft_id: u64,
+        pub tier: u8,
+        pub visual_rarity: u8,
+        pub boost_bps: u32,
+    }
+
+    /// Composição do boost de APY de um usuário
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BoostBreakdown {
+        /// Boost do Boost Registry (ou do Affiliate, se não houver registry)
+        pub ecosystem_bps: u32,
+        /// Soma bruta dos NFTs, antes do cap
+        pub nft_uncapped_bps: u32,
+        pub nft_cap_bps: u32,
+        pub nft_bps: u32,
+        pub nfts: Vec<NftBoostItem>,
+        pub total_bps: u32,
+    }
+
+    /// Situação do modo de emergência
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct EmergencyStatus {
+        pub active: bool,
+        pub activated_at: u64,
+        pub activated_by: Option<AccountId>,
+        pub reason: String,
+        pub multisig: Option<AccountId>,
+        pub timelock: Option<AccountId>,
+        /// Principal ainda em custódia (posições ativas + fila de unbonding)
+        pub principal_outstanding: Balance,
+        pub withdrawals: u64,
+    }
+
+    /// Cronograma de pagamento de uma posição (janelas de claim do pool)
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct RewardSchedule {
+        pub position_id: u64,
+        pub payment_frequency_days: u32,
+        pub last_claim_at: u64,
+        pub next_claim_at: u64,
+        /// Recompensas acumuladas até agora (accrual contínuo)
+        pub accrued: Balance,
+        /// Recompensas liberadas até a última janela de pagamento
+        pub claimable: Balance,
+        pub claim_open: bool,
+    }
+
+    /// Agregados por usuário, mantidos incrementalmente a cada operação
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct UserAggregates {
+        pub staked_per_pool: [Balance; 3],
+        pub positions_per_pool: [u32; 3],
+        /// Recompensas líquidas recebidas, por ativo (índice = `Asset::index`)
+        pub lifetime_rewards: [Balance; 3],
+        pub penalties_paid: Balance,
+    }
+
+    /// Posição com os dados de que o frontend precisa, sem chamadas extras
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct PortfolioPosition {
+        pub position: StakingPosition,
+        /// Recompensas acumuladas até agora, com boost
+        pub pending_rewards: Balance,
+        pub claimable_rewards: Balance,
+        pub next_claim_at: Option<u64>,
+        /// Fim do período mínimo (saída sem penalidade a partir daqui)
+        pub unlock_at: u64,
+        /// Penalidade se a saída fosse solicitada agora
+        pub early_exit_penalty: Balance,
+        pub unbonding: Option<UnbondingRequest>,
+    }
+
+    /// Portfólio paginado de um usuário
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct UserPortfolio {
+        pub aggregates: UserAggregates,
+        pub boost_bps: u32,
+        pub total_positions: u32,
+        pub positions: Vec<PortfolioPosition>,
+    }
+
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub struct StakingStats {
@@ -149,6 +466,33 @@ mod fiapo_staking {
         amount_net: Balance,
         fee_amount: Balance,
         boost_bps: u32,
+        asset: Asset,
+    }
+
+    #[ink(event)]
+    pub struct RewardsFunded {
+        #[ink(topic)]
+        funder: AccountId,
+        asset: Asset,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardShortfallRecorded {
+        #[ink(topic)]
+        position_id: u64,
+        #[ink(topic)]
+        user: AccountId,
+        asset: Asset,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardReserveWithdrawn {
+        #[ink(topic)]
+        to: AccountId,
+        asset: Asset,
+        amount: Balance,
     }
 
     #[ink(event)]
@@ -161,6 +505,55 @@ mod fiapo_staking {
         penalty: Balance,
     }
 
+    #[ink(event)]
+    pub struct UnstakeRequested {
+        #[ink(topic)]
+        position_id: u64,
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+        penalty: Balance,
+        release_at: u64,
+    }
+
+    /// Transferência de posição (compatível com o evento `Transfer` do PSP34)
+    #[ink(event)]
+    pub struct PositionTransfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        #[ink(topic)]
+        position_id: u64,
+    }
+
+    #[ink(event)]
+    pub struct PositionApproval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        operator: AccountId,
+        position_id: Option<u64>,
+        approved: bool,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyModeEnabled {
+        #[ink(topic)]
+        activated_by: AccountId,
+        reason: String,
+        timestamp: u64,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyWithdrawal {
+        #[ink(topic)]
+        position_id: u64,
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+    }
+
     #[ink(event)]
     pub struct FeeDistributed {
         #[ink(topic)]
@@ -173,6 +566,40 @@ mod fiapo_staking {
         noble_part: Balance, // Added Noble Part
     }
 
+    #[ink(event)]
+    pub struct PoolConfigUpdated {
+        #[ink(topic)]
+        pool: u8,
+        apy_bps: u16,
+        min_period_days: u32,
+        payment_frequency_days: u32,
+        active: bool,
+    }
+
+    #[ink(event)]
+    pub struct LockUpdated {
+        #[ink(topic)]
+        position_id: u64,
+        #[ink(topic)]
+        user: AccountId,
+        start: u64,
+        end: u64,
+        apy_multiplier_bps: u32,
+        voting_multiplier_bps: u32,
+    }
+
+    #[ink(event)]
+    pub struct ExitFeePaid {
+        #[ink(topic)]
+        position_id: u64,
+        #[ink(topic)]
+        user: AccountId,
+        amount_usdt_cents: u64,
+        /// LUSDT cobrado on-chain (0 quando confirmado pelo oracle)
+        amount_lusdt: Balance,
+        tx_hash: Option<String>,
+    }
+
     #[ink(event)]
     pub struct PingReceived {
         #[ink(topic)]
@@ -191,13 +618,79 @@ mod fiapo_staking {
         owner: AccountId,
         pool_configs: Mapping<u8, PoolConfig>,
         positions: Mapping<u64, StakingPosition>,
-        user_positions: Mapping<AccountId, Vec<u64>>,
+        /// Posições vivas por usuário: (usuário, índice) -> id, com contagem e índice inverso
+        user_positions: Mapping<(AccountId, u32), u64>,
+        user_position_count: Mapping<AccountId, u32>,
+        user_position_index: Mapping<u64, u32>,
         next_position_id: u64,
         total_staked_per_pool: [Balance; 3],
         stakers_per_pool: [u32; 3],
         active_positions: u64,
         total_rewards_distributed: Balance,
         paused: bool,
+        unbonding: Mapping<u64, UnbondingRequest>,
+        /// Fila de unbonding indexada: posição na fila -> id da posição (e o inverso)
+        unbonding_queue: Mapping<u32, u64>,
+        unbonding_queue_index: Mapping<u64, u32>,
+        unbonding_queue_len: u32,
+        /// Saídas por dia de liberação (`release_at` arredondado para cima ao dia)
+        unbonding_buckets: Mapping<u64, Balance>,
+        /// Último dia com saída agendada
+        unbonding_last_day: u64,
+        unbonding_per_pool: [Balance; 3],
+        /// Recompensas em FIAPO incluídas nos valores da fila
+        unbonding_fiapo_rewards: Balance,
+        marketplace_contract: Option<AccountId>,
+        /// Aprovação individual por posição (PSP34 `approve` com id)
+        position_approvals: Mapping<u64, AccountId>,
+        /// Aprovação de operador para todas as posições do owner
+        operator_approvals: Mapping<(AccountId, AccountId), bool>,
+        lusdt_contract: Option<AccountId>,
+        /// Reservas de recompensa por ativo (índice = `Asset::index`)
+        reward_reserves: [Balance; 3],
+        /// Recompensas em ativo externo devidas além da reserva na saída, por posição
+        reward_shortfalls: Mapping<u64, (Asset, Balance)>,
+        /// Recompensas em ativo externo devidas a ex-owners de posições transferidas
+        /// (auto-claim do seller sem reserva suficiente)
+        owed_rewards: Mapping<(AccountId, Asset), Balance>,
+        rewards_distributed_per_asset: [Balance; 3],
+        /// Autoridades que podem ativar o modo de emergência
+        emergency_multisig: Option<AccountId>,
+        timelock_contract: Option<AccountId>,
+        emergency_mode: bool,
+        emergency_activated_at: u64,
+        emergency_activated_by: Option<AccountId>,
+        emergency_reason: String,
+        emergency_withdrawals: u64,
+        boost_registry: Option<AccountId>,
+        ico_contract: Option<AccountId>,
+        nft_boost_config: NftBoostConfig,
+        /// NFTs ativos do ICO por usuário, informados pelo ICO a cada mudança
+        /// (base do boost, sem chamadas ao ICO nos claims)
+        nft_holdings: Mapping<AccountId, Vec<NftHolding>>,
+        user_aggregates: Mapping<AccountId, UserAggregates>,
+        /// Curva de penalidade por pool (ausente = `Cliff`)
+        penalty_curves: Mapping<u8, PenaltyCurve>,
+        burn_exit_fee_usdt_cents: u64,
+        /// Posições cuja parte fixa em USDT já foi paga
+        exit_fee_paid: Mapping<u64, bool>,
+        used_tx_hashes: Mapping<String, bool>,
+        position_locks: Mapping<u64, PositionLock>,
+        /// Bônus de lock acumulado com o multiplicador anterior a uma extensão:
+        /// (instante da extensão, valor em FIAPO), pago no primeiro claim após ele
+        settled_lock_bonus: Mapping<u64, (u64, Balance)>,
+        /// Tabela de multiplicadores por duração, em ordem crescente de `min_days`
+        lock_tiers: Vec<LockTier>,
+        /// Governança pode ajustar a tabela de lock
+        governance_contract: Option<AccountId>,
+        /// Usuários com ao menos uma posição viva (base de quórum da governança)
+        unique_stakers: u32,
+        /// Histórico de stake por usuário e total
+        stake_checkpoints: Mapping<CheckpointKey, Checkpoint>,
+        stake_checkpoint_counts: Mapping<Option<AccountId>, u32>,
+        /// Histórico dos locks vigentes por usuário (poder veFIAPO em instantes passados)
+        lock_checkpoints: Mapping<(AccountId, u32), LockCheckpoint>,
+        lock_checkpoint_counts: Mapping<AccountId, u32>,
     }
 
     impl FiapoStaking {
@@ -217,18 +710,80 @@ mod fiapo_staking {
                 pool_configs: Mapping::default(),
                 positions: Mapping::default(),
                 user_positions: Mapping::default(),
+                user_position_count: Mapping::default(),
+                user_position_index: Mapping::default(),
                 next_position_id: 1,
                 total_staked_per_pool: [0; 3],
                 stakers_per_pool: [0; 3],
                 active_positions: 0,
                 total_rewards_distributed: 0,
                 paused: false,
+                unbonding: Mapping::default(),
+                unbonding_queue: Mapping::default(),
+                unbonding_queue_index: Mapping::default(),
+                unbonding_queue_len: 0,
+                unbonding_buckets: Mapping::default(),
+                unbonding_last_day: 0,
+                unbonding_per_pool: [0; 3],
+                unbonding_fiapo_rewards: 0,
+                marketplace_contract: None,
+                position_approvals: Mapping::default(),
+                operator_approvals: Mapping::default(),
+                lusdt_contract: None,
+                reward_reserves: [0; 3],
+                reward_shortfalls: Mapping::default(),
+                owed_rewards: Mapping::default(),
+                rewards_distributed_per_asset: [0; 3],
+                emergency_multisig: None,
+                timelock_contract: None,
+                emergency_mode: false,
+                emergency_activated_at: 0,
+                emergency_activated_by: None,
+                emergency_reason: String::new(),
+                emergency_withdrawals: 0,
+                boost_registry: None,
+                ico_contract: None,
+                nft_boost_config: NftBoostConfig::default(),
+                nft_holdings: Mapping::default(),
+                user_aggregates: Mapping::default(),
+                penalty_curves: Mapping::default(),
+                burn_exit_fee_usdt_cents: DEFAULT_BURN_EXIT_FEE_USDT_CENTS,
+                exit_fee_paid: Mapping::default(),
+                used_tx_hashes: Mapping::default(),
+                position_locks: Mapping::default(),
+                settled_lock_bonus: Mapping::default(),
+                lock_tiers: Self::default_lock_tiers(),
+                governance_contract: None,
+                unique_stakers: 0,
+                stake_checkpoints: Mapping::default(),
+                stake_checkpoint_counts: Mapping::default(),
+                lock_checkpoints: Mapping::default(),
+                lock_checkpoint_counts: Mapping::default(),
             };
 
             contract.initialize_pool_configs();
             contract
         }
 
+        /// 1 mês a 4 anos: APY de 1.05x a 2x, voto de 0.25x a 4x
+        fn default_lock_tiers() -> Vec<LockTier> {
+            [
+                (30, 10500, 2500),
+                (90, 11000, 5000),
+                (180, 12500, 10000),
+                (365, 15000, 15000),
+                (730, 17500, 25000),
+                (1460, 20000, 40000),
+            ]
+            .iter()
+            .map(|&(min_days, apy_multiplier_bps, voting_multiplier_bps)| LockTier {
+                min_days,
+                apy_multiplier_bps,
+                voting_multiplier_bps,
+            })
+            .collect()
+        }
+
         fn initialize_pool_configs(&mut self) {
             self.pool_configs.insert(0, &PoolConfig {
                 apy_bps: 1000, 
@@ -237,6 +792,11 @@ mod fiapo_staking {
                 cancellation_penalty_bps: 2000,
                 payment_frequency_days: 1,
                 active: true,
+                unbonding_days: 7,
+                instant_exit_fee_bps: 500,
+                reward_asset: Asset::Fiapo,
+                reward_units_per_fiapo: SCALE,
+                fee_asset: Asset::Fiapo,
             });
             self.pool_configs.insert(1, &PoolConfig {
                 apy_bps: 600, 
@@ -245,6 +805,11 @@ mod fiapo_staking {
                 cancellation_penalty_bps: 250, // 2.5%
                 payment_frequency_days: 7,
                 active: true,
+                unbonding_days: 14,
+                instant_exit_fee_bps: 500,
+                reward_asset: Asset::Fiapo,
+                reward_units_per_fiapo: SCALE,
+                fee_asset: Asset::Fiapo,
             });
             self.pool_configs.insert(2, &PoolConfig {
                 apy_bps: 700, 
@@ -253,6 +818,11 @@ mod fiapo_staking {
                 cancellation_penalty_bps: 1000, 
                 payment_frequency_days: 30,
                 active: true,
+                unbonding_days: 21,
+                instant_exit_fee_bps: 500,
+                reward_asset: Asset::Fiapo,
+                reward_units_per_fiapo: SCALE,
+                fee_asset: Asset::Fiapo,
             });
         }
 
@@ -271,6 +841,7 @@ mod fiapo_staking {
             }
         }
 
+        /// Recompensas acumuladas até o momento (inclui a fração do período corrente)
         #[ink(message)]
         pub fn pending_rewards(&self, position_id: u64) -> Balance {
             if let Some(position) = self.positions.get(position_id) {
@@ -278,14 +849,226 @@ mod fiapo_staking {
                     return 0;
                 }
                 if let Some(config) = self.pool_configs.get(position.pool_type.to_u8()) {
-                    let boost = self.fetch_user_boost(position.user);
-                    return self.calculate_rewards_with_boost(&position, &config, boost);
+                    let now = self.env().block_timestamp();
+                    let boost = self.fetch_user_boost_over(position.user, position.last_reward_time, now);
+                    return self.calculate_rewards_with_boost(&position, &config, boost, now);
                 }
             }
             0
         }
 
+        /// Recompensas que podem ser sacadas agora (até a última janela de pagamento)
+        #[ink(message)]
+        pub fn claimable_rewards(&self, position_id: u64) -> Balance {
+            self.get_reward_schedule(position_id)
+                .map(|schedule| schedule.claimable)
+                .unwrap_or(0)
+        }
+
+        /// Timestamp em que a próxima janela de claim da posição é liberada
+        #[ink(message)]
+        pub fn next_claim_at(&self, position_id: u64) -> Option<u64> {
+            let position = self.positions.get(position_id)?;
+            if position.status != PositionStatus::Active {
+                return None;
+            }
+            let config = self.pool_configs.get(position.pool_type.to_u8())?;
+            Some(self.next_claim_boundary(&position, &config))
+        }
+
+        /// Cronograma completo de pagamento de uma posição
+        #[ink(message)]
+        pub fn get_reward_schedule(&self, position_id: u64) -> Option<RewardSchedule> {
+            let position = self.positions.get(position_id)?;
+            if position.status != PositionStatus::Active {
+                return None;
+            }
+            let config = self.pool_configs.get(position.pool_type.to_u8())?;
+            let now = self.env().block_timestamp();
+
+            let boundary = self.last_claim_boundary(&position, &config, now);
+            let boost = self.fetch_user_boost_over(position.user, position.last_reward_time, boundary);
+            let claimable = if boundary > position.last_reward_time {
+                self.calculate_rewards_with_boost(&position, &config, boost, boundary)
+            } else {
+                0
+            };
+
+            Some(RewardSchedule {
+                position_id,
+                payment_frequency_days: config.payment_frequency_days,
+                last_claim_at: position.last_reward_time,
+                next_claim_at: self.next_claim_boundary(&position, &config),
+                accrued: self.calculate_rewards_with_boost(&position, &config, boost, now),
+                claimable,
+                claim_open: boundary > position.last_reward_time,
+            })
+        }
+
+        #[ink(message)]
+        pub fn get_unbonding(&self, position_id: u64) -> Option<UnbondingRequest> {
+            self.unbonding.get(position_id)
+        }
+
+        /// Fila de unbonding paginada (saques concluídos são trocados pelo último da fila)
+        #[ink(message)]
+        pub fn get_unbonding_queue(&self, offset: u32, limit: u32) -> Vec<UnbondingRequest> {
+            let end = offset.saturating_add(limit.min(100)).min(self.unbonding_queue_len);
+            (offset..end)
+                .filter_map(|index| self.unbonding_queue.get(index))
+                .filter_map(|id| self.unbonding.get(id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn get_unbonding_summary(&self) -> UnbondingSummary {
+            let now = self.env().block_timestamp();
+            let releasable_now = self.unbonding_outflows_until(now);
+            UnbondingSummary {
+                total_unbonding: self.unbonding_per_pool.iter().fold(0, |acc, &x| acc.saturating_add(x)),
+                unbonding_per_pool: self.unbonding_per_pool,
+                releasable_now,
+                queue_length: self.unbonding_queue_len,
+            }
+        }
+
+        /// Total que pode ser sacado da fila até `timestamp` (no mínimo agora), com
+        /// granularidade diária: só os buckets ainda futuros são lidos
+        #[ink(message)]
+        pub fn unbonding_outflows_until(&self, timestamp: u64) -> Balance {
+            let total = self.unbonding_per_pool.iter().fold(0u128, |acc, &x| acc.saturating_add(x));
+            let released_day = timestamp.max(self.env().block_timestamp()) / SECONDS_PER_DAY;
+            let pending = (released_day.saturating_add(1)..=self.unbonding_last_day)
+                .filter_map(|day| self.unbonding_buckets.get(day))
+                .fold(0u128, |acc, amount| acc.saturating_add(amount));
+            total.saturating_sub(pending)
+        }
+
+        #[ink(message)]
+        pub fn get_reward_reserve(&self, asset: Asset) -> Balance {
+            self.reward_reserves[asset.index()]
+        }
+
+        /// Recompensas em ativo externo ainda devidas à posição após a saída
+        #[ink(message)]
+        pub fn get_reward_shortfall(&self, position_id: u64) -> Option<(Asset, Balance)> {
+            self.reward_shortfalls.get(position_id)
+        }
+
+        #[ink(message)]
+        pub fn get_rewards_distributed(&self, asset: Asset) -> Balance {
+            self.rewards_distributed_per_asset[asset.index()]
+        }
+
+        #[ink(message)]
+        pub fn lusdt_contract(&self) -> Option<AccountId> {
+            self.lusdt_contract
+        }
+
+        /// Explica a composição do boost de APY aplicado ao usuário no próximo claim
+        #[ink(message)]
+        pub fn boost_breakdown(&self, user: AccountId) -> BoostBreakdown {
+            let ecosystem_bps = self.fetch_ecosystem_boost(user);
+            let (nft_bps, nft_uncapped_bps, nfts) = self.fetch_nft_boost(user);
+            BoostBreakdown {
+                ecosystem_bps,
+                nft_uncapped_bps,
+                nft_cap_bps: self.nft_boost_config.max_boost_bps,
+                nft_bps,
+                nfts,
+                total_bps: ecosystem_bps.saturating_add(nft_bps),
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_nft_boost_config(&self) -> NftBoostConfig {
+            self.nft_boost_config.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_pool_config(&self, pool: u8) -> Option<PoolConfig> {
+            self.pool_configs.get(pool)
+        }
+
+        #[ink(message)]
+        pub fn get_penalty_curve(&self, pool: u8) -> PenaltyCurve {
+            self.penalty_curves.get(pool).unwrap_or_default()
+        }
+
+        /// Penalidade, divisão e valor líquido se a posição solicitasse o saque agora
+        #[ink(message)]
+        pub fn unstake_preview(&self, position_id: u64) -> Option<UnstakePreview> {
+            let position = self.positions.get(position_id)?;
+            if position.status != PositionStatus::Active {
+                return None;
+            }
+            let config = self.pool_configs.get(position.pool_type.to_u8())?;
+            let now = self.env().block_timestamp();
+            let boost = self.fetch_user_boost_over(position.user, position.last_reward_time, now);
+            let rewards = self.calculate_rewards_with_boost(&position, &config, boost, now);
+
+            let penalty_scale_bps = self.penalty_scale_bps(&position, &config, now);
+            let (principal_penalty, rewards_penalty) = self.exit_penalty(&position, &config, rewards, now);
+            let external_rewards = config.reward_asset != Asset::Fiapo;
+            let distributable = if external_rewards {
+                principal_penalty
+            } else {
+                principal_penalty.saturating_add(rewards_penalty)
+            };
+
+            let net_rewards = rewards.saturating_sub(rewards_penalty);
+            let net_principal = position.amount.saturating_sub(principal_penalty);
+            let (net_amount, reward_amount) = if external_rewards {
+                (net_principal, net_rewards.min(self.reward_reserves[config.reward_asset.index()]))
+            } else {
+                (net_principal.saturating_add(net_rewards), 0)
+            };
+
+            Some(UnstakePreview {
+                position_id,
+                is_early: penalty_scale_bps > 0,
+                penalty_scale_bps,
+                principal: position.amount,
+                rewards,
+                principal_penalty,
+                rewards_penalty,
+                split: Self::penalty_split(position.pool_type, distributable),
+                net_amount,
+                reward_asset: config.reward_asset,
+                reward_amount,
+                release_at: now.saturating_add((config.unbonding_days as u64).saturating_mul(SECONDS_PER_DAY)),
+                fixed_fee_usdt_cents: self.fixed_exit_fee_cents(&position, &config, now),
+                fixed_fee_settled: self.exit_fee_paid.get(position_id).unwrap_or(false),
+            })
+        }
+
+        #[ink(message)]
+        pub fn get_user_aggregates(&self, user: AccountId) -> UserAggregates {
+            self.user_aggregates.get(user).unwrap_or_default()
+        }
 
+        /// Portfólio paginado: posições vivas (ativas ou em unbonding) com
+        /// recompensas, datas de desbloqueio e prévia de penalidade
+        #[ink(message)]
+        pub fn get_user_portfolio(&self, user: AccountId, offset: u32, limit: u32) -> UserPortfolio {
+            let total_positions = self.balance_of(user);
+            let now = self.env().block_timestamp();
+            let boost_bps = if total_positions == 0 { 0 } else { self.fetch_user_boost(user) };
+
+            let end = offset.saturating_add(limit.min(50)).min(total_positions);
+            let positions = (offset..end)
+                .filter_map(|index| self.user_positions.get((user, index)))
+                .filter_map(|id| self.positions.get(id))
+                .filter_map(|position| self.portfolio_position(position, now))
+                .collect();
+
+            UserPortfolio {
+                aggregates: self.get_user_aggregates(user),
+                boost_bps,
+                total_positions,
+                positions,
+            }
+        }
 
         #[ink(message)]
         pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), StakingError> {
@@ -339,7 +1122,13 @@ mod fiapo_staking {
 
             // Fee Calculation
             let fee_result = self.calculate_entry_fee(amount);
-            let fee_deducted = amount.saturating_mul(fee_result.fee_bps as u128).saturating_div(10000);
+            let lusdt_fee = config.fee_asset == Asset::Lusdt && !is_for;
+            let fee_deducted = if lusdt_fee {
+                self.collect_lusdt_fee(user, fee_result.fee_lusdt, "EntryFeeLUSDT")?;
+                0
+            } else {
+                amount.saturating_mul(fee_result.fee_bps as u128).saturating_div(10000)
+            };
             let net_amount = amount.saturating_sub(fee_deducted);
 
             // Distribution Rule: 50% Team, 40% Staking (kept), 5% Rewards, 5% Noble
@@ -380,19 +1169,14 @@ mod fiapo_staking {
 
             self.positions.insert(position_id, &position);
 
-            let mut user_positions = self.user_positions.get(user).unwrap_or_default();
-            let is_new = user_positions.is_empty();
-            user_positions.push(position_id);
-            self.user_positions.insert(user, &user_positions);
+            self.add_user_position(user, position_id);
+            self.add_user_stake(user, pool_type, net_amount);
 
             self.next_position_id = self.next_position_id.saturating_add(1);
             self.total_staked_per_pool[pool as usize] = self.total_staked_per_pool[pool as usize].saturating_add(net_amount);
+            self.checkpoint_total_staked();
             self.active_positions = self.active_positions.saturating_add(1);
 
-            if is_new {
-                self.stakers_per_pool[pool as usize] = self.stakers_per_pool[pool as usize].saturating_add(1);
-            }
-
             // Old Affiliate Logic (Boosts)
             if let Some(affiliate_addr) = self.affiliate_contract {
                 let _ = self.call_affiliate_update_activity(affiliate_addr, user, amount);
@@ -411,6 +1195,7 @@ mod fiapo_staking {
 
         #[ink(message)]
         pub fn claim_rewards(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            self.ensure_not_emergency()?;
             let caller = self.env().caller();
             let current_time = self.env().block_timestamp();
 
@@ -427,132 +1212,757 @@ mod fiapo_staking {
             let config = self.pool_configs.get(position.pool_type.to_u8())
                 .ok_or(StakingError::PoolNotActive)?;
 
-            let apy_boost = self.fetch_user_boost(caller);
-            let rewards = self.calculate_rewards_with_boost(&position, &config, apy_boost);
+            // Claims só são liberados nas janelas de pagamento do pool
+            let boundary = self.last_claim_boundary(&position, &config, current_time);
+            if boundary <= position.last_reward_time {
+                return Err(StakingError::ClaimWindowClosed);
+            }
+
+            let apy_boost = self.fetch_user_boost_over(caller, position.last_reward_time, boundary);
+            let rewards = self.calculate_rewards_with_boost(&position, &config, apy_boost, boundary);
 
             if rewards == 0 {
                 return Err(StakingError::NoRewardsToClaim);
             }
 
+            // O restante após a janela continua acumulando para o próximo período
+            self.pay_rewards(&mut position, &config, rewards, boundary, apy_boost, false)
+        }
+
+        /// Paga ao dono atual as recompensas até `until` (em unidades do ativo do pool),
+        /// aplicando a taxa de saque de juros. Com `defer_shortfall`, o que a reserva de
+        /// um ativo externo não cobrir fica devido ao owner (`owed_rewards`) em vez de falhar.
+        fn pay_rewards(
+            &mut self,
+            position: &mut StakingPosition,
+            config: &PoolConfig,
+            rewards: Balance,
+            until: u64,
+            apy_boost: u32,
+            defer_shortfall: bool,
+        ) -> Result<Balance, StakingError> {
+            let user = position.user;
+            let asset = config.reward_asset;
+
             // Interest Withdrawal Fee Rule: 1% 
             // Distribution: 20% Burn, 50% Staking (kept), 30% Rewards
             let fee_amount = rewards.saturating_mul(1).saturating_div(100);
-            let net_rewards = rewards.saturating_sub(fee_amount);
-
-            if fee_amount > 0 {
+            let mut net_rewards = rewards.saturating_sub(fee_amount);
+
+            if asset != Asset::Fiapo {
+                // Ativos externos saem da reserva; a taxa permanece nela
+                let reserve = self.reward_reserves[asset.index()];
+                if reserve < net_rewards {
+                    if !defer_shortfall {
+                        return Err(StakingError::InsufficientRewardReserve);
+                    }
+                    let shortfall = net_rewards.saturating_sub(reserve);
+                    let owed = self.owed_rewards.get((user, asset)).unwrap_or(0);
+                    self.owed_rewards.insert((user, asset), &owed.saturating_add(shortfall));
+                    Self::env().emit_event(RewardShortfallRecorded {
+                        position_id: position.id,
+                        user,
+                        asset,
+                        amount: shortfall,
+                    });
+                    net_rewards = reserve;
+                }
+                self.reward_reserves[asset.index()] = reserve.saturating_sub(net_rewards);
+            } else if fee_amount > 0 {
                 let burn_part = fee_amount.saturating_mul(20).saturating_div(100);
                 let rewards_part = fee_amount.saturating_mul(30).saturating_div(100);
                 let staking_part = fee_amount.saturating_sub(burn_part).saturating_sub(rewards_part);
 
-                self.distribute_funds(fee_amount, 0, rewards_part, burn_part, staking_part, 0, None, caller, String::from("InterestFee"))?;
+                self.distribute_funds(fee_amount, 0, rewards_part, burn_part, staking_part, 0, None, user, String::from("InterestFee"))?;
             }
 
             position.accumulated_rewards = position.accumulated_rewards.saturating_add(net_rewards);
-            position.last_reward_time = current_time;
-            self.positions.insert(position_id, &position);
+            position.last_reward_time = until;
+            if self.settled_lock_bonus.get(position.id).is_some_and(|(since, _)| until >= since) {
+                self.settled_lock_bonus.remove(position.id);
+            }
+            self.positions.insert(position.id, position);
 
-            self.total_rewards_distributed = self.total_rewards_distributed.saturating_add(net_rewards);
-            self.call_core_transfer(caller, net_rewards)?;
+            if asset == Asset::Fiapo {
+                self.total_rewards_distributed = self.total_rewards_distributed.saturating_add(net_rewards);
+            }
+            self.rewards_distributed_per_asset[asset.index()] =
+                self.rewards_distributed_per_asset[asset.index()].saturating_add(net_rewards);
+            self.record_user_rewards(user, asset, net_rewards, 0);
+            if net_rewards > 0 {
+                self.transfer_asset(asset, user, net_rewards)?;
+            }
 
             Self::env().emit_event(RewardsClaimed {
-                position_id,
-                user: caller,
+                position_id: position.id,
+                user,
                 amount_net: net_rewards,
                 fee_amount,
                 boost_bps: apy_boost,
+                asset,
             });
 
             Ok(net_rewards)
         }
 
+        // ==================== Locks (veFIAPO) ====================
+
         #[ink(message)]
-        pub fn unstake(&mut self, position_id: u64) -> Result<Balance, StakingError> {
-            let caller = self.env().caller();
-            let current_time = self.env().block_timestamp();
+        pub fn get_lock(&self, position_id: u64) -> Option<PositionLock> {
+            self.position_locks.get(position_id)
+        }
 
-            let mut position = self.positions.get(position_id)
-                .ok_or(StakingError::PositionNotFound)?;
+        #[ink(message)]
+        pub fn get_lock_tiers(&self) -> Vec<LockTier> {
+            self.lock_tiers.clone()
+        }
 
+        /// Trava a posição por `duration_days` a partir de agora. Sobre um lock ativo,
+        /// apenas estende: o novo fim precisa ser posterior ao atual.
+        #[ink(message)]
+        pub fn lock_position(&mut self, position_id: u64, duration_days: u32) -> Result<u64, StakingError> {
+            self.ensure_not_emergency()?;
+            let caller = self.env().caller();
+            let now = self.env().block_timestamp();
+
+            let position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
             if position.user != caller {
                 return Err(StakingError::NotPositionOwner);
             }
             if position.status != PositionStatus::Active {
                 return Err(StakingError::PositionNotActive);
             }
+            if duration_days > MAX_LOCK_DAYS {
+                return Err(StakingError::InvalidLockDuration);
+            }
 
-            let config = self.pool_configs.get(position.pool_type.to_u8())
-                .ok_or(StakingError::PoolNotActive)?;
+            let end = now.saturating_add((duration_days as u64).saturating_mul(SECONDS_PER_DAY));
+            let current_lock = self.position_locks.get(position_id).filter(|lock| now < lock.end);
+            let start = match current_lock.as_ref() {
+                Some(lock) => {
+                    if end <= lock.end {
+                        return Err(StakingError::LockCannotShorten);
+                    }
+                    lock.start
+                }
+                None => now,
+            };
+
+            // Faixa definida pela duração total do lock
+            let total_days = (end.saturating_sub(start) / SECONDS_PER_DAY) as u32;
+            let tier = self.lock_tiers.iter()
+                .rev()
+                .find(|tier| total_days >= tier.min_days)
+                .cloned()
+                .ok_or(StakingError::InvalidLockDuration)?;
+
+            // O multiplicador novo não retroage: o bônus até agora fica liquidado com o anterior
+            if let Some(lock) = current_lock {
+                let config = self.pool_configs.get(position.pool_type.to_u8())
+                    .ok_or(StakingError::PoolNotActive)?;
+                let boost = self.fetch_user_boost_over(caller, position.last_reward_time, now);
+                let total_apy = (config.apy_bps as u128).saturating_add(boost as u128);
+                let bonus = self.accrued_lock_bonus(&position, &lock, total_apy, now);
+                self.settled_lock_bonus.insert(position_id, &(now, bonus));
+            }
+
+            let lock = PositionLock {
+                start,
+                end,
+                apy_multiplier_bps: tier.apy_multiplier_bps,
+                voting_multiplier_bps: tier.voting_multiplier_bps,
+            };
+            self.position_locks.insert(position_id, &lock);
+            self.checkpoint_locks(caller);
+
+            Self::env().emit_event(LockUpdated {
+                position_id,
+                user: caller,
+                start,
+                end,
+                apy_multiplier_bps: lock.apy_multiplier_bps,
+                voting_multiplier_bps: lock.voting_multiplier_bps,
+            });
+
+            Ok(end)
+        }
+
+        /// Atualiza a tabela de multiplicadores de lock (owner ou governança).
+        /// Locks existentes mantêm os multiplicadores com que foram criados.
+        #[ink(message)]
+        pub fn set_lock_tiers(&mut self, tiers: Vec<LockTier>) -> Result<(), StakingError> {
+            self.ensure_owner_or_governance()?;
+            let ordered = tiers.windows(2).all(|pair| pair[0].min_days < pair[1].min_days);
+            if tiers.is_empty()
+                || tiers.len() > MAX_LOCK_TIERS
+                || !ordered
+                || tiers[0].min_days == 0
+                || tiers.iter().any(|tier| tier.min_days > MAX_LOCK_DAYS || tier.apy_multiplier_bps < 10000)
+            {
+                return Err(StakingError::InvalidPoolConfig);
+            }
+            self.lock_tiers = tiers;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_governance_contract(&mut self, governance: Option<AccountId>) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.governance_contract = governance;
+            Ok(())
+        }
+
+        /// Owner, governança ou o Timelock (de `set_emergency_authorities`) que executa
+        /// as propostas da governança
+        fn ensure_owner_or_governance(&self) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            if caller != self.owner
+                && Some(caller) != self.governance_contract
+                && Some(caller) != self.timelock_contract
+            {
+                return Err(StakingError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        /// Poder de voto de `amount` travado em `lock`, no instante `at`
+        fn lock_voting_power(amount: Balance, lock: &PositionLock, at: u64) -> Balance {
+            if at < lock.start || at >= lock.end {
+                return 0;
+            }
+            let remaining = lock.end.saturating_sub(at) as u128;
+            let duration = lock.end.saturating_sub(lock.start) as u128;
+            amount
+                .saturating_mul(lock.voting_multiplier_bps as u128)
+                .checked_div(10000).unwrap_or(0)
+                .saturating_mul(remaining)
+                .checked_div(duration).unwrap_or(0)
+        }
+
+        /// Grava os locks vigentes das posições ativas do usuário no instante atual
+        /// (sobrescreve se já houver checkpoint no mesmo instante)
+        fn checkpoint_locks(&mut self, user: AccountId) {
+            let now = self.env().block_timestamp();
+            let locks: Vec<(Balance, PositionLock)> = self.user_position_ids(user)
+                .into_iter()
+                .filter_map(|id| self.positions.get(id))
+                .filter(|position| position.user == user && position.status == PositionStatus::Active)
+                .filter_map(|position| {
+                    self.position_locks.get(position.id)
+                        .filter(|lock| now < lock.end)
+                        .map(|lock| (position.amount, lock))
+                })
+                .collect();
+
+            let count = self.lock_checkpoint_counts.get(user).unwrap_or(0);
+            if count == 0 && locks.is_empty() {
+                return;
+            }
+            if count > 0 {
+                let last = count.saturating_sub(1);
+                if let Some((last_time, _)) = self.lock_checkpoints.get((user, last)) {
+                    if last_time == now {
+                        self.lock_checkpoints.insert((user, last), &(now, locks));
+                        return;
+                    }
+                }
+            }
+            self.lock_checkpoints.insert((user, count), &(now, locks));
+            self.lock_checkpoint_counts.insert(user, &count.saturating_add(1));
+        }
+
+        /// Busca binária pelo último checkpoint de locks com instante <= `at`
+        fn lock_checkpoint_at(&self, user: AccountId, at: u64) -> Vec<(Balance, PositionLock)> {
+            let (mut low, mut high) = (0u32, self.lock_checkpoint_counts.get(user).unwrap_or(0));
+            while low < high {
+                let mid = low.saturating_add(high.saturating_sub(low) / 2);
+                match self.lock_checkpoints.get((user, mid)) {
+                    Some((checkpoint_time, _)) if checkpoint_time <= at => low = mid.saturating_add(1),
+                    _ => high = mid,
+                }
+            }
+            if low == 0 {
+                return Vec::new();
+            }
+            self.lock_checkpoints.get((user, low.saturating_sub(1)))
+                .map(|(_, locks)| locks)
+                .unwrap_or_default()
+        }
 
-            let boost = self.fetch_user_boost(caller);
-            let pending = self.calculate_rewards_with_boost(&position, &config, boost);
-            let total_rewards = position.accumulated_rewards.saturating_add(pending);
-
-            let days_staked = (current_time.saturating_sub(position.start_time)) / SECONDS_PER_DAY;
-            let is_early = days_staked < config.min_period_days as u64;
-
-            let (penalty, rewards_penalty) = if is_early {
-                match position.pool_type {
-                    PoolType::DonBurn => {
-                        // 10 USDT + 50% capital + 80% interest
-                        let capital_penalty = position.amount.saturating_div(2); 
-                        let interest_penalty = total_rewards.saturating_mul(80).saturating_div(100); 
-                        // Note: 10 USDT fixed part is ignored for simplicity in FIAPO-only version
-                        (capital_penalty, interest_penalty)
+        // ==================== Position NFT (PSP34) ====================
+
+        #[ink(message)]
+        pub fn owner_of(&self, position_id: u64) -> Option<AccountId> {
+            self.positions.get(position_id).map(|position| position.user)
+        }
+
+        /// Quantidade de posições de um owner
+        #[ink(message)]
+        pub fn balance_of(&self, owner: AccountId) -> u32 {
+            self.user_position_count.get(owner).unwrap_or(0)
+        }
+
+        /// Enumeração de posições por owner
+        #[ink(message)]
+        pub fn owners_token_by_index(&self, owner: AccountId, index: u32) -> Option<u64> {
+            self.user_positions.get((owner, index))
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, operator: AccountId, position_id: Option<u64>) -> bool {
+            if self.operator_approvals.get((owner, operator)).unwrap_or(false) {
+                return true;
+            }
+            match position_id {
+                Some(id) => self.owner_of(id) == Some(owner)
+                    && self.position_approvals.get(id) == Some(operator),
+                None => false,
+            }
+        }
+
+        /// Aprova um operador para uma posição (`Some(id)`) ou para todas (`None`)
+        #[ink(message)]
+        pub fn approve(&mut self, operator: AccountId, position_id: Option<u64>, approved: bool) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            if operator == caller {
+                return Err(StakingError::SelfTransfer);
+            }
+
+            match position_id {
+                Some(id) => {
+                    let position = self.positions.get(id).ok_or(StakingError::PositionNotFound)?;
+                    if position.user != caller {
+                        return Err(StakingError::NotPositionOwner);
                     }
-                    PoolType::DonLunes | PoolType::DonFiapo => {
-                        let penalty = position.amount
-                            .saturating_mul(config.early_withdrawal_penalty_bps as u128)
-                            .saturating_div(10000);
-                        (penalty, 0)
+                    if approved {
+                        self.position_approvals.insert(id, &operator);
+                    } else {
+                        self.position_approvals.remove(id);
                     }
                 }
+                None => {
+                    self.operator_approvals.insert((caller, operator), &approved);
+                }
+            }
+
+            Self::env().emit_event(PositionApproval {
+                owner: caller,
+                operator,
+                position_id,
+                approved,
+            });
+
+            Ok(())
+        }
+
+        /// Transfere uma posição ativa. As recompensas das janelas de pagamento já
+        /// fechadas são pagas ao owner atual (o que faltar na reserva fica devido a ele);
+        /// o período em curso segue com a posição.
+        #[ink(message)]
+        pub fn transfer_position(&mut self, to: AccountId, position_id: u64) -> Result<Balance, StakingError> {
+            let caller = self.env().caller();
+            let position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+
+            if position.user != caller && !self.allowance(position.user, caller, Some(position_id)) {
+                return Err(StakingError::NotApproved);
+            }
+
+            self.transfer_position_internal(position.user, to, position_id)
+        }
+
+        /// Transferência via marketplace (venda). Retorna as recompensas pagas ao seller.
+        #[ink(message)]
+        pub fn marketplace_transfer_position(&mut self, from: AccountId, to: AccountId, position_id: u64) -> Result<Balance, StakingError> {
+            if Some(self.env().caller()) != self.marketplace_contract {
+                return Err(StakingError::Unauthorized);
+            }
+            self.transfer_position_internal(from, to, position_id)
+        }
+
+        fn transfer_position_internal(&mut self, from: AccountId, to: AccountId, position_id: u64) -> Result<Balance, StakingError> {
+            self.ensure_not_emergency()?;
+            if from == to {
+                return Err(StakingError::SelfTransfer);
+            }
+
+            let mut position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+            if position.user != from {
+                return Err(StakingError::NotPositionOwner);
+            }
+            if position.status != PositionStatus::Active {
+                return Err(StakingError::PositionNotActive);
+            }
+
+            // Auto-claim para o seller só até a última janela de pagamento, como no
+            // claim: transferir não pode antecipar o período em curso
+            let config = self.pool_configs.get(position.pool_type.to_u8())
+                .ok_or(StakingError::PoolNotActive)?;
+            let boundary = self.last_claim_boundary(&position, &config, self.env().block_timestamp());
+            let boost = self.fetch_user_boost_over(from, position.last_reward_time, boundary);
+            let pending = self.calculate_rewards_with_boost(&position, &config, boost, boundary);
+            let settled = if pending > 0 {
+                self.pay_rewards(&mut position, &config, pending, boundary, boost, true)?
             } else {
-                (0, 0)
+                0
             };
 
-            // Distribution logic for Penalties
-            if penalty > 0 || rewards_penalty > 0 {
-                let total_p = penalty.saturating_add(rewards_penalty);
-                match position.pool_type {
-                    PoolType::DonBurn => {
-                        // 20% Burn, 50% Staking, 30% Rewards
-                        let burn_part = total_p.saturating_mul(20).saturating_div(100);
-                        let rewards_part = total_p.saturating_mul(30).saturating_div(100);
-                        let staking_part = total_p.saturating_sub(burn_part).saturating_sub(rewards_part);
-                        self.distribute_funds(total_p, 0, rewards_part, burn_part, staking_part, 0, None, caller, String::from("BurnPenalty"))?;
-                    }
-                    PoolType::DonLunes | PoolType::DonFiapo => {
-                        // 10% Team, 50% Staking, 40% Rewards
-                        let team_part = total_p.saturating_mul(10).saturating_div(100);
-                        let rewards_part = total_p.saturating_mul(40).saturating_div(100);
-                        let staking_part = total_p.saturating_sub(team_part).saturating_sub(rewards_part);
-                        self.distribute_funds(total_p, team_part, rewards_part, 0, staking_part, 0, None, caller, String::from("UnstakePenalty"))?;
+            position.user = to;
+            self.positions.insert(position_id, &position);
+            self.position_approvals.remove(position_id);
+
+            self.remove_user_stake(from, position.pool_type, position.amount);
+            self.add_user_stake(to, position.pool_type, position.amount);
+            self.release_user_position(from, position_id);
+            self.add_user_position(to, position_id);
+            self.checkpoint_locks(from);
+            self.checkpoint_locks(to);
+
+            Self::env().emit_event(PositionTransfer {
+                from: Some(from),
+                to: Some(to),
+                position_id,
+            });
+
+            Ok(settled)
+        }
+
+        /// Mantido por compatibilidade. Antes pagava na hora; agora inicia o unbonding
+        /// (retorna o valor na fila) e só conclui o saque na mesma chamada quando o
+        /// pool não tem cooldown (`unbonding_days = 0`).
+        #[ink(message)]
+        pub fn unstake(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            let queued = self.request_unstake(position_id)?;
+            match self.unbonding.get(position_id) {
+                Some(request) if request.release_at <= self.env().block_timestamp() => {
+                    self.withdraw_unbonded(position_id)
+                }
+                _ => Ok(queued),
+            }
+        }
+
+        /// Inicia o saque: encerra o accrual, aplica penalidades e coloca o valor
+        /// líquido na fila de unbonding até o fim do cooldown do pool
+        #[ink(message)]
+        pub fn request_unstake(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            self.ensure_not_emergency()?;
+            let caller = self.env().caller();
+            let current_time = self.env().block_timestamp();
+
+            let position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+
+            if position.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+            if position.status != PositionStatus::Active {
+                return Err(StakingError::PositionNotActive);
+            }
+
+            let config = self.pool_configs.get(position.pool_type.to_u8())
+                .ok_or(StakingError::PoolNotActive)?;
+
+            if self.position_locks.get(position_id).is_some_and(|lock| current_time < lock.end) {
+                return Err(StakingError::PositionLocked);
+            }
+            if self.fixed_exit_fee_cents(&position, &config, current_time) > 0
+                && !self.exit_fee_paid.get(position_id).unwrap_or(false)
+            {
+                return Err(StakingError::ExitFeeNotSettled);
+            }
+
+            let (net_amount, fiapo_rewards, reward_amount, penalty) = self.settle_exit(position_id, &config, current_time)?;
+
+            let release_at = current_time.saturating_add(
+                (config.unbonding_days as u64).saturating_mul(SECONDS_PER_DAY)
+            );
+            self.enqueue_unbonding(UnbondingRequest {
+                position_id,
+                user: caller,
+                pool_type: position.pool_type,
+                amount: net_amount,
+                fiapo_rewards,
+                penalty,
+                requested_at: current_time,
+                release_at,
+                reward_asset: config.reward_asset,
+                reward_amount,
+            });
+
+            Self::env().emit_event(UnstakeRequested {
+                position_id,
+                user: caller,
+                amount: net_amount,
+                penalty,
+                release_at,
+            });
+
+            Ok(net_amount)
+        }
+
+        /// Conclui o saque após o cooldown
+        #[ink(message)]
+        pub fn withdraw_unbonded(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            let caller = self.env().caller();
+            let request = self.unbonding.get(position_id)
+                .ok_or(StakingError::UnbondingNotFound)?;
+
+            if request.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+            if self.env().block_timestamp() < request.release_at {
+                return Err(StakingError::UnbondingNotReady);
+            }
+
+            self.complete_unbonding(&request, request.amount, request.penalty)
+        }
+
+        /// Paga em LUSDT (PSP22, requer allowance) a parte fixa da penalidade Don Burn
+        #[ink(message)]
+        pub fn pay_exit_fee_lusdt(&mut self, position_id: u64) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            let cents = self.ensure_exit_fee_due(caller, position_id)?;
+            let amount_lusdt = (cents as u128).saturating_mul(LUSDT_SCALE).saturating_div(100);
+
+            self.collect_lusdt_fee(caller, amount_lusdt, "BurnExitFeeLUSDT")?;
+            self.exit_fee_paid.insert(position_id, &true);
+
+            Self::env().emit_event(ExitFeePaid {
+                position_id,
+                user: caller,
+                amount_usdt_cents: cents,
+                amount_lusdt,
+                tx_hash: None,
+            });
+
+            Ok(())
+        }
+
+        /// Registra a parte fixa da penalidade Don Burn paga em USDT e confirmada pelo oracle
+        #[ink(message)]
+        pub fn pay_exit_fee_oracle(&mut self, position_id: u64, usdt_tx_hash: String) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            let cents = self.ensure_exit_fee_due(caller, position_id)?;
+
+            self.verify_oracle_usdt(usdt_tx_hash.clone(), caller, cents, position_id)?;
+            self.exit_fee_paid.insert(position_id, &true);
+
+            Self::env().emit_event(ExitFeePaid {
+                position_id,
+                user: caller,
+                amount_usdt_cents: cents,
+                amount_lusdt: 0,
+                tx_hash: Some(usdt_tx_hash),
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn burn_exit_fee_usdt_cents(&self) -> u64 {
+            self.burn_exit_fee_usdt_cents
+        }
+
+        #[ink(message)]
+        pub fn set_burn_exit_fee(&mut self, usdt_cents: u64) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.burn_exit_fee_usdt_cents = usdt_cents;
+            Ok(())
+        }
+
+        /// Saída instantânea: paga a taxa extra do pool e dispensa o cooldown.
+        /// Aceita posições ativas ou já na fila de unbonding.
+        #[ink(message)]
+        pub fn instant_unstake(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            self.ensure_not_emergency()?;
+            let caller = self.env().caller();
+            let position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+
+            if position.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+            if position.status == PositionStatus::Active {
+                self.request_unstake(position_id)?;
+            } else if position.status != PositionStatus::Unbonding {
+                return Err(StakingError::PositionNotActive);
+            }
+
+            let request = self.unbonding.get(position_id)
+                .ok_or(StakingError::UnbondingNotFound)?;
+            let config = self.pool_configs.get(position.pool_type.to_u8())
+                .ok_or(StakingError::PoolNotActive)?;
+
+            let exit_fee = request.amount
+                .saturating_mul(config.instant_exit_fee_bps as u128)
+                .saturating_div(10000);
+            if exit_fee > 0 {
+                // Mesma divisão das penalidades de saque: 10% Team, 40% Rewards, 50% Staking
+                let team_part = exit_fee.saturating_mul(10).saturating_div(100);
+                let rewards_part = exit_fee.saturating_mul(40).saturating_div(100);
+                let staking_part = exit_fee.saturating_sub(team_part).saturating_sub(rewards_part);
+                self.distribute_funds(exit_fee, team_part, rewards_part, 0, staking_part, 0, None, caller, String::from("InstantExitFee"))?;
+            }
+
+            let net_amount = request.amount.saturating_sub(exit_fee);
+            self.record_user_rewards(caller, Asset::Fiapo, 0, exit_fee);
+            self.complete_unbonding(&request, net_amount, request.penalty.saturating_add(exit_fee))
+        }
+
+        fn complete_unbonding(&mut self, request: &UnbondingRequest, payout: Balance, penalty: Balance) -> Result<Balance, StakingError> {
+            let position_id = request.position_id;
+            self.dequeue_unbonding(request);
+
+            if let Some(mut position) = self.positions.get(position_id) {
+                position.status = PositionStatus::Completed;
+                self.positions.insert(position_id, &position);
+            }
+            self.release_user_position(request.user, position_id);
+
+            self.call_core_transfer(request.user, payout)?;
+            if request.reward_amount > 0 {
+                self.transfer_asset(request.reward_asset, request.user, request.reward_amount)?;
+                let index = request.reward_asset.index();
+                self.rewards_distributed_per_asset[index] =
+                    self.rewards_distributed_per_asset[index].saturating_add(request.reward_amount);
+            }
+
+            Self::env().emit_event(Unstaked {
+                position_id,
+                user: request.user,
+                amount: payout,
+                penalty,
+            });
+
+            Ok(payout)
+        }
+
+        fn release_day(release_at: u64) -> u64 {
+            release_at.saturating_add(SECONDS_PER_DAY - 1) / SECONDS_PER_DAY
+        }
+
+        /// Registra o saque na fila, no bucket do dia de liberação e nos totais do pool
+        fn enqueue_unbonding(&mut self, request: UnbondingRequest) {
+            let index = self.unbonding_queue_len;
+            self.unbonding_queue.insert(index, &request.position_id);
+            self.unbonding_queue_index.insert(request.position_id, &index);
+            self.unbonding_queue_len = index.saturating_add(1);
+
+            let day = Self::release_day(request.release_at);
+            let bucket = self.unbonding_buckets.get(day).unwrap_or(0);
+            self.unbonding_buckets.insert(day, &bucket.saturating_add(request.amount));
+            self.unbonding_last_day = self.unbonding_last_day.max(day);
+
+            let pool = request.pool_type.to_u8() as usize;
+            self.unbonding_per_pool[pool] = self.unbonding_per_pool[pool].saturating_add(request.amount);
+            self.unbonding_fiapo_rewards = self.unbonding_fiapo_rewards.saturating_add(request.fiapo_rewards);
+            self.unbonding.insert(request.position_id, &request);
+        }
+
+        /// Remove o saque da fila (troca pelo último) e desconta bucket e totais
+        fn dequeue_unbonding(&mut self, request: &UnbondingRequest) {
+            let position_id = request.position_id;
+            if let Some(index) = self.unbonding_queue_index.get(position_id) {
+                let last = self.unbonding_queue_len.saturating_sub(1);
+                if index != last {
+                    if let Some(moved) = self.unbonding_queue.get(last) {
+                        self.unbonding_queue.insert(index, &moved);
+                        self.unbonding_queue_index.insert(moved, &index);
                     }
                 }
+                self.unbonding_queue.remove(last);
+                self.unbonding_queue_index.remove(position_id);
+                self.unbonding_queue_len = last;
+            }
+
+            let day = Self::release_day(request.release_at);
+            let bucket = self.unbonding_buckets.get(day).unwrap_or(0).saturating_sub(request.amount);
+            if bucket == 0 {
+                self.unbonding_buckets.remove(day);
+            } else {
+                self.unbonding_buckets.insert(day, &bucket);
+            }
+
+            let pool = request.pool_type.to_u8() as usize;
+            self.unbonding_per_pool[pool] = self.unbonding_per_pool[pool].saturating_sub(request.amount);
+            self.unbonding_fiapo_rewards = self.unbonding_fiapo_rewards.saturating_sub(request.fiapo_rewards);
+            self.unbonding.remove(position_id);
+        }
+
+        /// Encerra o accrual da posição, aplica e distribui as penalidades de saída
+        /// antecipada. Retorna (valor líquido em FIAPO, recompensas em FIAPO incluídas nele,
+        /// recompensas em ativo externo, penalidade total).
+        fn settle_exit(&mut self, position_id: u64, config: &PoolConfig, current_time: u64) -> Result<(Balance, Balance, Balance, Balance), StakingError> {
+            let mut position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+            let caller = position.user;
+
+            // `accumulated_rewards` já foi pago nos claims; apenas o pendente é liquidado
+            let boost = self.fetch_user_boost_over(caller, position.last_reward_time, current_time);
+            let total_rewards = self.calculate_rewards_with_boost(&position, config, boost, current_time);
+            let (penalty, rewards_penalty) = self.exit_penalty(&position, config, total_rewards, current_time);
+
+            // Penalidade sobre recompensas em ativo externo permanece na reserva
+            let external_rewards = config.reward_asset != Asset::Fiapo;
+            let distributable_rewards_penalty = if external_rewards { 0 } else { rewards_penalty };
+
+            if penalty > 0 || distributable_rewards_penalty > 0 {
+                let total_p = penalty.saturating_add(distributable_rewards_penalty);
+                let split = Self::penalty_split(position.pool_type, total_p);
+                let reason = match position.pool_type {
+                    PoolType::DonBurn => "BurnPenalty",
+                    PoolType::DonLunes | PoolType::DonFiapo => "UnstakePenalty",
+                };
+                self.distribute_funds(total_p, split.team, split.rewards_fund, split.burn, split.staking, 0, None, caller, String::from(reason))?;
             }
 
             let net_rewards = total_rewards.saturating_sub(rewards_penalty);
             let net_principal = position.amount.saturating_sub(penalty);
-            let net_amount = net_principal.saturating_add(net_rewards);
+            let (net_amount, fiapo_rewards, reward_amount) = if external_rewards {
+                // Reserva o que houver disponível; o principal nunca fica bloqueado pela reserva
+                let index = config.reward_asset.index();
+                let reserved = net_rewards.min(self.reward_reserves[index]);
+                self.reward_reserves[index] = self.reward_reserves[index].saturating_sub(reserved);
+                // O que faltar fica devido e pode ser sacado quando a reserva for reposta
+                let shortfall = net_rewards.saturating_sub(reserved);
+                if shortfall > 0 {
+                    self.reward_shortfalls.insert(position_id, &(config.reward_asset, shortfall));
+                    Self::env().emit_event(RewardShortfallRecorded {
+                        position_id,
+                        user: caller,
+                        asset: config.reward_asset,
+                        amount: shortfall,
+                    });
+                }
+                (net_principal, 0, reserved)
+            } else {
+                (net_principal.saturating_add(net_rewards), net_rewards, 0)
+            };
 
-            position.status = PositionStatus::Completed;
+            position.status = PositionStatus::Unbonding;
             position.accumulated_rewards = 0;
+            position.last_reward_time = current_time;
+            self.settled_lock_bonus.remove(position_id);
             self.positions.insert(position_id, &position);
 
             let pool = position.pool_type.to_u8() as usize;
             self.total_staked_per_pool[pool] = self.total_staked_per_pool[pool].saturating_sub(position.amount);
             self.active_positions = self.active_positions.saturating_sub(1);
+            self.checkpoint_total_staked();
 
-            self.call_core_transfer(caller, net_amount)?;
+            let total_penalty = penalty.saturating_add(rewards_penalty);
+            let settled_rewards = if external_rewards { reward_amount } else { net_rewards };
+            self.remove_user_stake(caller, position.pool_type, position.amount);
+            self.checkpoint_locks(caller);
+            self.record_user_rewards(caller, config.reward_asset, settled_rewards, total_penalty);
 
-            Self::env().emit_event(Unstaked {
-                position_id,
-                user: caller,
-                amount: net_amount,
-                penalty: penalty.saturating_add(rewards_penalty),
-            });
-
-            Ok(net_amount)
+            Ok((net_amount, fiapo_rewards, reward_amount, total_penalty))
         }
 
         // ==================== Distribution Logic ====================
@@ -582,73 +1992,633 @@ mod fiapo_staking {
                         distributed = true;
                     }
                 }
-                if !distributed {
-                     // Fallback to Team
-                     team_part = team_part.saturating_add(noble_part);
+                if !distributed {
+                     // Fallback to Team
+                     team_part = team_part.saturating_add(noble_part);
+                }
+            }
+
+            if team_part > 0 {
+                if let Some(team) = self.team_wallet {
+                    let _ = self.call_core_transfer(team, team_part);
+                }
+            }
+
+            if rewards_part > 0 {
+                if let Some(rewards_addr) = self.rewards_contract {
+                    let _ = self.call_core_transfer(rewards_addr, rewards_part);
+                    let _ = self.call_rewards_add_fund(rewards_addr, rewards_part);
+                }
+            }
+
+            if burn_part > 0 {
+                if let Some(burn_addr) = self.burn_wallet {
+                    let _ = self.call_core_transfer(burn_addr, burn_part);
+                }
+            }
+
+            // Staking part remains in contract (implicitly added to balance for backing APY coverage)
+            
+            Self::env().emit_event(FeeDistributed {
+                reason,
+                amount: total,
+                team_part,
+                rewards_part,
+                burn_part,
+                staking_part,
+                noble_part,
+            });
+
+            Ok(())
+        }
+
+        // ==================== Admin / Setters ====================
+
+        #[ink(message)]
+        pub fn set_linked_contracts(
+            &mut self,
+            oracle: Option<AccountId>,
+            affiliate: Option<AccountId>,
+            rewards: Option<AccountId>,
+            noble: Option<AccountId>, // New
+            team: Option<AccountId>,
+            burn: Option<AccountId>
+        ) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.oracle_contract = oracle;
+            self.affiliate_contract = affiliate;
+            self.rewards_contract = rewards;
+            self.noble_contract = noble;
+            self.team_wallet = team;
+            self.burn_wallet = burn;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_lusdt_contract(&mut self, lusdt: Option<AccountId>) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.lusdt_contract = lusdt;
+            Ok(())
+        }
+
+        /// Deposita recompensas na reserva de um ativo externo.
+        /// LUNES: enviar o valor nativo na transação; LUSDT: requer approve prévio
+        /// (LUNES enviado junto é rejeitado). Recompensas em FIAPO não passam pela
+        /// reserva e são rejeitadas.
+        #[ink(message, payable)]
+        pub fn fund_rewards(&mut self, asset: Asset, amount: Balance) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            if asset != Asset::Lunes && self.env().transferred_value() > 0 {
+                return Err(StakingError::InvalidAmount);
+            }
+            let amount = match asset {
+                Asset::Lunes => self.env().transferred_value(),
+                Asset::Fiapo => return Err(StakingError::UnsupportedRewardAsset),
+                Asset::Lusdt => {
+                    self.call_lusdt_transfer_from(caller, self.env().account_id(), amount)?;
+                    amount
+                }
+            };
+            if amount == 0 {
+                return Err(StakingError::InvalidAmount);
+            }
+
+            self.reward_reserves[asset.index()] = self.reward_reserves[asset.index()].saturating_add(amount);
+
+            Self::env().emit_event(RewardsFunded {
+                funder: caller,
+                asset,
+                amount,
+            });
+
+            Ok(())
+        }
+
+        /// Recompensas em `asset` devidas a `account` por transferências de posições
+        #[ink(message)]
+        pub fn get_owed_rewards(&self, account: AccountId, asset: Asset) -> Balance {
+            self.owed_rewards.get((account, asset)).unwrap_or(0)
+        }
+
+        /// Saca recompensas devidas por transferências de posições, até o saldo da reserva
+        #[ink(message)]
+        pub fn claim_owed_rewards(&mut self, asset: Asset) -> Result<Balance, StakingError> {
+            let caller = self.env().caller();
+            let owed = self.owed_rewards.get((caller, asset)).ok_or(StakingError::NoRewardsToClaim)?;
+            let index = asset.index();
+            let paid = owed.min(self.reward_reserves[index]);
+            if paid == 0 {
+                return Err(StakingError::InsufficientRewardReserve);
+            }
+
+            self.reward_reserves[index] = self.reward_reserves[index].saturating_sub(paid);
+            let remaining = owed.saturating_sub(paid);
+            if remaining > 0 {
+                self.owed_rewards.insert((caller, asset), &remaining);
+            } else {
+                self.owed_rewards.remove((caller, asset));
+            }
+            self.rewards_distributed_per_asset[index] = self.rewards_distributed_per_asset[index].saturating_add(paid);
+            self.record_user_rewards(caller, asset, paid, 0);
+            self.transfer_asset(asset, caller, paid)?;
+            Ok(paid)
+        }
+
+        /// Saca recompensas devidas de uma posição encerrada, até o saldo da reserva
+        #[ink(message)]
+        pub fn claim_reward_shortfall(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            let caller = self.env().caller();
+            let position = self.positions.get(position_id).ok_or(StakingError::PositionNotFound)?;
+            if position.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+            let (asset, owed) = self.reward_shortfalls.get(position_id).ok_or(StakingError::NoRewardsToClaim)?;
+            let index = asset.index();
+            let paid = owed.min(self.reward_reserves[index]);
+            if paid == 0 {
+                return Err(StakingError::InsufficientRewardReserve);
+            }
+
+            self.reward_reserves[index] = self.reward_reserves[index].saturating_sub(paid);
+            let remaining = owed.saturating_sub(paid);
+            if remaining > 0 {
+                self.reward_shortfalls.insert(position_id, &(asset, remaining));
+            } else {
+                self.reward_shortfalls.remove(position_id);
+            }
+            self.rewards_distributed_per_asset[index] = self.rewards_distributed_per_asset[index].saturating_add(paid);
+            self.record_user_rewards(caller, asset, paid, 0);
+            self.transfer_asset(asset, caller, paid)?;
+            Ok(paid)
+        }
+
+        #[ink(message)]
+        pub fn withdraw_reward_reserve(&mut self, asset: Asset, amount: Balance, to: AccountId) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            let reserve = self.reward_reserves[asset.index()];
+            if amount == 0 || amount > reserve {
+                return Err(StakingError::InsufficientRewardReserve);
+            }
+
+            self.reward_reserves[asset.index()] = reserve.saturating_sub(amount);
+            self.transfer_asset(asset, to, amount)?;
+
+            Self::env().emit_event(RewardReserveWithdrawn { to, asset, amount });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_ico_contract(&mut self, ico: Option<AccountId>) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.ico_contract = ico;
+            Ok(())
+        }
+
+        /// NFTs ativos de `user` considerados no boost
+        #[ink(message)]
+        pub fn get_nft_holdings(&self, user: AccountId) -> Vec<NftHolding> {
+            self.nft_holdings.get(user).unwrap_or_default()
+        }
+
+        /// Atualiza os NFTs ativos de `user`. Apenas o ICO, a cada mint, transferência,
+        /// evolução ou revelação de raridade.
+        #[ink(message)]
+        pub fn sync_nft_holdings(&mut self, user: AccountId, holdings: Vec<NftHolding>) -> Result<(), StakingError> {
+            if Some(self.env().caller()) != self.ico_contract {
+                return Err(StakingError::Unauthorized);
+            }
+            self.store_nft_holdings(user, holdings);
+            Ok(())
+        }
+
+        /// Relê do ICO os NFTs de `user` (NFTs anteriores à integração ou correção
+        /// manual). Qualquer conta pode chamar; retorna o boost de NFT resultante.
+        #[ink(message)]
+        pub fn refresh_nft_boost(&mut self, user: AccountId) -> Result<u32, StakingError> {
+            let ico = self.ico_contract.ok_or(StakingError::AssetNotConfigured)?;
+            let holdings: Vec<NftHolding> = IcoCall::get_user_nfts(ico, user)
+                .into_iter()
+                .take(MAX_NFT_BOOST_SCAN)
+                .filter_map(|id| IcoCall::get_nft(ico, id))
+                .filter(|nft| nft.active && nft.owner == user)
+                .map(|nft| NftHolding { nft_id: nft.id, tier: nft.tier, visual_rarity: nft.visual_rarity })
+                .collect();
+            self.store_nft_holdings(user, holdings);
+            Ok(self.fetch_nft_boost(user).0)
+        }
+
+        fn store_nft_holdings(&mut self, user: AccountId, mut holdings: Vec<NftHolding>) {
+            holdings.truncate(MAX_NFT_HOLDINGS);
+            if holdings.is_empty() {
+                self.nft_holdings.remove(user);
+            } else {
+                self.nft_holdings.insert(user, &holdings);
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_nft_boost_config(&mut self, config: NftBoostConfig) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            if config.max_nfts_counted == 0
+                || config.max_nfts_counted > MAX_NFTS_COUNTED
+                || config.max_boost_bps > MAX_NFT_BOOST_BPS
+                || config.tier_boost_bps.iter().any(|&bps| bps > config.max_boost_bps)
+                || config.rarity_multiplier_bps.iter().any(|&bps| !(10000..=MAX_RARITY_MULTIPLIER_BPS).contains(&bps))
+            {
+                return Err(StakingError::InvalidPoolConfig);
+            }
+            self.nft_boost_config = config;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_boost_registry(&mut self, registry: Option<AccountId>) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.boost_registry = registry;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_marketplace_contract(&mut self, marketplace: Option<AccountId>) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            self.marketplace_contract = marketplace;
+            Ok(())
+        }
+
+        /// Atualiza a configuração de um pool (APY, períodos e cadência de pagamento).
+        /// Owner ou governança.
+        #[ink(message)]
+        pub fn set_pool_config(&mut self, pool: u8, config: PoolConfig) -> Result<(), StakingError> {
+            self.ensure_owner_or_governance()?;
+            PoolType::from_u8(pool).ok_or(StakingError::PoolNotActive)?;
+            if config.payment_frequency_days == 0
+                || config.unbonding_days > MAX_UNBONDING_DAYS
+                || config.instant_exit_fee_bps > 10000
+                || config.reward_units_per_fiapo == 0
+                || config.fee_asset == Asset::Lunes
+            {
+                return Err(StakingError::InvalidPoolConfig);
+            }
+
+            self.pool_configs.insert(pool, &config);
+
+            Self::env().emit_event(PoolConfigUpdated {
+                pool,
+                apy_bps: config.apy_bps,
+                min_period_days: config.min_period_days,
+                payment_frequency_days: config.payment_frequency_days,
+                active: config.active,
+            });
+
+            Ok(())
+        }
+
+        /// Define a curva de penalidade de saída antecipada de um pool
+        #[ink(message)]
+        pub fn set_penalty_curve(&mut self, pool: u8, curve: PenaltyCurve) -> Result<(), StakingError> {
+            if self.env().caller() != self.owner {
+                return Err(StakingError::Unauthorized);
+            }
+            PoolType::from_u8(pool).ok_or(StakingError::PoolNotActive)?;
+            if let PenaltyCurve::Steps(steps) = &curve {
+                let ordered = steps.windows(2).all(|pair| pair[0].from_day < pair[1].from_day);
+                if steps.is_empty()
+                    || steps.len() > MAX_PENALTY_STEPS
+                    || !ordered
+                    || steps.iter().any(|step| step.scale_bps > 10000)
+                {
+                    return Err(StakingError::InvalidPoolConfig);
+                }
+            }
+
+            self.penalty_curves.insert(pool, &curve);
+            Ok(())
+        }
+
+        /// Parte fixa em USDT devida se a posição sair agora (apenas Don Burn antes da maturidade)
+        fn fixed_exit_fee_cents(&self, position: &StakingPosition, config: &PoolConfig, now: u64) -> u64 {
+            if position.pool_type != PoolType::DonBurn || self.penalty_scale_bps(position, config, now) == 0 {
+                return 0;
+            }
+            self.burn_exit_fee_usdt_cents
+        }
+
+        fn ensure_exit_fee_due(&self, caller: AccountId, position_id: u64) -> Result<u64, StakingError> {
+            self.ensure_not_emergency()?;
+            let position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+            if position.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+            if position.status != PositionStatus::Active {
+                return Err(StakingError::PositionNotActive);
+            }
+            if self.exit_fee_paid.get(position_id).unwrap_or(false) {
+                return Err(StakingError::ExitFeeAlreadyPaid);
+            }
+            let config = self.pool_configs.get(position.pool_type.to_u8())
+                .ok_or(StakingError::PoolNotActive)?;
+            let cents = self.fixed_exit_fee_cents(&position, &config, self.env().block_timestamp());
+            if cents == 0 {
+                return Err(StakingError::ExitFeeNotRequired);
+            }
+            Ok(cents)
+        }
+
+        fn verify_oracle_usdt(&mut self, tx_hash: String, user: AccountId, expected_cents: u64, position_id: u64) -> Result<(), StakingError> {
+            if self.used_tx_hashes.get(&tx_hash).unwrap_or(false) {
+                return Err(StakingError::TxHashAlreadyUsed);
+            }
+
+            let oracle_addr = self.oracle_contract.ok_or(StakingError::OraclePaymentNotConfirmed)?;
+            let mut oracle: OracleRef = oracle_addr.into();
+
+            // O oracle só aceita pagamentos confirmados como taxa de saída desta posição
+            // e os marca como consumidos
+            if oracle.consume_payment(tx_hash.clone(), user, expected_cents, PaymentPurpose::StakingExitFee { position_id }) {
+                self.used_tx_hashes.insert(tx_hash, &true);
+                Ok(())
+            } else {
+                Err(StakingError::OraclePaymentNotConfirmed)
+            }
+        }
+
+        // ==================== Portfolio & User Aggregates ====================
+
+        fn portfolio_position(&self, position: StakingPosition, now: u64) -> Option<PortfolioPosition> {
+            let config = self.pool_configs.get(position.pool_type.to_u8())?;
+            let lock_end = self.position_locks.get(position.id).map(|lock| lock.end).unwrap_or(0);
+            let unlock_at = position.start_time.saturating_add(
+                (config.min_period_days as u64).saturating_mul(SECONDS_PER_DAY)
+            ).max(lock_end);
+
+            match position.status {
+                PositionStatus::Active => {
+                    let boost_bps = self.fetch_user_boost_over(position.user, position.last_reward_time, now);
+                    let pending_rewards = self.calculate_rewards_with_boost(&position, &config, boost_bps, now);
+                    let boundary = self.last_claim_boundary(&position, &config, now);
+                    let claimable_rewards = if boundary > position.last_reward_time {
+                        let boost_bps = self.fetch_user_boost_over(position.user, position.last_reward_time, boundary);
+                        self.calculate_rewards_with_boost(&position, &config, boost_bps, boundary)
+                    } else {
+                        0
+                    };
+                    let (penalty, rewards_penalty) = self.exit_penalty(&position, &config, pending_rewards, now);
+                    Some(PortfolioPosition {
+                        next_claim_at: Some(self.next_claim_boundary(&position, &config)),
+                        position,
+                        pending_rewards,
+                        claimable_rewards,
+                        unlock_at,
+                        early_exit_penalty: penalty.saturating_add(rewards_penalty),
+                        unbonding: None,
+                    })
+                }
+                PositionStatus::Unbonding => {
+                    let unbonding = self.unbonding.get(position.id);
+                    Some(PortfolioPosition {
+                        early_exit_penalty: unbonding.as_ref().map(|r| r.penalty).unwrap_or(0),
+                        position,
+                        pending_rewards: 0,
+                        claimable_rewards: 0,
+                        next_claim_at: None,
+                        unlock_at,
+                        unbonding,
+                    })
                 }
+                _ => None,
             }
+        }
 
-            if team_part > 0 {
-                if let Some(team) = self.team_wallet {
-                    let _ = self.call_core_transfer(team, team_part);
+        /// Penalidade de saída antecipada: (sobre o principal, sobre as recompensas).
+        /// A penalidade base do pool é escalada pela curva configurada.
+        fn exit_penalty(&self, position: &StakingPosition, config: &PoolConfig, rewards: Balance, now: u64) -> (Balance, Balance) {
+            let scale_bps = self.penalty_scale_bps(position, config, now) as u128;
+            if scale_bps == 0 {
+                return (0, 0);
+            }
+            let (capital_penalty, interest_penalty) = match position.pool_type {
+                PoolType::DonBurn => {
+                    // 50% capital + 80% interest (os 10 USDT fixos são cobrados à parte)
+                    let capital_penalty = position.amount.saturating_div(2);
+                    let interest_penalty = rewards.saturating_mul(80).saturating_div(100);
+                    (capital_penalty, interest_penalty)
+                }
+                PoolType::DonLunes | PoolType::DonFiapo => {
+                    let penalty = position.amount
+                        .saturating_mul(config.early_withdrawal_penalty_bps as u128)
+                        .saturating_div(10000);
+                    (penalty, 0)
+                }
+            };
+            (
+                capital_penalty.saturating_mul(scale_bps).saturating_div(10000),
+                interest_penalty.saturating_mul(scale_bps).saturating_div(10000),
+            )
+        }
+
+        /// Fração (bps) da penalidade base devida em `now`; zero a partir da maturidade
+        fn penalty_scale_bps(&self, position: &StakingPosition, config: &PoolConfig, now: u64) -> u32 {
+            let maturity = (config.min_period_days as u64).saturating_mul(SECONDS_PER_DAY);
+            let elapsed = now.saturating_sub(position.start_time);
+            if elapsed >= maturity {
+                return 0;
+            }
+            match self.penalty_curves.get(position.pool_type.to_u8()).unwrap_or_default() {
+                PenaltyCurve::Cliff => 10000,
+                PenaltyCurve::Linear => {
+                    let remaining = maturity.saturating_sub(elapsed) as u128;
+                    remaining.saturating_mul(10000)
+                        .checked_div(maturity as u128)
+                        .unwrap_or(0) as u32
+                }
+                PenaltyCurve::Steps(steps) => {
+                    let days = (elapsed / SECONDS_PER_DAY) as u32;
+                    steps.iter()
+                        .rev()
+                        .find(|step| days >= step.from_day)
+                        .map(|step| step.scale_bps as u32)
+                        .unwrap_or(10000)
                 }
             }
+        }
 
-            if rewards_part > 0 {
-                if let Some(rewards_addr) = self.rewards_contract {
-                    let _ = self.call_core_transfer(rewards_addr, rewards_part);
-                    let _ = self.call_rewards_add_fund(rewards_addr, rewards_part);
+        /// Divisão das penalidades: Don Burn 20% Burn / 30% Rewards / 50% Staking;
+        /// demais pools 10% Team / 40% Rewards / 50% Staking
+        fn penalty_split(pool_type: PoolType, total: Balance) -> PenaltySplit {
+            match pool_type {
+                PoolType::DonBurn => {
+                    let burn = total.saturating_mul(20).saturating_div(100);
+                    let rewards_fund = total.saturating_mul(30).saturating_div(100);
+                    PenaltySplit {
+                        team: 0,
+                        rewards_fund,
+                        burn,
+                        staking: total.saturating_sub(burn).saturating_sub(rewards_fund),
+                    }
+                }
+                PoolType::DonLunes | PoolType::DonFiapo => {
+                    let team = total.saturating_mul(10).saturating_div(100);
+                    let rewards_fund = total.saturating_mul(40).saturating_div(100);
+                    PenaltySplit {
+                        team,
+                        rewards_fund,
+                        burn: 0,
+                        staking: total.saturating_sub(team).saturating_sub(rewards_fund),
+                    }
                 }
             }
+        }
 
-            if burn_part > 0 {
-                if let Some(burn_addr) = self.burn_wallet {
-                    let _ = self.call_core_transfer(burn_addr, burn_part);
+        /// Registra uma posição viva nos agregados do usuário
+        fn add_user_stake(&mut self, user: AccountId, pool_type: PoolType, amount: Balance) {
+            let pool = pool_type.to_u8() as usize;
+            let mut aggregates = self.user_aggregates.get(user).unwrap_or_default();
+            if aggregates.positions_per_pool.iter().all(|&count| count == 0) {
+                self.unique_stakers = self.unique_stakers.saturating_add(1);
+            }
+            if aggregates.positions_per_pool[pool] == 0 {
+                self.stakers_per_pool[pool] = self.stakers_per_pool[pool].saturating_add(1);
+            }
+            aggregates.staked_per_pool[pool] = aggregates.staked_per_pool[pool].saturating_add(amount);
+            aggregates.positions_per_pool[pool] = aggregates.positions_per_pool[pool].saturating_add(1);
+            self.user_aggregates.insert(user, &aggregates);
+            self.write_stake_checkpoint(Some(user), Self::sum_staked(&aggregates));
+        }
+
+        /// Remove uma posição dos agregados (saída ou transferência)
+        fn remove_user_stake(&mut self, user: AccountId, pool_type: PoolType, amount: Balance) {
+            let pool = pool_type.to_u8() as usize;
+            let mut aggregates = self.user_aggregates.get(user).unwrap_or_default();
+            aggregates.staked_per_pool[pool] = aggregates.staked_per_pool[pool].saturating_sub(amount);
+            aggregates.positions_per_pool[pool] = aggregates.positions_per_pool[pool].saturating_sub(1);
+            if aggregates.positions_per_pool[pool] == 0 {
+                self.stakers_per_pool[pool] = self.stakers_per_pool[pool].saturating_sub(1);
+            }
+            if aggregates.positions_per_pool.iter().all(|&count| count == 0) {
+                self.unique_stakers = self.unique_stakers.saturating_sub(1);
+            }
+            self.user_aggregates.insert(user, &aggregates);
+            self.write_stake_checkpoint(Some(user), Self::sum_staked(&aggregates));
+        }
+
+        fn sum_staked(aggregates: &UserAggregates) -> Balance {
+            aggregates.staked_per_pool.iter().fold(0, |acc, &x| acc.saturating_add(x))
+        }
+
+        fn checkpoint_total_staked(&mut self) {
+            let total = self.total_staked_per_pool.iter().fold(0, |acc: Balance, &x| acc.saturating_add(x));
+            self.write_stake_checkpoint(None, total);
+        }
+
+        /// Grava o valor no bloco atual (sobrescreve se já houver checkpoint no bloco)
+        fn write_stake_checkpoint(&mut self, key: Option<AccountId>, value: Balance) {
+            let block = self.env().block_number();
+            let count = self.stake_checkpoint_counts.get(key).unwrap_or(0);
+            if count > 0 {
+                let last = count.saturating_sub(1);
+                if let Some((last_block, _)) = self.stake_checkpoints.get((key, last)) {
+                    if last_block == block {
+                        self.stake_checkpoints.insert((key, last), &(block, value));
+                        return;
+                    }
                 }
             }
+            self.stake_checkpoints.insert((key, count), &(block, value));
+            self.stake_checkpoint_counts.insert(key, &count.saturating_add(1));
+        }
 
-            // Staking part remains in contract (implicitly added to balance for backing APY coverage)
-            
-            Self::env().emit_event(FeeDistributed {
-                reason,
-                amount: total,
-                team_part,
-                rewards_part,
-                burn_part,
-                staking_part,
-                noble_part,
-            });
+        /// Busca binária pelo último checkpoint com bloco <= `block`
+        fn stake_checkpoint_at(&self, key: Option<AccountId>, block: u32) -> Balance {
+            let (mut low, mut high) = (0u32, self.stake_checkpoint_counts.get(key).unwrap_or(0));
+            while low < high {
+                let mid = low.saturating_add(high.saturating_sub(low) / 2);
+                match self.stake_checkpoints.get((key, mid)) {
+                    Some((checkpoint_block, _)) if checkpoint_block <= block => low = mid.saturating_add(1),
+                    _ => high = mid,
+                }
+            }
+            if low == 0 {
+                return 0;
+            }
+            self.stake_checkpoints.get((key, low.saturating_sub(1)))
+                .map(|(_, value)| value)
+                .unwrap_or(0)
+        }
 
-            Ok(())
+        fn record_user_rewards(&mut self, user: AccountId, asset: Asset, rewards: Balance, penalty: Balance) {
+            let mut aggregates = self.user_aggregates.get(user).unwrap_or_default();
+            aggregates.lifetime_rewards[asset.index()] = aggregates.lifetime_rewards[asset.index()].saturating_add(rewards);
+            aggregates.penalties_paid = aggregates.penalties_paid.saturating_add(penalty);
+            self.user_aggregates.insert(user, &aggregates);
         }
 
-        // ==================== Admin / Setters ====================
+        fn add_user_position(&mut self, user: AccountId, position_id: u64) {
+            let index = self.balance_of(user);
+            self.user_positions.insert((user, index), &position_id);
+            self.user_position_index.insert(position_id, &index);
+            self.user_position_count.insert(user, &index.saturating_add(1));
+        }
 
-        #[ink(message)]
-        pub fn set_linked_contracts(
-            &mut self,
-            oracle: Option<AccountId>,
-            affiliate: Option<AccountId>,
-            rewards: Option<AccountId>,
-            noble: Option<AccountId>, // New
-            team: Option<AccountId>,
-            burn: Option<AccountId>
-        ) -> Result<(), StakingError> {
-            if self.env().caller() != self.owner {
-                return Err(StakingError::Unauthorized);
+        /// Remove do índice do usuário uma posição encerrada ou transferida
+        /// (swap com a última)
+        fn release_user_position(&mut self, user: AccountId, position_id: u64) {
+            let Some(index) = self.user_position_index.get(position_id) else { return };
+            let last = self.balance_of(user).saturating_sub(1);
+            if index != last {
+                if let Some(moved) = self.user_positions.get((user, last)) {
+                    self.user_positions.insert((user, index), &moved);
+                    self.user_position_index.insert(moved, &index);
+                }
             }
-            self.oracle_contract = oracle;
-            self.affiliate_contract = affiliate;
-            self.rewards_contract = rewards;
-            self.noble_contract = noble;
-            self.team_wallet = team;
-            self.burn_wallet = burn;
-            Ok(())
+            self.user_positions.remove((user, last));
+            self.user_position_index.remove(position_id);
+            self.user_position_count.insert(user, &last);
+        }
+
+        fn user_position_ids(&self, user: AccountId) -> Vec<u64> {
+            (0..self.balance_of(user))
+                .filter_map(|index| self.user_positions.get((user, index)))
+                .collect()
         }
 
         // ==================== Helper Calls ====================
 
+        /// Boost total de APY vigente agora (ecossistema + NFTs)
         fn fetch_user_boost(&self, user: AccountId) -> u32 {
+            self.fetch_ecosystem_boost(user).saturating_add(self.fetch_nft_boost(user).0)
+        }
+
+        /// Boost total médio em `[from, to)`: o do ecossistema só conta enquanto
+        /// esteve vigente, não o valor do momento do claim
+        fn fetch_user_boost_over(&self, user: AccountId, from: u64, to: u64) -> u32 {
+            let ecosystem = match self.boost_registry {
+                Some(registry) => BoostRegistryCall::get_boost_over(registry, user, BoostTarget::StakingApy, from, to),
+                // Boost de afiliado não expira: o valor atual vale para todo o intervalo
+                None => self.fetch_ecosystem_boost(user),
+            };
+            ecosystem.saturating_add(self.fetch_nft_boost(user).0)
+        }
+
+        fn fetch_ecosystem_boost(&self, user: AccountId) -> u32 {
+            // Boost Registry agrega affiliate, spin, burn etc. com caps
+            if let Some(registry) = self.boost_registry {
+                return BoostRegistryCall::get_boost(registry, user, BoostTarget::StakingApy);
+            }
             if let Some(affiliate_addr) = self.affiliate_contract {
                 // Uses build_call with raw selector (standalone method in FiapoAffiliate)
                 return AffiliateCall::calculate_apy_boost(affiliate_addr, user);
@@ -656,6 +2626,39 @@ mod fiapo_staking {
             0
         }
 
+        /// Boost dos NFTs ativos do ICO em posse do usuário: (bps com cap, bruto, detalhes)
+        fn fetch_nft_boost(&self, user: AccountId) -> (u32, u32, Vec<NftBoostItem>) {
+            if !self.nft_boost_config.enabled {
+                return (0, 0, Vec::new());
+            }
+            self.compute_nft_boost(&self.nft_holdings.get(user).unwrap_or_default())
+        }
+
+        /// `holdings` já contém apenas NFTs ativos do usuário
+        fn compute_nft_boost(&self, holdings: &[NftHolding]) -> (u32, u32, Vec<NftBoostItem>) {
+            let config = &self.nft_boost_config;
+            let items: Vec<NftBoostItem> = holdings.iter()
+                .take(config.max_nfts_counted as usize)
+                .map(|nft| {
+                    let base = config.tier_boost_bps.get(nft.tier as usize).copied().unwrap_or(0);
+                    let multiplier = if config.rarity_enabled {
+                        config.rarity_multiplier_bps.get(nft.visual_rarity as usize).copied().unwrap_or(10000)
+                    } else {
+                        10000
+                    };
+                    NftBoostItem {
+                        nft_id: nft.nft_id,
+                        tier: nft.tier,
+                        visual_rarity: nft.visual_rarity,
+                        boost_bps: base.saturating_mul(multiplier).checked_div(10000).unwrap_or(0),
+                    }
+                })
+                .collect();
+
+            let uncapped = items.iter().fold(0u32, |acc, item| acc.saturating_add(item.boost_bps));
+            (uncapped.min(config.max_boost_bps), uncapped, items)
+        }
+
         fn call_affiliate_update_activity(&self, affiliate_addr: AccountId, user: AccountId, amount: Balance) -> Result<(), StakingError> {
             // Uses build_call with raw selector (standalone method in FiapoAffiliate)
             match AffiliateCall::update_referral_activity(affiliate_addr, user, amount) {
@@ -719,24 +2722,136 @@ mod fiapo_staking {
             }
         }
 
+        /// LUSDT: transfer_from via PSP22Ref
+        fn call_lusdt_transfer_from(&self, from: AccountId, to: AccountId, amount: Balance) -> Result<(), StakingError> {
+            let lusdt = self.lusdt_contract.ok_or(StakingError::AssetNotConfigured)?;
+            let mut psp22: PSP22Ref = lusdt.into();
+            match psp22.transfer_from(from, to, amount) {
+                Ok(_) => Ok(()),
+                _ => Err(StakingError::TransferFailed)
+            }
+        }
+
+        /// Transfere `amount` do ativo indicado a partir do saldo do contrato
+        fn transfer_asset(&self, asset: Asset, to: AccountId, amount: Balance) -> Result<(), StakingError> {
+            match asset {
+                Asset::Fiapo => self.call_core_transfer(to, amount),
+                Asset::Lunes => self.env().transfer(to, amount)
+                    .map_err(|_| StakingError::TransferFailed),
+                Asset::Lusdt => {
+                    let lusdt = self.lusdt_contract.ok_or(StakingError::AssetNotConfigured)?;
+                    let mut psp22: PSP22Ref = lusdt.into();
+                    match psp22.transfer(to, amount) {
+                        Ok(_) => Ok(()),
+                        _ => Err(StakingError::TransferFailed)
+                    }
+                }
+            }
+        }
+
+        /// Cobra uma taxa em LUSDT: 50% Team, restante para a reserva LUSDT de recompensas
+        fn collect_lusdt_fee(&mut self, user: AccountId, fee_lusdt: Balance, reason: &str) -> Result<(), StakingError> {
+            if fee_lusdt == 0 {
+                return Ok(());
+            }
+            self.call_lusdt_transfer_from(user, self.env().account_id(), fee_lusdt)?;
+
+            let team_part = match self.team_wallet {
+                Some(team) => {
+                    let part = fee_lusdt.saturating_mul(50).saturating_div(100);
+                    self.transfer_asset(Asset::Lusdt, team, part)?;
+                    part
+                }
+                None => 0,
+            };
+            let staking_part = fee_lusdt.saturating_sub(team_part);
+            let index = Asset::Lusdt.index();
+            self.reward_reserves[index] = self.reward_reserves[index].saturating_add(staking_part);
+
+            Self::env().emit_event(FeeDistributed {
+                reason: String::from(reason),
+                amount: fee_lusdt,
+                staking_part,
+                rewards_part: 0,
+                team_part,
+                burn_part: 0,
+                noble_part: 0,
+            });
+
+            Ok(())
+        }
+
         // ==================== Math ====================
 
-        fn calculate_rewards_with_boost(&self, position: &StakingPosition, config: &PoolConfig, boost_bps: u32) -> Balance {
-            let current_time = self.env().block_timestamp();
-            let seconds_elapsed = current_time.saturating_sub(position.last_reward_time);
-            let days_elapsed = seconds_elapsed.saturating_div(SECONDS_PER_DAY);
-            
-            let frequency = config.payment_frequency_days as u64;
-            if frequency == 0 || days_elapsed < frequency { return 0; }
-            
-            let periods = days_elapsed.checked_div(frequency).unwrap_or(0);
-            let total_days = periods.saturating_mul(frequency);
+        /// Recompensas acumuladas de forma contínua entre `last_reward_time` e `until`,
+        /// em unidades do ativo de recompensa do pool
+        fn calculate_rewards_with_boost(&self, position: &StakingPosition, config: &PoolConfig, boost_bps: u32, until: u64) -> Balance {
+            // Em emergência o accrual para no momento da ativação
+            let until = if self.emergency_mode { until.min(self.emergency_activated_at) } else { until };
+            let seconds_elapsed = until.saturating_sub(position.last_reward_time);
+            if seconds_elapsed == 0 { return 0; }
+
             let total_apy = (config.apy_bps as u128).saturating_add(boost_bps as u128);
-            
-            position.amount.saturating_mul(total_apy)
-                .saturating_mul(total_days as u128)
-                .checked_div(365).unwrap_or(0)
-                .checked_div(10000).unwrap_or(0)
+            let seconds_per_year = (SECONDS_PER_DAY as u128).saturating_mul(365);
+
+            let mut fiapo_rewards = position.amount.saturating_mul(total_apy)
+                .saturating_mul(seconds_elapsed as u128)
+                .checked_div(seconds_per_year).unwrap_or(0)
+                .checked_div(10000).unwrap_or(0);
+
+            // Bônus do lock apenas sobre o trecho coberto por ele
+            if let Some(lock) = self.position_locks.get(position.id) {
+                fiapo_rewards = fiapo_rewards.saturating_add(self.accrued_lock_bonus(position, &lock, total_apy, until));
+            }
+
+            // Converte para o ativo de recompensa do pool
+            if config.reward_asset == Asset::Fiapo {
+                fiapo_rewards
+            } else {
+                fiapo_rewards.saturating_mul(config.reward_units_per_fiapo)
+                    .checked_div(SCALE).unwrap_or(0)
+            }
+        }
+
+        /// Bônus do lock desde o último pagamento até `until`, incluindo o liquidado
+        /// na última extensão quando `until` já o alcança
+        fn accrued_lock_bonus(&self, position: &StakingPosition, lock: &PositionLock, total_apy: u128, until: u64) -> Balance {
+            let settled = self.settled_lock_bonus.get(position.id);
+            let bonus_start = settled.map_or(lock.start, |(since, _)| since.max(lock.start));
+            let locked_seconds = until.min(lock.end)
+                .saturating_sub(position.last_reward_time.max(bonus_start));
+            let extra_bps = (lock.apy_multiplier_bps as u128).saturating_sub(10000);
+            let bonus = position.amount.saturating_mul(total_apy)
+                .saturating_mul(locked_seconds as u128)
+                .checked_div((SECONDS_PER_DAY as u128).saturating_mul(365)).unwrap_or(0)
+                .saturating_mul(extra_bps)
+                .checked_div(10000 * 10000).unwrap_or(0);
+            match settled {
+                Some((since, settled_bonus)) if until >= since => bonus.saturating_add(settled_bonus),
+                _ => bonus,
+            }
+        }
+
+        fn payment_period(config: &PoolConfig) -> u64 {
+            (config.payment_frequency_days as u64).saturating_mul(SECONDS_PER_DAY)
+        }
+
+        /// Última janela de pagamento atingida (ancorada em `start_time`)
+        fn last_claim_boundary(&self, position: &StakingPosition, config: &PoolConfig, now: u64) -> u64 {
+            let period = Self::payment_period(config);
+            if period == 0 { return now; }
+            let periods = now.saturating_sub(position.start_time).checked_div(period).unwrap_or(0);
+            position.start_time.saturating_add(periods.saturating_mul(period))
+        }
+
+        /// Primeira janela de pagamento posterior ao último claim
+        fn next_claim_boundary(&self, position: &StakingPosition, config: &PoolConfig) -> u64 {
+            let period = Self::payment_period(config);
+            if period == 0 { return position.last_reward_time; }
+            let claimed_periods = position.last_reward_time
+                .saturating_sub(position.start_time)
+                .checked_div(period).unwrap_or(0);
+            position.start_time.saturating_add(claimed_periods.saturating_add(1).saturating_mul(period))
         }
         
         #[ink(message)]
@@ -756,6 +2871,147 @@ mod fiapo_staking {
             if self.env().caller() != self.owner { return Err(StakingError::Unauthorized); }
             self.paused = true; Ok(())
         }
+
+        // ==================== Emergency Mode ====================
+
+        #[ink(message)]
+        pub fn is_emergency_mode(&self) -> bool {
+            self.emergency_mode
+        }
+
+        #[ink(message)]
+        pub fn get_emergency_status(&self) -> EmergencyStatus {
+            let staked = self.total_staked_per_pool.iter().fold(0u128, |acc, &x| acc.saturating_add(x));
+            let unbonding = self.unbonding_per_pool.iter().fold(0u128, |acc, &x| acc.saturating_add(x))
+                .saturating_sub(self.unbonding_fiapo_rewards);
+            EmergencyStatus {
+                active: self.emergency_mode,
+                activated_at: self.emergency_activated_at,
+                activated_by: self.emergency_activated_by,
+                reason: self.emergency_reason.clone(),
+                multisig: self.emergency_multisig,
+                timelock: self.timelock_contract,
+                principal_outstanding: staked.saturating_add(unbonding),
+                withdrawals: self.emergency_withdrawals,
+            }
+        }
+
+        /// Define multisig e timelock autorizados a ativar a emergência (ambos obrigatórios,
+        /// para que o timelock sempre possa trocá-los). O owner só configura enquanto
+        /// nenhum estiver definido; depois, apenas o timelock.
+        #[ink(message)]
+        pub fn set_emergency_authorities(&mut self, multisig: AccountId, timelock: AccountId) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            let bootstrap = self.emergency_multisig.is_none() && self.timelock_contract.is_none();
+            let authorized = if bootstrap {
+                caller == self.owner
+            } else {
+                Some(caller) == self.timelock_contract
+            };
+            if !authorized {
+                return Err(StakingError::Unauthorized);
+            }
+            self.emergency_multisig = Some(multisig);
+            self.timelock_contract = Some(timelock);
+            Ok(())
+        }
+
+        /// Ativa o modo de emergência (irreversível). Apenas multisig ou timelock.
+        /// O accrual para e os stakers podem sacar o principal sem penalidades.
+        #[ink(message)]
+        pub fn enable_emergency_mode(&mut self, reason: String) -> Result<(), StakingError> {
+            let caller = self.env().caller();
+            if Some(caller) != self.emergency_multisig && Some(caller) != self.timelock_contract {
+                return Err(StakingError::Unauthorized);
+            }
+            self.ensure_not_emergency()?;
+
+            let now = self.env().block_timestamp();
+            self.emergency_mode = true;
+            self.emergency_activated_at = now;
+            self.emergency_activated_by = Some(caller);
+            self.emergency_reason = reason.clone();
+            self.paused = true;
+
+            Self::env().emit_event(EmergencyModeEnabled {
+                activated_by: caller,
+                reason,
+                timestamp: now,
+            });
+
+            Ok(())
+        }
+
+        /// Saque de emergência: devolve o principal sem recompensas nem penalidades.
+        /// Posições já em unbonding recebem o principal líquido da fila sem aguardar o cooldown.
+        #[ink(message)]
+        pub fn emergency_withdraw(&mut self, position_id: u64) -> Result<Balance, StakingError> {
+            if !self.emergency_mode {
+                return Err(StakingError::EmergencyModeInactive);
+            }
+            let caller = self.env().caller();
+            let amount = self.release_for_emergency(position_id, caller)?;
+
+            self.call_core_transfer(caller, amount)?;
+
+            Self::env().emit_event(EmergencyWithdrawal {
+                position_id,
+                user: caller,
+                amount,
+            });
+
+            Ok(amount)
+        }
+
+        /// Encerra a posição para o saque de emergência e retorna o principal a devolver.
+        /// Em unbonding, a penalidade já foi distribuída na saída: devolve o principal
+        /// líquido e as recompensas já liquidadas são perdidas.
+        fn release_for_emergency(&mut self, position_id: u64, caller: AccountId) -> Result<Balance, StakingError> {
+            let mut position = self.positions.get(position_id)
+                .ok_or(StakingError::PositionNotFound)?;
+            if position.user != caller {
+                return Err(StakingError::NotPositionOwner);
+            }
+
+            let pool = position.pool_type.to_u8() as usize;
+            let amount = match position.status {
+                PositionStatus::Active => {
+                    self.total_staked_per_pool[pool] = self.total_staked_per_pool[pool].saturating_sub(position.amount);
+                    self.active_positions = self.active_positions.saturating_sub(1);
+                    self.checkpoint_total_staked();
+                    self.remove_user_stake(caller, position.pool_type, position.amount);
+                    position.amount
+                }
+                PositionStatus::Unbonding => {
+                    let request = self.unbonding.get(position_id)
+                        .ok_or(StakingError::UnbondingNotFound)?;
+                    self.dequeue_unbonding(&request);
+                    // Recompensas em ativo externo voltam para a reserva; o devido é perdido
+                    self.reward_shortfalls.remove(position_id);
+                    let index = request.reward_asset.index();
+                    self.reward_reserves[index] = self.reward_reserves[index].saturating_add(request.reward_amount);
+                    let mut aggregates = self.get_user_aggregates(caller);
+                    aggregates.lifetime_rewards[index] = aggregates.lifetime_rewards[index].saturating_sub(request.reward_amount);
+                    self.user_aggregates.insert(caller, &aggregates);
+                    request.amount.saturating_sub(request.fiapo_rewards)
+                }
+                _ => return Err(StakingError::PositionNotActive),
+            };
+
+            position.status = PositionStatus::Completed;
+            self.positions.insert(position_id, &position);
+            self.release_user_position(caller, position_id);
+            self.checkpoint_locks(caller);
+            self.emergency_withdrawals = self.emergency_withdrawals.saturating_add(1);
+            Ok(amount)
+        }
+
+        fn ensure_not_emergency(&self) -> Result<(), StakingError> {
+            if self.emergency_mode {
+                return Err(StakingError::EmergencyModeActive);
+            }
+            Ok(())
+        }
     }
 
     impl Staking for FiapoStaking {
@@ -766,13 +3022,610 @@ mod fiapo_staking {
 
         #[ink(message)]
         fn get_user_positions(&self, user: AccountId) -> Vec<u64> {
-            self.user_positions.get(user).unwrap_or_default()
+            self.user_position_ids(user)
         }
 
         #[ink(message)]
         fn core_contract(&self) -> AccountId {
             self.core_contract
         }
+
+        #[ink(message)]
+        fn total_stakers(&self) -> u32 {
+            self.unique_stakers
+        }
+
+        /// Poder de voto (veFIAPO) do usuário em `at`, pelos locks vigentes no
+        /// último checkpoint até esse instante
+        #[ink(message)]
+        fn voting_power_of(&self, account: AccountId, at: u64) -> Balance {
+            self.lock_checkpoint_at(account, at)
+                .iter()
+                .fold(0, |acc, (amount, lock)| acc.saturating_add(Self::lock_voting_power(*amount, lock, at)))
+        }
+
+        #[ink(message)]
+        fn staked_balance_at(&self, account: AccountId, block: u32) -> Balance {
+            self.stake_checkpoint_at(Some(account), block)
+        }
+
+        #[ink(message)]
+        fn total_staked_at(&self, block: u32) -> Balance {
+            self.stake_checkpoint_at(None, block)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use fiapo_logics::traits::staking::PoolConfigParams;
+        use scale::{Decode, Encode};
+
+        type Env = ink::env::DefaultEnvironment;
+
+        fn insert_position(contract: &mut FiapoStaking, user: AccountId, pool_type: PoolType, start: u64) -> u64 {
+            let id = contract.next_position_id;
+            contract.positions.insert(id, &StakingPosition {
+                id,
+                user,
+                pool_type,
+                amount: 1_000 * SCALE,
+                entry_fee: 0,
+                start_time: start,
+                last_reward_time: start,
+                accumulated_rewards: 0,
+                status: PositionStatus::Active,
+            });
+            contract.add_user_position(user, id);
+            contract.add_user_stake(user, pool_type, 1_000 * SCALE);
+            contract.next_position_id = id.saturating_add(1);
+            id
+        }
+
+        #[ink::test]
+        fn claim_window_follows_payment_frequency() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(0);
+            // Don Lunes paga semanalmente
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonLunes, 0);
+
+            assert_eq!(contract.next_claim_at(id), Some(7 * SECONDS_PER_DAY));
+
+            ink::env::test::set_block_timestamp::<Env>(3 * SECONDS_PER_DAY);
+            assert!(contract.pending_rewards(id) > 0);
+            assert_eq!(contract.claimable_rewards(id), 0);
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.claim_rewards(id), Err(StakingError::ClaimWindowClosed));
+
+            ink::env::test::set_block_timestamp::<Env>(8 * SECONDS_PER_DAY);
+            let schedule = contract.get_reward_schedule(id).unwrap();
+            assert!(schedule.claim_open);
+            assert!(schedule.claimable > 0 && schedule.claimable < schedule.accrued);
+        }
+
+        #[ink::test]
+        fn set_pool_config_changes_cadence() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            assert_eq!(contract.next_claim_at(id), Some(30 * SECONDS_PER_DAY));
+
+            let mut config = contract.get_pool_config(2).unwrap();
+            config.payment_frequency_days = 0;
+            assert_eq!(contract.set_pool_config(2, config.clone()), Err(StakingError::InvalidPoolConfig));
+
+            config.payment_frequency_days = 14;
+            assert_eq!(contract.set_pool_config(2, config.clone()), Ok(()));
+            assert_eq!(contract.next_claim_at(id), Some(14 * SECONDS_PER_DAY));
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.set_pool_config(2, config.clone()), Err(StakingError::Unauthorized));
+
+            // Governança envia o espelho `PoolConfigParams` (mesma codificação SCALE)
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            contract.set_governance_contract(Some(accounts.bob)).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            config.payment_frequency_days = 7;
+            let params = PoolConfigParams::decode(&mut &config.encode()[..]).unwrap();
+            assert_eq!(params.fee_asset, Asset::Fiapo as u8);
+            let decoded = PoolConfig::decode(&mut &params.encode()[..]).unwrap();
+            assert_eq!(contract.set_pool_config(2, decoded.clone()), Ok(()));
+            assert_eq!(contract.get_pool_config(2).unwrap().payment_frequency_days, 7);
+
+            // O Timelock que executa as propostas também é aceito
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            contract.set_emergency_authorities(accounts.eve, accounts.frank).unwrap();
+            ink::env::test::set_caller::<Env>(accounts.frank);
+            assert_eq!(contract.set_pool_config(2, decoded), Ok(()));
+        }
+
+        #[ink::test]
+        fn request_unstake_queues_until_cooldown() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            contract.total_staked_per_pool[2] = 1_000 * SCALE;
+            contract.active_positions = 1;
+
+            // Após o período mínimo não há penalidade
+            let now = 100 * SECONDS_PER_DAY;
+            ink::env::test::set_block_timestamp::<Env>(now);
+            let queued = contract.request_unstake(id).unwrap();
+            assert!(queued > 1_000 * SCALE);
+
+            let request = contract.get_unbonding(id).unwrap();
+            assert_eq!(request.release_at, now + 21 * SECONDS_PER_DAY);
+            assert_eq!(contract.positions.get(id).unwrap().status, PositionStatus::Unbonding);
+            assert_eq!(contract.pending_rewards(id), 0);
+            assert_eq!(contract.claim_rewards(id), Err(StakingError::PositionNotActive));
+
+            let summary = contract.get_unbonding_summary();
+            assert_eq!(summary.total_unbonding, queued);
+            assert_eq!(summary.unbonding_per_pool[2], queued);
+            assert_eq!(summary.releasable_now, 0);
+            assert_eq!(summary.queue_length, 1);
+            assert_eq!(contract.unbonding_outflows_until(request.release_at), queued);
+            assert_eq!(contract.get_unbonding_queue(0, 10).len(), 1);
+
+            assert_eq!(contract.withdraw_unbonded(id), Err(StakingError::UnbondingNotReady));
+            assert_eq!(contract.request_unstake(id), Err(StakingError::PositionNotActive));
+
+            // Segundo saque num bucket anterior; remover o primeiro mantém a fila compacta
+            let other = insert_position(&mut contract, accounts.bob, PoolType::DonLunes, 0);
+            contract.total_staked_per_pool[1] = 1_000 * SCALE;
+            contract.active_positions = 2;
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            let burn_queued = contract.request_unstake(other).unwrap();
+            let burn_release = contract.get_unbonding(other).unwrap().release_at;
+            assert!(burn_release < request.release_at);
+            assert_eq!(contract.unbonding_outflows_until(burn_release), burn_queued);
+            assert_eq!(contract.unbonding_outflows_until(request.release_at), queued + burn_queued);
+
+            contract.dequeue_unbonding(&request);
+            assert_eq!(contract.get_unbonding_queue(0, 10), ink::prelude::vec![contract.get_unbonding(other).unwrap()]);
+            assert_eq!(contract.get_unbonding_summary().queue_length, 1);
+            assert_eq!(contract.unbonding_outflows_until(request.release_at), burn_queued);
+        }
+
+        #[ink::test]
+        fn lunes_reward_pool_uses_reserve() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+
+            // Don Lunes: stake em FIAPO rendendo LUNES (1 FIAPO de recompensa = 2 LUNES)
+            let mut config = contract.get_pool_config(1).unwrap();
+            config.reward_asset = Asset::Lunes;
+            config.reward_units_per_fiapo = 2 * SCALE;
+            assert_eq!(contract.set_pool_config(1, config.clone()), Ok(()));
+
+            config.fee_asset = Asset::Lunes;
+            assert_eq!(contract.set_pool_config(1, config), Err(StakingError::InvalidPoolConfig));
+
+            ink::env::test::set_block_timestamp::<Env>(0);
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonLunes, 0);
+            let fiapo_id = insert_position(&mut contract, accounts.bob, PoolType::DonFiapo, 0);
+
+            ink::env::test::set_block_timestamp::<Env>(70 * SECONDS_PER_DAY);
+            assert_eq!(contract.pending_rewards(id), contract.pending_rewards(fiapo_id) * 12 / 7);
+
+            // Sem reserva o claim falha, mas o saque do principal não é bloqueado
+            assert_eq!(contract.claim_rewards(id), Err(StakingError::InsufficientRewardReserve));
+
+            // Nem a transferência: o auto-claim que a reserva não cobre fica devido ao seller
+            let sold = insert_position(&mut contract, accounts.alice, PoolType::DonLunes, 0);
+            assert_eq!(contract.transfer_position(accounts.django, sold), Ok(0));
+            assert_eq!(contract.owner_of(sold), Some(accounts.django));
+            let owed_on_transfer = contract.get_owed_rewards(accounts.alice, Asset::Lunes);
+            assert!(owed_on_transfer > 0);
+            assert_eq!(contract.get_owed_rewards(accounts.django, Asset::Lunes), 0);
+
+            ink::env::test::set_value_transferred::<Env>(SCALE);
+            // LUNES enviado com outro ativo não seria creditado em reserva alguma
+            assert_eq!(contract.fund_rewards(Asset::Lusdt, SCALE), Err(StakingError::InvalidAmount));
+            assert_eq!(contract.fund_rewards(Asset::Lunes, 0), Ok(()));
+            assert_eq!(contract.get_reward_reserve(Asset::Lunes), SCALE);
+            ink::env::test::set_value_transferred::<Env>(0);
+
+            let principal = contract.request_unstake(id).unwrap();
+            assert_eq!(principal, 1_000 * SCALE);
+            let request = contract.get_unbonding(id).unwrap();
+            assert_eq!(request.reward_asset, Asset::Lunes);
+            assert_eq!(request.reward_amount, SCALE);
+            assert_eq!(contract.get_reward_reserve(Asset::Lunes), 0);
+
+            // O que a reserva não cobriu fica devido à posição
+            let (asset, owed) = contract.get_reward_shortfall(id).unwrap();
+            assert_eq!(asset, Asset::Lunes);
+            assert!(owed > 0);
+            assert_eq!(contract.claim_reward_shortfall(id), Err(StakingError::InsufficientRewardReserve));
+
+            ink::env::test::set_value_transferred::<Env>(owed);
+            assert_eq!(contract.fund_rewards(Asset::Lunes, 0), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.claim_reward_shortfall(id), Err(StakingError::NotPositionOwner));
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            ink::env::test::set_account_balance::<Env>(ink::env::account_id::<Env>(), owed);
+            assert_eq!(contract.claim_reward_shortfall(id), Ok(owed));
+            assert_eq!(contract.get_reward_shortfall(id), None);
+            assert_eq!(contract.get_reward_reserve(Asset::Lunes), 0);
+
+            ink::env::test::set_value_transferred::<Env>(owed_on_transfer);
+            assert_eq!(contract.fund_rewards(Asset::Lunes, 0), Ok(()));
+            ink::env::test::set_value_transferred::<Env>(0);
+            ink::env::test::set_account_balance::<Env>(ink::env::account_id::<Env>(), owed_on_transfer);
+            assert_eq!(contract.claim_owed_rewards(Asset::Lunes), Ok(owed_on_transfer));
+            assert_eq!(contract.get_owed_rewards(accounts.alice, Asset::Lunes), 0);
+            assert_eq!(contract.claim_owed_rewards(Asset::Lunes), Err(StakingError::NoRewardsToClaim));
+
+            // FIAPO não tem reserva de recompensas
+            assert_eq!(contract.fund_rewards(Asset::Fiapo, SCALE), Err(StakingError::UnsupportedRewardAsset));
+        }
+
+        #[ink::test]
+        fn emergency_mode_requires_authority_and_stops_accrual() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(0);
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+
+            // Owner não ativa diretamente
+            assert_eq!(contract.enable_emergency_mode(String::from("exploit")), Err(StakingError::Unauthorized));
+            assert_eq!(contract.emergency_withdraw(id), Err(StakingError::EmergencyModeInactive));
+
+            contract.set_emergency_authorities(accounts.django, accounts.frank).unwrap();
+            // Após configurado, apenas o timelock altera as autoridades
+            assert_eq!(contract.set_emergency_authorities(accounts.bob, accounts.bob), Err(StakingError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.frank);
+            assert_eq!(contract.set_emergency_authorities(accounts.django, accounts.eve), Ok(()));
+            assert_eq!(contract.get_emergency_status().timelock, Some(accounts.eve));
+
+            ink::env::test::set_block_timestamp::<Env>(10 * SECONDS_PER_DAY);
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.enable_emergency_mode(String::from("exploit")), Ok(()));
+
+            let status = contract.get_emergency_status();
+            assert!(status.active);
+            assert_eq!(status.activated_by, Some(accounts.django));
+            assert_eq!(status.activated_at, 10 * SECONDS_PER_DAY);
+
+            let accrued = contract.pending_rewards(id);
+            ink::env::test::set_block_timestamp::<Env>(40 * SECONDS_PER_DAY);
+            assert_eq!(contract.pending_rewards(id), accrued);
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.claim_rewards(id), Err(StakingError::EmergencyModeActive));
+            assert_eq!(contract.request_unstake(id), Err(StakingError::EmergencyModeActive));
+            assert_eq!(contract.transfer_position(accounts.bob, id), Err(StakingError::EmergencyModeActive));
+
+            // Em unbonding devolve o principal líquido: a penalidade já foi distribuída
+            // e as recompensas em FIAPO da fila não são pagas
+            let unbonding = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            let mut position = contract.positions.get(unbonding).unwrap();
+            position.status = PositionStatus::Unbonding;
+            contract.positions.insert(unbonding, &position);
+            contract.enqueue_unbonding(UnbondingRequest {
+                position_id: unbonding,
+                user: accounts.alice,
+                pool_type: PoolType::DonFiapo,
+                amount: 990 * SCALE,
+                fiapo_rewards: 50 * SCALE,
+                penalty: 60 * SCALE,
+                requested_at: 10 * SECONDS_PER_DAY,
+                release_at: 31 * SECONDS_PER_DAY,
+                reward_asset: Asset::Fiapo,
+                reward_amount: 0,
+            });
+            assert_eq!(contract.release_for_emergency(unbonding, accounts.bob), Err(StakingError::NotPositionOwner));
+            assert_eq!(contract.get_emergency_status().principal_outstanding, 940 * SCALE);
+            assert_eq!(contract.release_for_emergency(unbonding, accounts.alice), Ok(940 * SCALE));
+            assert_eq!(contract.get_emergency_status().principal_outstanding, 0);
+            assert_eq!(contract.get_unbonding_summary().queue_length, 0);
+            assert_eq!(contract.positions.get(unbonding).unwrap().status, PositionStatus::Completed);
+        }
+
+        fn holding(nft_id: u64, tier: u8, visual_rarity: u8) -> NftHolding {
+            NftHolding { nft_id, tier, visual_rarity }
+        }
+
+        #[ink::test]
+        fn nft_boost_is_tier_weighted_and_capped() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let alice = accounts.alice;
+            contract.set_ico_contract(Some(accounts.django)).unwrap();
+
+            // Apenas o ICO informa os NFTs ativos do usuário
+            let holdings = vec![holding(1, 6, 4), holding(2, 4, 0)];
+            assert_eq!(contract.sync_nft_holdings(alice, holdings.clone()), Err(StakingError::Unauthorized));
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.sync_nft_holdings(alice, holdings), Ok(()));
+            ink::env::test::set_caller::<Env>(alice);
+
+            // Tier7 Legendary (200 * 2x) + Tier5 Common (100)
+            let breakdown = contract.boost_breakdown(alice);
+            assert_eq!(breakdown.nfts.len(), 2);
+            assert_eq!(breakdown.nft_uncapped_bps, 500);
+            assert_eq!(breakdown.nft_bps, 500);
+
+            let mut config = contract.get_nft_boost_config();
+            config.rarity_enabled = false;
+            config.max_boost_bps = 250;
+            contract.set_nft_boost_config(config.clone()).unwrap();
+            let (capped, uncapped, _) = contract.fetch_nft_boost(alice);
+            assert_eq!(uncapped, 300);
+            assert_eq!(capped, 250);
+
+            config.max_nfts_counted = 1;
+            contract.set_nft_boost_config(config).unwrap();
+            let (capped, uncapped, items) = contract.fetch_nft_boost(alice);
+            assert_eq!(items.len(), 1);
+            assert_eq!((capped, uncapped), (200, 200));
+
+            let invalid = [
+                NftBoostConfig { max_nfts_counted: 0, ..Default::default() },
+                NftBoostConfig { max_nfts_counted: MAX_NFTS_COUNTED + 1, ..Default::default() },
+                NftBoostConfig { max_boost_bps: MAX_NFT_BOOST_BPS + 1, ..Default::default() },
+                NftBoostConfig { tier_boost_bps: [10, 25, 50, 75, 100, 150, 600], ..Default::default() },
+                NftBoostConfig { rarity_multiplier_bps: [9999, 11000, 12500, 15000, 20000], ..Default::default() },
+            ];
+            for config in invalid {
+                assert_eq!(contract.set_nft_boost_config(config), Err(StakingError::InvalidPoolConfig));
+            }
+
+            // NFTs transferidos: o ICO informa a lista vazia e o boost some
+            ink::env::test::set_caller::<Env>(accounts.django);
+            assert_eq!(contract.sync_nft_holdings(alice, Vec::new()), Ok(()));
+            let breakdown = contract.boost_breakdown(alice);
+            assert_eq!(breakdown.nft_bps, 0);
+            assert_eq!(breakdown.total_bps, 0);
+            assert_eq!(breakdown.nft_cap_bps, 250);
+            assert!(contract.get_nft_holdings(alice).is_empty());
+        }
+
+        #[ink::test]
+        fn position_transfer_respects_approvals() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(0);
+            let id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+
+            assert_eq!(contract.owner_of(id), Some(accounts.alice));
+            assert_eq!(contract.balance_of(accounts.alice), 1);
+            assert_eq!(contract.owners_token_by_index(accounts.alice, 0), Some(id));
+
+            // Bob não aprovado
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.transfer_position(accounts.bob, id), Err(StakingError::NotApproved));
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.approve(accounts.bob, Some(id), true), Ok(()));
+            assert!(contract.allowance(accounts.alice, accounts.bob, Some(id)));
+            assert!(!contract.allowance(accounts.alice, accounts.bob, None));
+
+            // Antes da primeira janela (30 dias) nada é liquidado: o accrual segue com a posição
+            ink::env::test::set_block_timestamp::<Env>(10 * SECONDS_PER_DAY);
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.transfer_position(accounts.django, id), Ok(0));
+            assert_eq!(contract.owner_of(id), Some(accounts.django));
+            assert_eq!(contract.positions.get(id).unwrap().last_reward_time, 0);
+            assert_eq!(contract.balance_of(accounts.alice), 0);
+            assert_eq!(contract.balance_of(accounts.django), 1);
+            assert!(!contract.allowance(accounts.django, accounts.bob, Some(id)));
+
+            // Apenas o marketplace configurado pode usar a rota de venda
+            assert_eq!(
+                contract.marketplace_transfer_position(accounts.django, accounts.bob, id),
+                Err(StakingError::Unauthorized)
+            );
+        }
+
+        #[ink::test]
+        fn portfolio_is_paginated_and_aggregates_follow_transfers() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            ink::env::test::set_block_timestamp::<Env>(0);
+            let lunes_id = insert_position(&mut contract, accounts.alice, PoolType::DonLunes, 0);
+            let fiapo_id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            assert_eq!(contract.get_stats().total_stakers, 2);
+            assert_eq!(contract.total_stakers(), 1);
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.transfer_position(accounts.bob, lunes_id), Ok(0));
+
+            let alice = contract.get_user_aggregates(accounts.alice);
+            assert_eq!(alice.staked_per_pool, [0, 0, 1_000 * SCALE]);
+            assert_eq!(alice.positions_per_pool, [0, 0, 1]);
+            assert_eq!(contract.get_user_aggregates(accounts.bob).staked_per_pool, [0, 1_000 * SCALE, 0]);
+            assert_eq!(contract.get_stats().total_stakers, 2);
+            assert_eq!(contract.total_stakers(), 2);
+
+            // Índice por usuário: a última posição ocupa a vaga da transferida
+            assert_eq!(contract.owners_token_by_index(accounts.alice, 0), Some(fiapo_id));
+            assert_eq!(contract.owners_token_by_index(accounts.alice, 1), None);
+            assert_eq!(contract.get_user_positions(accounts.bob), vec![lunes_id]);
+
+            ink::env::test::set_block_timestamp::<Env>(10 * SECONDS_PER_DAY);
+            let portfolio = contract.get_user_portfolio(accounts.alice, 0, 10);
+            assert_eq!(portfolio.total_positions, 1);
+            assert_eq!(portfolio.positions.len(), 1);
+
+            let entry = &portfolio.positions[0];
+            assert_eq!(entry.position.id, fiapo_id);
+            assert_eq!(entry.pending_rewards, contract.pending_rewards(fiapo_id));
+            assert!(entry.pending_rewards > 0);
+            assert_eq!(entry.unlock_at, 90 * SECONDS_PER_DAY);
+            // Don Fiapo: 6% do principal antes do período mínimo
+            assert_eq!(entry.early_exit_penalty, 60 * SCALE);
+
+            assert!(contract.get_user_portfolio(accounts.alice, 1, 10).positions.is_empty());
+        }
+
+        #[ink::test]
+        fn penalty_curves_scale_early_exit_penalty() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let fiapo_id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            let lunes_id = insert_position(&mut contract, accounts.alice, PoolType::DonLunes, 0);
+
+            // Cliff (padrão): 6% integral até o dia 90
+            ink::env::test::set_block_timestamp::<Env>(45 * SECONDS_PER_DAY);
+            let preview = contract.unstake_preview(fiapo_id).unwrap();
+            assert_eq!(preview.penalty_scale_bps, 10000);
+            assert_eq!(preview.principal_penalty, 60 * SCALE);
+
+            // Linear: metade do caminho até a maturidade, metade da penalidade
+            assert_eq!(contract.set_penalty_curve(2, PenaltyCurve::Linear), Ok(()));
+            let preview = contract.unstake_preview(fiapo_id).unwrap();
+            assert_eq!(preview.penalty_scale_bps, 5000);
+            assert_eq!(preview.principal_penalty, 30 * SCALE);
+            assert_eq!(preview.split, PenaltySplit {
+                team: 3 * SCALE,
+                rewards_fund: 12 * SCALE,
+                burn: 0,
+                staking: 15 * SCALE,
+            });
+            assert_eq!(
+                preview.net_amount,
+                preview.principal - preview.principal_penalty + preview.rewards
+            );
+
+            // Degraus: 8% até o dia 30, 4% até o dia 60
+            let steps = vec![
+                PenaltyStep { from_day: 0, scale_bps: 10000 },
+                PenaltyStep { from_day: 30, scale_bps: 5000 },
+            ];
+            assert_eq!(contract.set_penalty_curve(1, PenaltyCurve::Steps(steps)), Ok(()));
+            assert_eq!(contract.unstake_preview(lunes_id).unwrap().principal_penalty, 40 * SCALE);
+
+            ink::env::test::set_block_timestamp::<Env>(60 * SECONDS_PER_DAY);
+            let preview = contract.unstake_preview(lunes_id).unwrap();
+            assert!(!preview.is_early);
+            assert_eq!(preview.principal_penalty, 0);
+
+            let unordered = vec![
+                PenaltyStep { from_day: 30, scale_bps: 5000 },
+                PenaltyStep { from_day: 10, scale_bps: 8000 },
+            ];
+            assert_eq!(
+                contract.set_penalty_curve(1, PenaltyCurve::Steps(unordered)),
+                Err(StakingError::InvalidPoolConfig)
+            );
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.set_penalty_curve(1, PenaltyCurve::Linear), Err(StakingError::Unauthorized));
+        }
+
+        #[ink::test]
+        fn burn_exit_requires_fixed_usdt_fee() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let burn_id = insert_position(&mut contract, accounts.alice, PoolType::DonBurn, 0);
+            let fiapo_id = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            ink::env::test::set_block_timestamp::<Env>(5 * SECONDS_PER_DAY);
+            let preview = contract.unstake_preview(burn_id).unwrap();
+            assert_eq!(preview.fixed_fee_usdt_cents, DEFAULT_BURN_EXIT_FEE_USDT_CENTS);
+            assert!(!preview.fixed_fee_settled);
+            assert_eq!(contract.request_unstake(burn_id), Err(StakingError::ExitFeeNotSettled));
+
+            // Sem oracle configurado o pagamento em USDT não é confirmado
+            assert_eq!(
+                contract.pay_exit_fee_oracle(burn_id, String::from("0xabc")),
+                Err(StakingError::OraclePaymentNotConfirmed)
+            );
+            assert_eq!(contract.pay_exit_fee_lusdt(fiapo_id), Err(StakingError::ExitFeeNotRequired));
+
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.pay_exit_fee_lusdt(burn_id), Err(StakingError::NotPositionOwner));
+
+            // Após a maturidade a parte fixa deixa de ser exigida
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            ink::env::test::set_block_timestamp::<Env>(30 * SECONDS_PER_DAY);
+            assert_eq!(contract.unstake_preview(burn_id).unwrap().fixed_fee_usdt_cents, 0);
+            assert_eq!(contract.pay_exit_fee_lusdt(burn_id), Err(StakingError::ExitFeeNotRequired));
+        }
+
+        #[ink::test]
+        fn locks_decay_voting_power_and_boost_apy() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+            let locked = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            let unlocked = insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+
+            ink::env::test::set_caller::<Env>(accounts.alice);
+            assert_eq!(contract.lock_position(locked, 0), Err(StakingError::InvalidLockDuration));
+            assert_eq!(contract.lock_position(locked, MAX_LOCK_DAYS + 1), Err(StakingError::InvalidLockDuration));
+            assert_eq!(contract.lock_position(locked, 365), Ok(365 * SECONDS_PER_DAY));
+            let lock = contract.get_lock(locked).unwrap();
+            assert_eq!((lock.apy_multiplier_bps, lock.voting_multiplier_bps), (15000, 15000));
+
+            // Voto decai linearmente até o fim do lock
+            assert_eq!(contract.voting_power_of(accounts.alice, 0), 1_500 * SCALE);
+            assert_eq!(contract.voting_power_of(accounts.alice, 365 * SECONDS_PER_DAY / 2), 750 * SCALE);
+            assert_eq!(contract.voting_power_of(accounts.alice, 365 * SECONDS_PER_DAY), 0);
+
+            ink::env::test::set_block_timestamp::<Env>(10 * SECONDS_PER_DAY);
+            let base = contract.pending_rewards(unlocked);
+            assert_eq!(contract.pending_rewards(locked), base * 3 / 2);
+
+            ink::env::test::set_block_timestamp::<Env>(100 * SECONDS_PER_DAY);
+            assert_eq!(contract.request_unstake(locked), Err(StakingError::PositionLocked));
+            assert_eq!(contract.lock_position(locked, 30), Err(StakingError::LockCannotShorten));
+
+            // Extensão mantém o início e sobe de faixa pela duração total
+            let (before, unlocked_before) = (contract.pending_rewards(locked), contract.pending_rewards(unlocked));
+            assert_eq!(contract.lock_position(locked, 730), Ok(830 * SECONDS_PER_DAY));
+            let lock = contract.get_lock(locked).unwrap();
+            assert_eq!(lock.start, 0);
+            assert_eq!(lock.apy_multiplier_bps, 17500);
+
+            // A extensão não reescreve o histórico do poder de voto
+            assert_eq!(contract.voting_power_of(accounts.alice, 50 * SECONDS_PER_DAY), 1_500 * SCALE * 315 / 365);
+            assert_eq!(contract.voting_power_of(accounts.alice, 100 * SECONDS_PER_DAY), 2_500 * SCALE * 730 / 830);
+
+            // O multiplicador novo só vale a partir da extensão
+            assert_eq!(contract.pending_rewards(locked), before);
+            ink::env::test::set_block_timestamp::<Env>(110 * SECONDS_PER_DAY);
+            let base_delta = contract.pending_rewards(unlocked) - unlocked_before;
+            assert_eq!(contract.pending_rewards(locked) - before, base_delta * 7 / 4);
+
+            let tiers = vec![
+                LockTier { min_days: 90, apy_multiplier_bps: 11000, voting_multiplier_bps: 5000 },
+                LockTier { min_days: 30, apy_multiplier_bps: 10500, voting_multiplier_bps: 2500 },
+            ];
+            assert_eq!(contract.set_lock_tiers(tiers.clone()), Err(StakingError::InvalidPoolConfig));
+            ink::env::test::set_caller::<Env>(accounts.bob);
+            assert_eq!(contract.set_lock_tiers(tiers), Err(StakingError::Unauthorized));
+        }
+
+        #[ink::test]
+        fn stake_checkpoints_snapshot_per_block() {
+            let accounts = ink::env::test::default_accounts::<Env>();
+            let mut contract = FiapoStaking::new(accounts.charlie);
+
+            ink::env::test::advance_block::<Env>();
+            insert_position(&mut contract, accounts.alice, PoolType::DonFiapo, 0);
+            contract.total_staked_per_pool[2] = 1_000 * SCALE;
+            contract.checkpoint_total_staked();
+
+            ink::env::test::advance_block::<Env>();
+            insert_position(&mut contract, accounts.alice, PoolType::DonBurn, 0);
+            contract.remove_user_stake(accounts.alice, PoolType::DonFiapo, 1_000 * SCALE);
+            contract.add_user_stake(accounts.bob, PoolType::DonFiapo, 1_000 * SCALE);
+
+            assert_eq!(contract.staked_balance_at(accounts.alice, 0), 0);
+            assert_eq!(contract.staked_balance_at(accounts.alice, 1), 1_000 * SCALE);
+            // Alterações no mesmo bloco resultam no último valor
+            assert_eq!(contract.staked_balance_at(accounts.alice, 2), 1_000 * SCALE);
+            assert_eq!(contract.stake_checkpoint_counts.get(Some(accounts.alice)), Some(2));
+            assert_eq!(contract.staked_balance_at(accounts.bob, 1), 0);
+            assert_eq!(contract.staked_balance_at(accounts.bob, 5), 1_000 * SCALE);
+            assert_eq!(contract.total_staked_at(0), 0);
+            assert_eq!(contract.total_staked_at(9), 1_000 * SCALE);
+        }
     }
 }
 
@@ -13,6 +13,7 @@ mod fiapo_timelock {
     use super::*;
     use ink::prelude::{string::String, vec::Vec};
     use ink::storage::Mapping;
+    use fiapo_logics::traits::timelock::dispatch_raw;
 
     /// Constantes de tempo
     pub const HOUR: u64 = 3600 * 1000; // em ms
@@ -40,6 +41,8 @@ mod fiapo_timelock {
         InvalidDelay,
         OperationAlreadyScheduled,
         InvalidOperation,
+        /// A chamada agendada falhou no contrato alvo
+        ExecutionFailed,
     }
 
     /// Tipos de operação que requerem timelock
@@ -237,6 +240,19 @@ mod fiapo_timelock {
             }
         }
 
+        /// Status efetivo no bloco atual: `Scheduled` vira `ReadyToExecute` após o
+        /// delay e `Expired` após `expires_at`, mesmo antes de alguém executar
+        #[ink(message)]
+        pub fn get_operation_status(&self, operation_id: u64) -> Option<OperationStatus> {
+            let op = self.operations.get(operation_id)?;
+            let current_time = self.env().block_timestamp();
+            Some(match op.status {
+                OperationStatus::Scheduled if current_time >= op.expires_at => OperationStatus::Expired,
+                OperationStatus::Scheduled if current_time >= op.executable_at => OperationStatus::ReadyToExecute,
+                status => status,
+            })
+        }
+
         #[ink(message)]
         pub fn get_stats(&self) -> (u64, u64, u64) {
             (self.total_scheduled, self.total_executed, self.total_cancelled)
@@ -338,14 +354,19 @@ mod fiapo_timelock {
             self.operations.insert(operation_id, &operation);
             self.total_executed = self.total_executed.saturating_add(1);
 
+            // Executa a chamada agendada (selector + args SCALE); sem call_data é apenas sinalização.
+            // Em caso de falha o erro reverte a transação e a operação pode ser re-tentada até expirar.
+            if !operation.call_data.is_empty()
+                && !dispatch_raw(operation.target_contract, &operation.call_data, operation.value)
+            {
+                return Err(TimelockError::ExecutionFailed);
+            }
+
             Self::env().emit_event(OperationExecuted {
                 operation_id,
                 executor: caller,
             });
 
-            // A execução real seria feita via cross-contract call
-            // usando operation.target_contract, operation.call_data, operation.value
-
             Ok(())
         }
 
@@ -476,6 +497,55 @@ mod fiapo_timelock {
 
             let op = contract.get_operation(op_id).unwrap();
             assert_eq!(op.status, OperationStatus::Cancelled);
+            assert_eq!(contract.get_operation_status(op_id), Some(OperationStatus::Cancelled));
+        }
+
+        #[ink::test]
+        fn operation_status_tracks_delay_and_expiry() {
+            let mut contract = FiapoTimelock::new(vec![]);
+            let target = AccountId::from([1u8; 32]);
+            let op_id = contract.schedule(
+                OperationType::ConfigChange,
+                target,
+                vec![],
+                0,
+                String::from("Test"),
+                Some(HOUR),
+            ).unwrap();
+            let op = contract.get_operation(op_id).unwrap();
+
+            assert_eq!(contract.get_operation_status(op_id), Some(OperationStatus::Scheduled));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(op.executable_at);
+            assert_eq!(contract.get_operation_status(op_id), Some(OperationStatus::ReadyToExecute));
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(op.expires_at);
+            assert_eq!(contract.get_operation_status(op_id), Some(OperationStatus::Expired));
+            assert_eq!(contract.get_operation_status(op_id + 1), None);
+        }
+
+        #[ink::test]
+        fn dispatch_output_detects_reverted_err() {
+            use fiapo_logics::traits::timelock::DispatchOutput;
+            use scale::{Decode, Encode};
+
+            // Saída de uma mensagem revertida: MessageResult Ok com Result interno Err
+            let reverted: ink::MessageResult<Result<(), TimelockError>> =
+                Ok(Err(TimelockError::Unauthorized));
+            let output = ink::MessageResult::<DispatchOutput>::decode(&mut &reverted.encode()[..])
+                .unwrap()
+                .unwrap();
+            assert!(!output.succeeded());
+
+            let ok: ink::MessageResult<Result<(), TimelockError>> = Ok(Ok(()));
+            let output = ink::MessageResult::<DispatchOutput>::decode(&mut &ok.encode()[..])
+                .unwrap()
+                .unwrap();
+            assert!(output.succeeded());
+
+            let unit: ink::MessageResult<()> = Ok(());
+            let output = ink::MessageResult::<DispatchOutput>::decode(&mut &unit.encode()[..])
+                .unwrap()
+                .unwrap();
+            assert!(output.succeeded());
         }
     }
 }
@@ -113,6 +113,16 @@ pub trait IPSP22Burnable {
     fn burn_from(&mut self, from: AccountId, amount: Balance) -> PSP22Result<()>;
 }
 
+/// PSP22 Votes Extension
+///
+/// Historical balances recorded as per-block checkpoints (governance snapshots).
+#[ink::trait_definition]
+pub trait IPSP22Votes {
+    /// Returns the balance of `owner` at the end of block `block`
+    #[ink(message)]
+    fn balance_of_at(&self, owner: AccountId, block: u32) -> Balance;
+}
+
 /// Staking Contract Interface
 #[ink::trait_definition]
 pub trait IStaking {
@@ -158,15 +168,14 @@ pub trait IICO {
 }
 
 /// Governance Contract Interface
+///
+/// Proposal creation and voting require fee payments specific to the
+/// governance contract; this interface covers the permissionless lifecycle steps.
 #[ink::trait_definition]
 pub trait IGovernance {
-    /// Creates a new proposal
-    #[ink(message)]
-    fn create_proposal(&mut self, description: String) -> PSP22Result<u64>;
-    
-    /// Casts a vote on a proposal
+    /// Tallies a proposal after its voting period (approved or rejected)
     #[ink(message)]
-    fn vote(&mut self, proposal_id: u64, support: bool) -> PSP22Result<()>;
+    fn finalize_proposal(&mut self, proposal_id: u64) -> PSP22Result<()>;
     
     /// Executes an approved proposal
     #[ink(message)]
@@ -358,6 +367,44 @@ impl StakingPoolType {
     }
 }
 
+/// Origem de um boost registrado no Boost Registry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum BoostSource {
+    /// Referidos ativos (FiapoAffiliate)
+    Affiliate = 0,
+    /// Prêmio de boost da Royal Wheel
+    SpinGame = 1,
+    /// Boost de mineração do ICO
+    NftMining = 2,
+    /// Volume de queima
+    Burn = 3,
+}
+
+impl BoostSource {
+    pub const COUNT: u8 = 4;
+
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(BoostSource::Affiliate),
+            1 => Some(BoostSource::SpinGame),
+            2 => Some(BoostSource::NftMining),
+            3 => Some(BoostSource::Burn),
+            _ => None,
+        }
+    }
+}
+
+/// Onde o boost agregado é aplicado
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum BoostTarget {
+    /// APY de staking (FiapoStaking)
+    StakingApy = 0,
+    /// Taxa de mineração dos NFTs (FiapoICO)
+    Mining = 1,
+}
+
 // ==================== Security Module ====================
 
 /// Erros de segurança compartilhados
@@ -0,0 +1,545 @@
+//! # Fiapo Treasury Contract
+//!
+//! Tesouraria controlada pela governança do ecossistema Don Fiapo.
+//! Propostas de gasto aprovadas criam concessões (grants) pagas ao longo do
+//! tempo, por marcos ou em fluxo linear a partir da criação. Uma proposta
+//! posterior pode recuperar (claw back) a parte ainda não liberada.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod fiapo_treasury {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use fiapo_logics::traits::psp22::{PSP22, PSP22Ref};
+
+    /// Limite de marcos por concessão
+    pub const MAX_MILESTONES: usize = 12;
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum TreasuryError {
+        Unauthorized,
+        InvalidGrant,
+        InsufficientFunds,
+        GrantNotFound,
+        GrantRevoked,
+        NothingToRelease,
+        AssetNotConfigured,
+        TransferFailed,
+    }
+
+    /// Ativos mantidos pela tesouraria (mesma codificação do staking)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum Asset {
+        /// Token FIAPO (fiapo-core)
+        Fiapo = 0,
+        /// LUNES nativo da chain
+        Lunes = 1,
+        /// LUSDT (PSP22)
+        Lusdt = 2,
+    }
+
+    impl Asset {
+        pub fn index(&self) -> usize {
+            match self {
+                Asset::Fiapo => 0,
+                Asset::Lunes => 1,
+                Asset::Lusdt => 2,
+            }
+        }
+    }
+
+    /// Parcela liberada `after` ms após o início da concessão
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Milestone {
+        pub after: u64,
+        pub amount: Balance,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub enum VestingSchedule {
+        /// Parcelas fixas; a soma deve ser igual ao total
+        Milestones(Vec<Milestone>),
+        /// Fluxo linear por `duration` ms, nada antes de `cliff`
+        Linear { cliff: u64, duration: u64 },
+    }
+
+    impl VestingSchedule {
+        /// Valor liberado `elapsed` ms após o início
+        pub fn vested(&self, total: Balance, elapsed: u64) -> Balance {
+            match self {
+                VestingSchedule::Milestones(milestones) => milestones.iter()
+                    .filter(|milestone| milestone.after <= elapsed)
+                    .fold(0, |acc: Balance, milestone| acc.saturating_add(milestone.amount))
+                    .min(total),
+                VestingSchedule::Linear { cliff, duration } => {
+                    if elapsed < *cliff {
+                        0
+                    } else if elapsed >= *duration {
+                        total
+                    } else {
+                        total.saturating_mul(elapsed as u128)
+                            .checked_div(*duration as u128)
+                            .unwrap_or(0)
+                    }
+                }
+            }
+        }
+
+        pub fn is_valid(&self, total: Balance) -> bool {
+            match self {
+                VestingSchedule::Milestones(milestones) => {
+                    !milestones.is_empty()
+                        && milestones.len() <= MAX_MILESTONES
+                        && milestones.iter().all(|milestone| milestone.amount > 0)
+                        && milestones.iter().fold(0, |acc: Balance, milestone| acc.saturating_add(milestone.amount)) == total
+                }
+                VestingSchedule::Linear { cliff, duration } => *duration > 0 && cliff <= duration,
+            }
+        }
+    }
+
+    /// Concessão aprovada pela governança
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+    pub struct Grant {
+        pub id: u64,
+        pub recipient: AccountId,
+        pub asset: Asset,
+        pub total: Balance,
+        /// Já pago ao beneficiário
+        pub released: Balance,
+        pub schedule: VestingSchedule,
+        pub start: u64,
+        /// Momento do claw back; a liberação para nesse ponto
+        pub revoked_at: Option<u64>,
+        pub clawed_back: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GrantCreated {
+        #[ink(topic)]
+        grant_id: u64,
+        #[ink(topic)]
+        recipient: AccountId,
+        asset: Asset,
+        total: Balance,
+        start: u64,
+    }
+
+    #[ink(event)]
+    pub struct GrantReleased {
+        #[ink(topic)]
+        grant_id: u64,
+        #[ink(topic)]
+        recipient: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct GrantClawedBack {
+        #[ink(topic)]
+        grant_id: u64,
+        amount: Balance,
+    }
+
+    #[ink(storage)]
+    pub struct FiapoTreasury {
+        owner: AccountId,
+        core_contract: AccountId,
+        lusdt_contract: Option<AccountId>,
+        /// Governança: autorizada a gastar
+        governance_contract: Option<AccountId>,
+        /// Timelock: executa os gastos aprovados pela governança
+        timelock_contract: Option<AccountId>,
+        grants: Mapping<u64, Grant>,
+        next_grant_id: u64,
+        /// Índice por beneficiário: (beneficiário, posição) -> concessão
+        recipient_grants: Mapping<(AccountId, u32), u64>,
+        recipient_grant_count: Mapping<AccountId, u32>,
+        /// Valor de concessões ativas ainda não pago, por ativo
+        committed: [Balance; 3],
+        total_released: [Balance; 3],
+    }
+
+    impl FiapoTreasury {
+        #[ink(constructor)]
+        pub fn new(core_contract: AccountId) -> Self {
+            Self {
+                owner: Self::env().caller(),
+                core_contract,
+                lusdt_contract: None,
+                governance_contract: None,
+                timelock_contract: None,
+                grants: Mapping::default(),
+                next_grant_id: 1,
+                recipient_grants: Mapping::default(),
+                recipient_grant_count: Mapping::default(),
+                committed: [0; 3],
+                total_released: [0; 3],
+            }
+        }
+
+        /// Depósito de LUNES; FIAPO e LUSDT chegam por `transfer` direto
+        #[ink(message, payable)]
+        pub fn deposit(&mut self) {
+            Self::env().emit_event(Deposited {
+                from: self.env().caller(),
+                amount: self.env().transferred_value(),
+            });
+        }
+
+        /// Cria uma concessão com o saldo livre da tesouraria (apenas governança)
+        #[ink(message)]
+        pub fn create_grant(
+            &mut self,
+            recipient: AccountId,
+            asset: Asset,
+            total: Balance,
+            schedule: VestingSchedule,
+        ) -> Result<u64, TreasuryError> {
+            self.ensure_governance()?;
+            if total == 0 || !schedule.is_valid(total) {
+                return Err(TreasuryError::InvalidGrant);
+            }
+            if self.available(asset)? < total {
+                return Err(TreasuryError::InsufficientFunds);
+            }
+
+            let grant_id = self.next_grant_id;
+            let start = self.env().block_timestamp();
+            self.grants.insert(grant_id, &Grant {
+                id: grant_id,
+                recipient,
+                asset,
+                total,
+                released: 0,
+                schedule,
+                start,
+                revoked_at: None,
+                clawed_back: 0,
+            });
+            self.next_grant_id = grant_id.saturating_add(1);
+            self.committed[asset.index()] = self.committed[asset.index()].saturating_add(total);
+
+            let index = self.recipient_grant_count.get(recipient).unwrap_or(0);
+            self.recipient_grants.insert((recipient, index), &grant_id);
+            self.recipient_grant_count.insert(recipient, &index.saturating_add(1));
+
+            Self::env().emit_event(GrantCreated { grant_id, recipient, asset, total, start });
+            Ok(grant_id)
+        }
+
+        /// Paga ao beneficiário o valor já liberado (qualquer um pode acionar)
+        #[ink(message)]
+        pub fn release(&mut self, grant_id: u64) -> Result<Balance, TreasuryError> {
+            let mut grant = self.grants.get(grant_id).ok_or(TreasuryError::GrantNotFound)?;
+            let amount = self.releasable(&grant);
+            if amount == 0 {
+                return Err(TreasuryError::NothingToRelease);
+            }
+
+            let index = grant.asset.index();
+            grant.released = grant.released.saturating_add(amount);
+            self.grants.insert(grant_id, &grant);
+            self.committed[index] = self.committed[index].saturating_sub(amount);
+            self.total_released[index] = self.total_released[index].saturating_add(amount);
+            self.transfer_asset(grant.asset, grant.recipient, amount)?;
+
+            Self::env().emit_event(GrantReleased { grant_id, recipient: grant.recipient, amount });
+            Ok(amount)
+        }
+
+        /// Recupera a parte ainda não liberada (apenas governança). O que já
+        /// foi liberado continua disponível ao beneficiário.
+        #[ink(message)]
+        pub fn claw_back(&mut self, grant_id: u64) -> Result<Balance, TreasuryError> {
+            self.ensure_governance()?;
+            let mut grant = self.grants.get(grant_id).ok_or(TreasuryError::GrantNotFound)?;
+            if grant.revoked_at.is_some() {
+                return Err(TreasuryError::GrantRevoked);
+            }
+
+            let now = self.env().block_timestamp();
+            let vested = grant.schedule.vested(grant.total, now.saturating_sub(grant.start));
+            let amount = grant.total.saturating_sub(vested);
+            grant.revoked_at = Some(now);
+            grant.clawed_back = amount;
+            self.grants.insert(grant_id, &grant);
+            let index = grant.asset.index();
+            self.committed[index] = self.committed[index].saturating_sub(amount);
+
+            Self::env().emit_event(GrantClawedBack { grant_id, amount });
+            Ok(amount)
+        }
+
+        // ==================== Views ====================
+
+        #[ink(message)]
+        pub fn get_grant(&self, grant_id: u64) -> Option<Grant> {
+            self.grants.get(grant_id)
+        }
+
+        #[ink(message)]
+        pub fn grant_count(&self) -> u64 {
+            self.next_grant_id.saturating_sub(1)
+        }
+
+        /// Total liberado até agora (ou até o claw back)
+        #[ink(message)]
+        pub fn vested_amount(&self, grant_id: u64) -> Balance {
+            self.grants.get(grant_id).map(|grant| self.vested(&grant)).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn releasable_amount(&self, grant_id: u64) -> Balance {
+            self.grants.get(grant_id).map(|grant| self.releasable(&grant)).unwrap_or(0)
+        }
+
+        /// Concessões de um beneficiário, em ordem de criação (paginado)
+        #[ink(message)]
+        pub fn get_recipient_grants(&self, recipient: AccountId, offset: u32, limit: u32) -> Vec<Grant> {
+            let total = self.recipient_grant_count.get(recipient).unwrap_or(0);
+            let end = offset.saturating_add(limit.min(100)).min(total);
+            (offset..end)
+                .filter_map(|index| self.recipient_grants.get((recipient, index)))
+                .filter_map(|grant_id| self.grants.get(grant_id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn committed(&self, asset: Asset) -> Balance {
+            self.committed[asset.index()]
+        }
+
+        #[ink(message)]
+        pub fn total_released(&self, asset: Asset) -> Balance {
+            self.total_released[asset.index()]
+        }
+
+        /// Saldo livre para novas concessões
+        #[ink(message)]
+        pub fn available(&self, asset: Asset) -> Result<Balance, TreasuryError> {
+            Ok(self.balance_of(asset)?.saturating_sub(self.committed[asset.index()]))
+        }
+
+        #[ink(message)]
+        pub fn governance_contract(&self) -> Option<AccountId> {
+            self.governance_contract
+        }
+
+        #[ink(message)]
+        pub fn timelock_contract(&self) -> Option<AccountId> {
+            self.timelock_contract
+        }
+
+        // ==================== Setters ====================
+
+        #[ink(message)]
+        pub fn set_governance_contract(&mut self, governance: Option<AccountId>) -> Result<(), TreasuryError> {
+            if self.env().caller() != self.owner {
+                return Err(TreasuryError::Unauthorized);
+            }
+            self.governance_contract = governance;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_timelock_contract(&mut self, timelock: Option<AccountId>) -> Result<(), TreasuryError> {
+            if self.env().caller() != self.owner {
+                return Err(TreasuryError::Unauthorized);
+            }
+            self.timelock_contract = timelock;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn set_lusdt_contract(&mut self, lusdt: Option<AccountId>) -> Result<(), TreasuryError> {
+            if self.env().caller() != self.owner {
+                return Err(TreasuryError::Unauthorized);
+            }
+            self.lusdt_contract = lusdt;
+            Ok(())
+        }
+
+        // ==================== Private Helpers ====================
+
+        fn ensure_governance(&self) -> Result<(), TreasuryError> {
+            let caller = Some(self.env().caller());
+            if caller != self.governance_contract && caller != self.timelock_contract {
+                return Err(TreasuryError::Unauthorized);
+            }
+            Ok(())
+        }
+
+        fn vested(&self, grant: &Grant) -> Balance {
+            let until = grant.revoked_at.unwrap_or_else(|| self.env().block_timestamp());
+            grant.schedule.vested(grant.total, until.saturating_sub(grant.start))
+        }
+
+        fn releasable(&self, grant: &Grant) -> Balance {
+            self.vested(grant).saturating_sub(grant.released)
+        }
+
+        fn balance_of(&self, asset: Asset) -> Result<Balance, TreasuryError> {
+            let token = match asset {
+                Asset::Lunes => return Ok(self.env().balance()),
+                Asset::Fiapo => self.core_contract,
+                Asset::Lusdt => self.lusdt_contract.ok_or(TreasuryError::AssetNotConfigured)?,
+            };
+            let psp22: PSP22Ref = token.into();
+            Ok(psp22.balance_of(self.env().account_id()))
+        }
+
+        fn transfer_asset(&self, asset: Asset, to: AccountId, amount: Balance) -> Result<(), TreasuryError> {
+            let token = match asset {
+                Asset::Lunes => {
+                    return self.env().transfer(to, amount).map_err(|_| TreasuryError::TransferFailed);
+                }
+                Asset::Fiapo => self.core_contract,
+                Asset::Lusdt => self.lusdt_contract.ok_or(TreasuryError::AssetNotConfigured)?,
+            };
+            let mut psp22: PSP22Ref = token.into();
+            match psp22.transfer(to, amount) {
+                Ok(_) => Ok(()),
+                _ => Err(TreasuryError::TransferFailed),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::DefaultEnvironment;
+
+        fn setup(funds: Balance) -> (FiapoTreasury, ink::env::test::DefaultAccounts<DefaultEnvironment>) {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            let mut treasury = FiapoTreasury::new(accounts.alice);
+            treasury.set_governance_contract(Some(accounts.bob)).unwrap();
+            let contract = ink::env::account_id::<DefaultEnvironment>();
+            ink::env::test::set_account_balance::<DefaultEnvironment>(contract, funds);
+            (treasury, accounts)
+        }
+
+        #[ink::test]
+        fn linear_stream_releases_and_claws_back() {
+            let (mut treasury, accounts) = setup(1000);
+            let schedule = VestingSchedule::Linear { cliff: 100, duration: 1000 };
+
+            assert_eq!(
+                treasury.create_grant(accounts.charlie, Asset::Lunes, 600, schedule.clone()),
+                Err(TreasuryError::Unauthorized)
+            );
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let grant_id = treasury.create_grant(accounts.charlie, Asset::Lunes, 600, schedule.clone()).unwrap();
+            assert_eq!(treasury.available(Asset::Lunes), Ok(400));
+            assert_eq!(
+                treasury.create_grant(accounts.charlie, Asset::Lunes, 500, schedule),
+                Err(TreasuryError::InsufficientFunds)
+            );
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(50);
+            assert_eq!(treasury.release(grant_id), Err(TreasuryError::NothingToRelease));
+
+            let before = ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(500);
+            assert_eq!(treasury.release(grant_id), Ok(300));
+            let after = ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.charlie).unwrap();
+            assert_eq!(after - before, 300);
+
+            // Claw back aos 750 ms: 450 liberados, 150 voltam ao saldo livre
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(750);
+            assert_eq!(treasury.claw_back(grant_id), Ok(150));
+            assert_eq!(treasury.claw_back(grant_id), Err(TreasuryError::GrantRevoked));
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(2000);
+            assert_eq!(treasury.vested_amount(grant_id), 450);
+            assert_eq!(treasury.releasable_amount(grant_id), 150);
+            assert_eq!(treasury.committed(Asset::Lunes), 150);
+            assert_eq!(treasury.available(Asset::Lunes), Ok(550));
+
+            assert_eq!(treasury.release(grant_id), Ok(150));
+            assert_eq!(treasury.total_released(Asset::Lunes), 450);
+            assert_eq!(treasury.committed(Asset::Lunes), 0);
+            assert_eq!(treasury.get_recipient_grants(accounts.charlie, 0, 10).len(), 1);
+        }
+
+        #[ink::test]
+        fn milestones_validate_and_match_mirror() {
+            use fiapo_logics::traits::treasury::{MilestoneParams, VestingScheduleParams};
+            use scale::{Decode, Encode};
+
+            let (mut treasury, accounts) = setup(1000);
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+
+            let params = VestingScheduleParams::Milestones(vec![
+                MilestoneParams { after: 0, amount: 100 },
+                MilestoneParams { after: 1000, amount: 200 },
+            ]);
+            let schedule = VestingSchedule::decode(&mut &params.encode()[..]).unwrap();
+            assert_eq!(
+                treasury.create_grant(accounts.django, Asset::Lunes, 400, schedule.clone()),
+                Err(TreasuryError::InvalidGrant)
+            );
+            assert_eq!(
+                treasury.create_grant(accounts.django, Asset::Lunes, 0, VestingSchedule::Linear { cliff: 0, duration: 1 }),
+                Err(TreasuryError::InvalidGrant)
+            );
+            assert_eq!(
+                treasury.create_grant(accounts.django, Asset::Lusdt, 300, schedule.clone()),
+                Err(TreasuryError::AssetNotConfigured)
+            );
+
+            // Propostas executadas pelo Timelock também criam grants
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+            treasury.set_timelock_contract(Some(accounts.frank)).unwrap();
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.frank);
+            let grant_id = treasury.create_grant(accounts.django, Asset::Lunes, 300, schedule).unwrap();
+            assert_eq!(treasury.releasable_amount(grant_id), 100);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(999);
+            assert_eq!(treasury.release(grant_id), Ok(100));
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1000);
+            assert_eq!(treasury.releasable_amount(grant_id), 200);
+            assert_eq!(treasury.grant_count(), 1);
+        }
+
+        #[ink::test]
+        fn failed_grant_output_is_reported_as_failed_dispatch() {
+            use fiapo_logics::traits::timelock::DispatchOutput;
+            use scale::{Decode, Encode};
+
+            let (mut treasury, accounts) = setup(100);
+            ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            let schedule = VestingSchedule::Linear { cliff: 0, duration: 10 };
+
+            // Um TreasurySpend cujo create_grant reverte não pode contar como executado
+            let reverted: ink::MessageResult<_> =
+                Ok(treasury.create_grant(accounts.charlie, Asset::Lunes, 500, schedule.clone()));
+            let output = ink::MessageResult::<DispatchOutput>::decode(&mut &reverted.encode()[..])
+                .unwrap()
+                .unwrap();
+            assert!(!output.succeeded());
+
+            let created: ink::MessageResult<_> =
+                Ok(treasury.create_grant(accounts.charlie, Asset::Lunes, 50, schedule));
+            let output = ink::MessageResult::<DispatchOutput>::decode(&mut &created.encode()[..])
+                .unwrap()
+                .unwrap();
+            assert!(output.succeeded());
+        }
+    }
+}
+
+#[cfg(feature = "ink-as-dependency")]
+pub use self::fiapo_treasury::*;
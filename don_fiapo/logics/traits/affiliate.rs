@@ -9,6 +9,17 @@
 
 use fiapo_traits::{AccountId, Balance};
 
+/// Mirror of `FiapoAffiliate::AffiliateConfig` (argument of `update_config`).
+/// Field order and types MUST match the affiliate struct.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct AffiliateConfigParams {
+    pub enabled: bool,
+    pub boost_per_affiliate_bps: u32,
+    pub max_boost_bps: u32,
+    pub min_staking_for_active: Balance,
+}
+
 /// Helper for cross-contract calls to Affiliate using build_call.
 /// Uses raw selectors because FiapoAffiliate methods are standalone #[ink(message)].
 pub struct AffiliateCall;
@@ -0,0 +1,98 @@
+//! # Boost Registry Helper
+//! 
+//! Pure ink! helper for cross-contract calls to the Boost Registry contract.
+//! No OpenBrush dependency.
+//! 
+//! NOTE: FiapoBoostRegistry methods are standalone (not via trait), so we use
+//! build_call with explicit selectors for cross-contract calls.
+
+use fiapo_traits::{AccountId, BoostSource, BoostTarget};
+
+/// Helper for cross-contract calls to the Boost Registry using build_call.
+pub struct BoostRegistryCall;
+
+impl BoostRegistryCall {
+    /// Calls `get_boost(user, target) -> u32` on the registry.
+    /// Returns 0 if the call fails.
+    pub fn get_boost(registry_addr: AccountId, user: AccountId, target: BoostTarget) -> u32 {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_boost");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(registry_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(user)
+                    .push_arg(target)
+            )
+            .returns::<u32>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(val)) => val,
+            _ => 0,
+        }
+    }
+
+    /// Calls `get_boost_over(user, target, from, to) -> u32` on the registry.
+    /// Returns 0 if the call fails.
+    pub fn get_boost_over(registry_addr: AccountId, user: AccountId, target: BoostTarget, from: u64, to: u64) -> u32 {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_boost_over");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(registry_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(user)
+                    .push_arg(target)
+                    .push_arg(from)
+                    .push_arg(to)
+            )
+            .returns::<u32>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(val)) => val,
+            _ => 0,
+        }
+    }
+
+    /// Calls `post_boost(user, source, target, bps, expires_at)` on the registry.
+    /// Returns `true` if the registry accepted the boost.
+    pub fn post_boost(
+        registry_addr: AccountId,
+        user: AccountId,
+        source: BoostSource,
+        target: BoostTarget,
+        bps: u32,
+        expires_at: u64,
+    ) -> bool {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("post_boost");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(registry_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(user)
+                    .push_arg(source)
+                    .push_arg(target)
+                    .push_arg(bps)
+                    .push_arg(expires_at)
+            )
+            .returns::<Result<(), u8>>()
+            .try_invoke();
+
+        matches!(result, Ok(Ok(Ok(()))))
+    }
+}
@@ -0,0 +1,82 @@
+//! # ICO Helper
+//! 
+//! Pure ink! helper for read-only cross-contract calls to the ICO contract.
+//! No OpenBrush dependency.
+//! 
+//! NOTE: FiapoICO methods are standalone (not via trait), so we use
+//! build_call with explicit selectors for cross-contract calls.
+
+use fiapo_traits::AccountId;
+use ink::prelude::vec::Vec;
+
+/// Mirror of `FiapoICO::NFTData` for decoding `get_nft` results.
+/// Field order and types MUST match the ICO struct (fieldless enums decode as u8).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct IcoNftInfo {
+    pub id: u64,
+    /// NFTTier (0 = Free ... 6 = Tier7)
+    pub tier: u8,
+    pub owner: AccountId,
+    pub created_at: u64,
+    pub tokens_mined: u128,
+    pub tokens_claimed: u128,
+    pub last_mining_timestamp: u64,
+    pub active: bool,
+    /// VisualRarity (0 = Common ... 4 = Legendary)
+    pub visual_rarity: u8,
+    pub evolution_count: u8,
+    pub mining_bonus_bps: u16,
+    pub evolved_from: Vec<u64>,
+}
+
+/// Helper for cross-contract calls to the ICO using build_call.
+pub struct IcoCall;
+
+impl IcoCall {
+    /// Calls `get_user_nfts(owner) -> Vec<u64>` on the ICO contract.
+    pub fn get_user_nfts(ico_addr: AccountId, owner: AccountId) -> Vec<u64> {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_user_nfts");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(ico_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(owner)
+            )
+            .returns::<Vec<u64>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(ids)) => ids,
+            _ => Vec::new(),
+        }
+    }
+
+    /// Calls `get_nft(nft_id) -> Option<NFTData>` on the ICO contract.
+    pub fn get_nft(ico_addr: AccountId, nft_id: u64) -> Option<IcoNftInfo> {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_nft");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(ico_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(nft_id)
+            )
+            .returns::<Option<IcoNftInfo>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(nft)) => nft,
+            _ => None,
+        }
+    }
+}
@@ -8,3 +8,8 @@ pub mod rewards;
 pub mod oracle;
 pub mod psp22;
 pub mod affiliate;
+pub mod boost;
+pub mod ico;
+pub mod timelock;
+pub mod treasury;
+pub mod randomness;
@@ -3,23 +3,50 @@
 //! Pure ink! trait for type-safe cross-contract calls to the Oracle Multisig contract.
 //! No OpenBrush dependency.
 
-use fiapo_traits::AccountId;
+use fiapo_traits::{AccountId, Balance};
 use ink::prelude::string::String;
 use ink::env::DefaultEnvironment as Environment;
 
 /// Reference type for cross-contract calls to Oracle
 pub type OracleRef = ink::contract_ref!(Oracle);
 
+/// What a consumer contract settles with an oracle-confirmed payment.
+/// Must match the payment type the oracles confirmed.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PaymentPurpose {
+    /// Governance deposits and USDT fees
+    GovernanceDeposit,
+    /// Fixed part of the staking Don Burn exit fee for one position
+    StakingExitFee { position_id: u64 },
+}
+
 /// Oracle trait for cross-contract communication
 #[ink::trait_definition]
 pub trait Oracle {
-    /// Verifies if a payment has been confirmed by the oracle
+    /// Verifies if a payment has been confirmed for `purpose` and not consumed yet
     #[ink(message)]
     fn is_payment_confirmed(
         &self,
         tx_hash: String,
         user: AccountId,
         amount_cents: u64,
-        is_governance_deposit: bool,
+        purpose: PaymentPurpose,
+    ) -> bool;
+
+    /// Marks a confirmed payment as consumed so no contract can settle it again.
+    /// Only the contract registered for `purpose` may call it.
+    #[ink(message)]
+    fn consume_payment(
+        &mut self,
+        tx_hash: String,
+        user: AccountId,
+        amount_cents: u64,
+        purpose: PaymentPurpose,
     ) -> bool;
+
+    /// FIAPO price in LUSDT units (6 decimals) per whole FIAPO: median of the
+    /// fresh oracle reports, or `None` without enough of them
+    #[ink(message)]
+    fn fiapo_price(&self) -> Option<Balance>;
 }
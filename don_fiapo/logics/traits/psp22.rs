@@ -3,7 +3,7 @@
 //! Pure ink! trait references for type-safe cross-contract calls to PSP22 token contracts.
 //! Uses IPSP22 from fiapo-traits to guarantee matching selectors with fiapo-core.
 
-use fiapo_traits::{IPSP22, IPSP22Mintable, IPSP22Burnable};
+use fiapo_traits::{IPSP22, IPSP22Mintable, IPSP22Burnable, IPSP22Votes};
 use ink::env::DefaultEnvironment as Environment;
 
 /// Reference type for cross-contract calls to PSP22 (transfer, balance_of, etc.)
@@ -15,5 +15,11 @@ pub type PSP22MintableRef = ink::contract_ref!(IPSP22Mintable);
 /// Reference type for cross-contract calls to PSP22 Burnable (burn, burn_from)
 pub type PSP22BurnableRef = ink::contract_ref!(IPSP22Burnable);
 
+/// Reference type for cross-contract calls to PSP22 Votes (balance_of_at)
+pub type PSP22VotesRef = ink::contract_ref!(IPSP22Votes);
+
 // Re-export traits for convenience
-pub use fiapo_traits::{IPSP22 as PSP22, IPSP22Mintable as PSP22Mintable, IPSP22Burnable as PSP22Burnable};
+pub use fiapo_traits::{
+    IPSP22 as PSP22, IPSP22Mintable as PSP22Mintable, IPSP22Burnable as PSP22Burnable,
+    IPSP22Votes as PSP22Votes,
+};
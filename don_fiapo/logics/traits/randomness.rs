@@ -0,0 +1,50 @@
+//! # Randomness Beacon Trait Definition
+//!
+//! Pure ink! trait for type-safe cross-contract calls to the Randomness Beacon.
+//! No OpenBrush dependency.
+//!
+//! Two-phase flow: a consumer calls `request_randomness` and stores the returned
+//! round; once that round is finalized it reads `randomness(round)` and derives
+//! its own value with `derive_random`. A failed round (no entropy) must be re-requested.
+
+use ink::env::DefaultEnvironment as Environment;
+
+/// Reference type for cross-contract calls to the Randomness Beacon
+pub type RandomnessRef = ink::contract_ref!(Randomness);
+
+/// Randomness Beacon trait for cross-contract communication
+#[ink::trait_definition]
+pub trait Randomness {
+    /// Registers a request and returns the future round that will serve it
+    #[ink(message)]
+    fn request_randomness(&mut self) -> u64;
+
+    /// Seed of a finalized round, `None` while pending or if it failed
+    #[ink(message)]
+    fn randomness(&self, round: u64) -> Option<[u8; 32]>;
+
+    /// Whether the round was finalized without any entropy
+    #[ink(message)]
+    fn round_failed(&self, round: u64) -> bool;
+}
+
+/// Consumer-specific value from a round seed (`context` separates requests)
+pub fn derive_random<C: scale::Encode>(seed: &[u8; 32], context: &C) -> [u8; 32] {
+    let mut output = [0u8; 32];
+    ink::env::hash_encoded::<ink::env::hash::Blake2x256, _>(&(seed, context), &mut output);
+    output
+}
+
+/// Uniform-ish value in `0..max` from a derived value (0 if `max == 0`)
+pub fn random_below(random: &[u8; 32], max: u64) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&random[..8]);
+    u64::from_le_bytes(bytes).checked_rem(max).unwrap_or(0)
+}
+
+/// Same as `random_below` for 128-bit ranges (cumulative lottery weights)
+pub fn random_below_u128(random: &[u8; 32], max: u128) -> u128 {
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&random[..16]);
+    u128::from_le_bytes(bytes).checked_rem(max).unwrap_or(0)
+}
@@ -8,6 +8,33 @@
 
 use fiapo_traits::{AccountId, Balance};
 
+/// Mirror of `FiapoRewards::ScoringWeights` (argument of `update_scoring_weights`).
+/// Field order and types MUST match the rewards struct; weights sum to 100.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct ScoringWeightsParams {
+    pub balance_weight: u8,
+    pub staking_weight: u8,
+    pub burn_weight: u8,
+    pub transaction_weight: u8,
+    pub affiliate_weight: u8,
+    pub governance_weight: u8,
+}
+
+/// Mirror of `FiapoRewards::WalletData` (return of `get_wallet_data`).
+/// Field order and types MUST match the rewards struct.
+#[derive(Debug, Clone, PartialEq, Eq, Default, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct WalletDataInfo {
+    pub balance: Balance,
+    pub staking_balance: Balance,
+    pub burn_volume: Balance,
+    pub affiliate_count: u32,
+    pub governance_score: u32,
+    pub total_score: u128,
+    pub last_updated: u64,
+}
+
 /// Helper for cross-contract calls to Rewards using build_call.
 /// Uses raw selectors because FiapoRewards methods are standalone #[ink(message)].
 pub struct RewardsCall;
@@ -36,4 +63,60 @@ impl RewardsCall {
             _ => Err(()),
         }
     }
+
+    /// Calls `get_wallet_data(wallet)` on the rewards contract.
+    pub fn get_wallet_data(rewards_addr: AccountId, wallet: AccountId) -> Option<WalletDataInfo> {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_wallet_data");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(rewards_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(wallet)
+            )
+            .returns::<WalletDataInfo>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(data)) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Calls `update_wallet_data(...)` on the rewards contract.
+    /// Returns `true` if the rewards contract accepted the update.
+    pub fn update_wallet_data(rewards_addr: AccountId, wallet: AccountId, data: &WalletDataInfo) -> bool {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("update_wallet_data");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(rewards_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(wallet)
+                    .push_arg(data.balance)
+                    .push_arg(data.staking_balance)
+                    .push_arg(data.burn_volume)
+                    .push_arg(data.affiliate_count)
+                    .push_arg(data.governance_score)
+            )
+            .returns::<Result<(), u8>>()
+            .try_invoke();
+
+        matches!(result, Ok(Ok(Ok(()))))
+    }
+
+    /// Replaces only the governance score of `wallet`, keeping the other ranking inputs.
+    pub fn sync_governance_score(rewards_addr: AccountId, wallet: AccountId, governance_score: u32) -> bool {
+        let mut data = Self::get_wallet_data(rewards_addr, wallet).unwrap_or_default();
+        data.governance_score = governance_score;
+        Self::update_wallet_data(rewards_addr, wallet, &data)
+    }
 }
@@ -4,9 +4,42 @@
 //! No OpenBrush dependency.
 
 use ink::prelude::vec::Vec;
-use fiapo_traits::AccountId;
+use fiapo_traits::{AccountId, Balance};
 use ink::env::DefaultEnvironment as Environment;
 
+/// Mirror of `FiapoStaking::PoolConfig` (argument of `set_pool_config`).
+/// Field order and types MUST match the staking struct (fieldless enums decode as u8).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct PoolConfigParams {
+    pub apy_bps: u16,
+    pub min_period_days: u32,
+    pub early_withdrawal_penalty_bps: u16,
+    pub cancellation_penalty_bps: u16,
+    pub payment_frequency_days: u32,
+    pub active: bool,
+    pub unbonding_days: u32,
+    pub instant_exit_fee_bps: u16,
+    /// Asset (0 = Fiapo, 1 = Lunes, 2 = Lusdt)
+    pub reward_asset: u8,
+    pub reward_units_per_fiapo: Balance,
+    /// Asset (0 = Fiapo, 2 = Lusdt; Lunes is not accepted)
+    pub fee_asset: u8,
+}
+
+/// Active ICO NFT reported to Staking for the APY boost.
+/// Tier and rarity use the ICO enum indexes (0 = Free / Common).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct NftHolding {
+    pub nft_id: u64,
+    pub tier: u8,
+    pub visual_rarity: u8,
+}
+
+/// Maximum NFTs per user kept by Staking for the boost
+pub const MAX_NFT_HOLDINGS: usize = 50;
+
 /// Reference type for cross-contract calls to Staking
 pub type StakingRef = ink::contract_ref!(Staking);
 
@@ -24,4 +57,47 @@ pub trait Staking {
     /// Gets the core contract address
     #[ink(message)]
     fn core_contract(&self) -> AccountId;
+
+    /// Number of accounts with at least one live position
+    #[ink(message)]
+    fn total_stakers(&self) -> u32;
+
+    /// veFIAPO voting power of an account at timestamp `at` (decays to zero at lock end)
+    #[ink(message)]
+    fn voting_power_of(&self, account: AccountId, at: u64) -> Balance;
+
+    /// Staked amount of an account at the end of block `block`
+    #[ink(message)]
+    fn staked_balance_at(&self, account: AccountId, block: u32) -> Balance;
+
+    /// Total staked across all pools at the end of block `block`
+    #[ink(message)]
+    fn total_staked_at(&self, block: u32) -> Balance;
+}
+
+/// Helper for calls to standalone (non-trait) Staking messages using build_call.
+pub struct StakingCall;
+
+impl StakingCall {
+    /// Calls `sync_nft_holdings(user, holdings)` on Staking (caller must be its ICO).
+    /// Returns `true` if Staking accepted the update.
+    pub fn sync_nft_holdings(staking_addr: AccountId, user: AccountId, holdings: Vec<NftHolding>) -> bool {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("sync_nft_holdings");
+
+        let result = build_call::<Environment>()
+            .call(staking_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(user)
+                    .push_arg(holdings)
+            )
+            .returns::<Result<(), u8>>()
+            .try_invoke();
+
+        matches!(result, Ok(Ok(Ok(()))))
+    }
 }
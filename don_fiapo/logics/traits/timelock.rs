@@ -0,0 +1,207 @@
+//! # Timelock Helper
+//!
+//! Pure ink! helper for cross-contract calls to the Timelock contract and for
+//! dispatching pre-encoded calls (selector + SCALE-encoded args).
+//! No OpenBrush dependency.
+//!
+//! NOTE: FiapoTimelock methods are standalone (not via trait), so we use
+//! build_call with explicit selectors for cross-contract calls.
+
+use fiapo_traits::{AccountId, Balance};
+use ink::prelude::{string::String, vec::Vec};
+
+/// Mirror of `fiapo_timelock::OperationType` (same variant order / SCALE encoding)
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OperationKind {
+    TransferOwnership,
+    ConfigChange,
+    ContractUpgrade,
+    SystemWalletChange,
+    TokenomicsChange,
+    FeeChange,
+    EmergencyAction,
+    Custom(String),
+}
+
+/// Mirror of `fiapo_timelock::OperationStatus` (same variant order / SCALE encoding)
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OperationState {
+    Scheduled,
+    ReadyToExecute,
+    Executed,
+    Cancelled,
+    Expired,
+}
+
+/// Already-encoded call arguments, appended verbatim to the selector
+pub struct RawCallInput<'a>(pub &'a [u8]);
+
+impl scale::Encode for RawCallInput<'_> {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.0);
+    }
+}
+
+/// Raw return value of a dispatched message, after the outer `MessageResult`.
+///
+/// ink! reverts a message that returns `Err`, but `try_invoke` still decodes the
+/// reverted output, so success is read from the encoded value itself: a unit
+/// return (empty) or an `Ok` tag (`0x00`). Pre-encoded calls must therefore
+/// target messages returning `()` or a `Result`.
+pub struct DispatchOutput(pub Vec<u8>);
+
+impl DispatchOutput {
+    /// `true` if the callee returned `()` or `Ok(..)`.
+    pub fn succeeded(&self) -> bool {
+        matches!(self.0.first(), None | Some(0))
+    }
+}
+
+impl scale::Decode for DispatchOutput {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let mut bytes = Vec::new();
+        while let Ok(byte) = input.read_byte() {
+            bytes.push(byte);
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// Dispatches `call_data` (4-byte selector followed by encoded args) to `target`.
+/// Returns `true` if the callee did not trap and did not return `Err`.
+pub fn dispatch_raw(target: AccountId, call_data: &[u8], value: Balance) -> bool {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+
+    if call_data.len() < 4 {
+        return false;
+    }
+    let mut selector = [0u8; 4];
+    selector.copy_from_slice(&call_data[..4]);
+
+    let result = build_call::<ink::env::DefaultEnvironment>()
+        .call(target)
+        .gas_limit(0)
+        .transferred_value(value)
+        .exec_input(
+            ExecutionInput::new(Selector::new(selector))
+                .push_arg(RawCallInput(&call_data[4..]))
+        )
+        .returns::<DispatchOutput>()
+        .try_invoke();
+
+    matches!(result, Ok(Ok(output)) if output.succeeded())
+}
+
+/// Helper for cross-contract calls to the Timelock using build_call.
+pub struct TimelockCall;
+
+impl TimelockCall {
+    /// Calls `schedule(...)` on the timelock. `delay` is in milliseconds; `None`
+    /// uses the timelock's default delay for `kind`.
+    /// Returns the operation id, or `None` if scheduling failed.
+    pub fn schedule(
+        timelock_addr: AccountId,
+        kind: OperationKind,
+        target: AccountId,
+        call_data: Vec<u8>,
+        value: Balance,
+        description: String,
+        delay: Option<u64>,
+    ) -> Option<u64> {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("schedule");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(timelock_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(kind)
+                    .push_arg(target)
+                    .push_arg(call_data)
+                    .push_arg(value)
+                    .push_arg(description)
+                    .push_arg(delay)
+            )
+            .returns::<Result<u64, u8>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(Ok(operation_id))) => Some(operation_id),
+            _ => None,
+        }
+    }
+
+    /// Calls `execute(operation_id)` on the timelock.
+    /// Returns `true` if the operation (and its dispatched call) succeeded.
+    pub fn execute(timelock_addr: AccountId, operation_id: u64) -> bool {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("execute");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(timelock_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(operation_id)
+            )
+            .returns::<Result<(), u8>>()
+            .try_invoke();
+
+        matches!(result, Ok(Ok(Ok(()))))
+    }
+
+    /// Calls `cancel(operation_id)` on the timelock (caller must be its scheduler or an admin).
+    pub fn cancel(timelock_addr: AccountId, operation_id: u64) -> bool {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("cancel");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(timelock_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(operation_id)
+            )
+            .returns::<Result<(), u8>>()
+            .try_invoke();
+
+        matches!(result, Ok(Ok(Ok(()))))
+    }
+
+    /// Calls `get_operation_status(operation_id)` on the timelock.
+    /// Returns `None` if the operation does not exist or the call failed.
+    pub fn operation_status(timelock_addr: AccountId, operation_id: u64) -> Option<OperationState> {
+        use ink::env::call::{build_call, ExecutionInput, Selector};
+
+        let selector = ink::selector_bytes!("get_operation_status");
+
+        let result = build_call::<ink::env::DefaultEnvironment>()
+            .call(timelock_addr)
+            .gas_limit(0)
+            .transferred_value(0)
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector))
+                    .push_arg(operation_id)
+            )
+            .returns::<Option<OperationState>>()
+            .try_invoke();
+
+        match result {
+            Ok(Ok(status)) => status,
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+//! # Treasury Type Mirrors
+//!
+//! SCALE mirrors of the FiapoTreasury payload types, used by governance to
+//! encode `create_grant` calls. No OpenBrush dependency.
+//!
+//! NOTE: FiapoTreasury methods are standalone (not via trait); governance
+//! dispatches them as pre-encoded proposal calls.
+
+use fiapo_traits::Balance;
+use ink::prelude::vec::Vec;
+
+/// Mirror of `fiapo_treasury::Milestone`.
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub struct MilestoneParams {
+    /// Unlock delay after the grant start (ms)
+    pub after: u64,
+    pub amount: Balance,
+}
+
+/// Mirror of `fiapo_treasury::VestingSchedule` (same variant order / SCALE encoding).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout))]
+pub enum VestingScheduleParams {
+    Milestones(Vec<MilestoneParams>),
+    Linear { cliff: u64, duration: u64 },
+}